@@ -0,0 +1,75 @@
+use once_cell::sync::Lazy;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::Notify;
+
+/// 後台任務跟蹤器
+///
+/// 事件分發和通知發送在獨立任務中進行；單次運行退出前需要等待這些任務完成，
+/// 否則 IP 變更的通知可能還沒發出進程就已退出
+#[derive(Default)]
+pub struct BackgroundTasks {
+    pending: AtomicUsize,
+    idle: Notify,
+}
+
+/// 任務結束（包括 panic）時減少計數
+pub struct PendingGuard(&'static BackgroundTasks);
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        if self.0.pending.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+impl BackgroundTasks {
+    /// 在後台運行任務並跟蹤其完成
+    ///
+    /// # 參數
+    ///
+    /// - `task`: 要運行的任務
+    pub fn spawn<F>(&'static self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let guard = self.track();
+        tokio::spawn(async move {
+            let _guard = guard;
+            task.await;
+        });
+    }
+
+    /// 跟蹤一項不在獨立任務中運行的後台工作（如排隊等待分發的事件），返回的守衛釋放時視為完成
+    pub fn track(&'static self) -> PendingGuard {
+        self.pending.fetch_add(1, Ordering::SeqCst);
+        PendingGuard(self)
+    }
+
+    /// 正在運行的後台任務數量
+    pub fn pending(&self) -> usize {
+        self.pending.load(Ordering::SeqCst)
+    }
+
+    /// 等待所有後台任務（包括等待期間新啟動的任務）完成
+    pub async fn wait_idle(&self) {
+        loop {
+            let notified = self.idle.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.pending() == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// 全局後台任務跟蹤器
+static BACKGROUND_TASKS: Lazy<BackgroundTasks> = Lazy::new(BackgroundTasks::default);
+
+/// 獲取全局後台任務跟蹤器
+pub fn background_tasks() -> &'static BackgroundTasks {
+    &BACKGROUND_TASKS
+}
@@ -1,10 +1,12 @@
-use crate::domain::config::DdnsConfig;
+use crate::domain::config::{ConfigId, DdnsConfig, NotificationSettings, RecordGroup};
 use crate::domain::error::DomainError;
 use crate::infrastructure::config::FileConfigRepository;
-use crate::application::events::{EventManager, EventType, EventData};
+use crate::application::events::{EventManager, Event};
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use log::{info, error};
+use std::collections::HashSet;
+use std::time::UNIX_EPOCH;
+use tokio::sync::{Mutex, RwLock};
+use log::{info, error, warn};
 use tokio::select;
 use tokio::time::{sleep, Duration};
 
@@ -14,10 +16,16 @@ pub struct ConfigService {
     config_repository: Arc<FileConfigRepository>,
     /// 配置緩存
     config_cache: Arc<RwLock<Vec<DdnsConfig>>>,
+    /// 記錄組緩存
+    group_cache: Arc<RwLock<Vec<RecordGroup>>>,
+    /// 通知設置緩存
+    notification_cache: Arc<RwLock<NotificationSettings>>,
     /// 事件管理器
     event_manager: Arc<EventManager>,
     /// 是否正在監視配置變更
     is_watching: Arc<RwLock<bool>>,
+    /// 寫入鎖，確保「讀取-修改-保存」過程不會交錯
+    write_lock: Mutex<()>,
 }
 
 impl ConfigService {
@@ -28,30 +36,27 @@ impl ConfigService {
         Self {
             config_repository,
             config_cache: Arc::new(RwLock::new(Vec::new())),
+            group_cache: Arc::new(RwLock::new(Vec::new())),
+            notification_cache: Arc::new(RwLock::new(NotificationSettings::default())),
             event_manager,
             is_watching: Arc::new(RwLock::new(false)),
+            write_lock: Mutex::new(()),
         }
     }
 
     /// 初始化配置服務
     pub async fn initialize(&self) -> Result<(), DomainError> {
         // 從文件加載配置
-        let configs = self.config_repository.load_configs().await?;
+        let document = self.config_repository.load_document().await?;
         
         // 如果沒有配置，嘗試創建默認配置
-        if configs.is_empty() {
+        if document.configs.is_empty() && document.groups.is_empty() {
             info!("沒有找到配置，將創建示例配置檔案");
             self.create_example_config().await?;
         }
         
         // 從文件重新加載配置
-        let configs = self.config_repository.load_configs().await?;
-        
-        // 更新配置緩存
-        {
-            let mut cache = self.config_cache.write().await;
-            *cache = configs;
-        }
+        self.load_document(true).await?;
         
         // 開始監視配置文件變更
         self.config_repository.start_watching().await?;
@@ -62,6 +67,48 @@ impl ConfigService {
         Ok(())
     }
 
+    /// 只讀加載配置
+    ///
+    /// 用於單次運行和試運行：不創建示例配置、不監視文件變更，缺少的 ID 只在內存中生成、不寫回文件
+    pub async fn load(&self) -> Result<(), DomainError> {
+        self.load_document(false).await
+    }
+    
+    /// 從文件加載配置並更新緩存
+    ///
+    /// # 參數
+    ///
+    /// - `persist_ids`: 是否將新生成的配置和記錄組 ID 寫回文件
+    async fn load_document(&self, persist_ids: bool) -> Result<(), DomainError> {
+        let document = self.config_repository.load_document().await?;
+        let mut configs = document.configs;
+        let mut groups = document.groups;
+        
+        // 更新通知設置緩存
+        {
+            let mut notifications = self.notification_cache.write().await;
+            *notifications = document.notifications;
+        }
+        
+        // 為缺少 ID 的配置生成 ID 並寫回文件
+        if assign_missing_ids(&mut configs, &[]) && persist_ids {
+            info!("為缺少 ID 的配置生成了新 ID");
+            self.config_repository.save_configs(&configs).await?;
+        }
+        if assign_missing_group_ids(&mut groups) && persist_ids {
+            info!("為缺少 ID 的記錄組生成了新 ID");
+            self.config_repository.save_groups(&groups).await?;
+        }
+        
+        // 更新配置緩存
+        {
+            let mut cache = self.config_cache.write().await;
+            *cache = configs;
+        }
+        *self.group_cache.write().await = groups;
+        Ok(())
+    }
+    
     /// 創建示例配置檔案
     async fn create_example_config(&self) -> Result<(), DomainError> {
         // 從環境變量獲取配置（如果存在）
@@ -77,12 +124,21 @@ impl ConfigService {
         // 如果沒有環境變量配置，則創建示例配置
         let example_config = vec![
             DdnsConfig {
+                id: ConfigId::generate(),
                 api_token: "your_cloudflare_api_token".to_string(),
                 zone_id: "your_cloudflare_zone_id".to_string(),
                 record_id: "your_cloudflare_record_id".to_string(),
                 record_name: "your.domain.com".to_string(),
                 update_interval: 300,
                 ip_type: crate::domain::config::IpType::IPv4,
+                hooks: Default::default(),
+                source: Default::default(),
+                ipv6_loss: Default::default(),
+                prefix_delegation: None,
+                record_type: None,
+                content: None,
+                failover: None,
+                drift_check_interval: None,
             }
         ];
         
@@ -109,12 +165,21 @@ impl ConfigService {
                 .unwrap_or(300);
 
             configs.push(DdnsConfig {
+                id: ConfigId::new("env-ipv4"),
                 api_token,
                 zone_id,
                 record_id,
                 record_name,
                 update_interval,
                 ip_type: crate::domain::config::IpType::IPv4,
+                hooks: Default::default(),
+                source: Default::default(),
+                ipv6_loss: Default::default(),
+                prefix_delegation: None,
+                record_type: None,
+                content: None,
+                failover: None,
+                drift_check_interval: None,
             });
         }
         
@@ -130,12 +195,21 @@ impl ConfigService {
                 .unwrap_or(300);
                 
             configs.push(DdnsConfig {
+                id: ConfigId::new("env-ipv6"),
                 api_token,
                 zone_id,
                 record_id,
                 record_name,
                 update_interval,
                 ip_type: crate::domain::config::IpType::IPv6,
+                hooks: Default::default(),
+                source: Default::default(),
+                ipv6_loss: Default::default(),
+                prefix_delegation: None,
+                record_type: None,
+                content: None,
+                failover: None,
+                drift_check_interval: None,
             });
         }
         
@@ -148,6 +222,32 @@ impl ConfigService {
         Ok(cache.clone())
     }
 
+    /// 獲取所有記錄組
+    pub async fn get_groups(&self) -> Vec<RecordGroup> {
+        self.group_cache.read().await.clone()
+    }
+    
+    /// 根據 ID 獲取單個記錄組
+    pub async fn get_group(&self, id: &ConfigId) -> Option<RecordGroup> {
+        self.group_cache.read().await.iter().find(|g| &g.id == id).cloned()
+    }
+    
+    /// 獲取需要運行更新任務的所有記錄配置
+    /// 
+    /// 包括單條記錄配置，以及記錄組展開後的每條記錄
+    pub async fn get_record_configs(&self) -> Result<Vec<DdnsConfig>, DomainError> {
+        let mut configs = self.get_configs().await?;
+        for group in self.group_cache.read().await.iter() {
+            configs.extend(group.expand());
+        }
+        Ok(configs)
+    }
+
+    /// 獲取通知設置
+    pub async fn get_notification_settings(&self) -> NotificationSettings {
+        self.notification_cache.read().await.clone()
+    }
+
     /// 獲取指定 IP 類型的配置
     pub async fn get_config_by_ip_type(&self, ip_type: &str) -> Result<Option<DdnsConfig>, DomainError> {
         let ip_type = crate::domain::config::IpType::try_from(ip_type)?;
@@ -160,12 +260,204 @@ impl ConfigService {
         Ok(config)
     }
 
+    /// 根據 ID 獲取單個配置
+    pub async fn get_config(&self, id: &ConfigId) -> Result<Option<DdnsConfig>, DomainError> {
+        let cache = self.config_cache.read().await;
+        Ok(cache.iter().find(|c| &c.id == id).cloned())
+    }
+    
+    /// 獲取整份配置的修訂標記
+    /// 
+    /// 基於配置文件的最後修改時間，文件被任何一方寫入後標記都會改變
+    pub async fn get_configs_revision(&self) -> String {
+        let nanos = self.config_repository.last_modified().await
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_nanos())
+            .unwrap_or(0);
+        format!("{:x}", nanos)
+    }
+
     /// 保存配置
     pub async fn save_configs(&self, configs: Vec<DdnsConfig>) -> Result<(), DomainError> {
+        self.save_configs_if_match(configs, None).await
+    }
+    
+    /// 在整份配置未被修改的前提下保存配置
+    /// 
+    /// # 參數
+    /// 
+    /// - `configs`: 新的完整配置列表
+    /// - `expected_revision`: 客戶端持有的修訂標記，為 None 時不檢查
+    pub async fn save_configs_if_match(&self, mut configs: Vec<DdnsConfig>, expected_revision: Option<&str>) -> Result<(), DomainError> {
+        let _guard = self.write_lock.lock().await;
+        
+        if let Some(expected) = expected_revision {
+            let current = self.get_configs_revision().await;
+            if !revision_matches(expected, &current) {
+                return Err(DomainError::conflict(format!(
+                    "配置已被修改 (當前版本: {}, 請求版本: {})", current, expected
+                )));
+            }
+        }
+        
+        // 沿用已有配置的 ID，並為新配置生成 ID
+        let existing = self.config_cache.read().await.clone();
+        assign_missing_ids(&mut configs, &existing);
+        
+        self.persist(configs).await
+    }
+    
+    /// 創建或替換單個配置
+    /// 
+    /// # 參數
+    /// 
+    /// - `id`: 配置 ID
+    /// - `config`: 新配置內容（其中的 ID 會被路徑中的 ID 覆蓋）
+    /// - `expected_revision`: 客戶端持有的修訂標記，為 None 時不檢查
+    /// 
+    /// # 返回
+    /// 
+    /// - `Result<(DdnsConfig, bool), DomainError>`: 保存後的配置及是否為新建
+    pub async fn put_config(&self, id: &ConfigId, mut config: DdnsConfig, expected_revision: Option<&str>) -> Result<(DdnsConfig, bool), DomainError> {
+        let _guard = self.write_lock.lock().await;
+        let mut configs = self.load_latest().await?;
+        
+        config.id = id.clone();
+        
+        let created = match configs.iter().position(|c| &c.id == id) {
+            Some(index) => {
+                check_revision(&configs[index], expected_revision)?;
+                configs[index] = config.clone();
+                false
+            },
+            None => {
+                if expected_revision.is_some() {
+                    return Err(DomainError::not_found(format!("配置 {} 不存在", id)));
+                }
+                configs.push(config.clone());
+                true
+            }
+        };
+        
+        self.persist(configs).await?;
+        Ok((config, created))
+    }
+    
+    /// 部分更新單個配置
+    /// 
+    /// 使用 JSON Merge Patch 語義，只修改請求中出現的欄位
+    pub async fn patch_config(&self, id: &ConfigId, patch: serde_json::Value, expected_revision: Option<&str>) -> Result<DdnsConfig, DomainError> {
+        let _guard = self.write_lock.lock().await;
+        let mut configs = self.load_latest().await?;
+        
+        let index = configs.iter().position(|c| &c.id == id)
+            .ok_or_else(|| DomainError::not_found(format!("配置 {} 不存在", id)))?;
+        check_revision(&configs[index], expected_revision)?;
+        
+        let mut value = serde_json::to_value(&configs[index])
+            .map_err(|e| DomainError::SerializationError(e.to_string()))?;
+        merge_patch(&mut value, patch);
+        
+        let mut patched: DdnsConfig = serde_json::from_value(value)
+            .map_err(|e| DomainError::validation(format!("無效的配置內容: {}", e)))?;
+        patched.id = id.clone();
+        
+        configs[index] = patched.clone();
+        self.persist(configs).await?;
+        Ok(patched)
+    }
+    
+    /// 刪除單個配置
+    /// 
+    /// # 返回
+    /// 
+    /// - `Result<DdnsConfig, DomainError>`: 被刪除的配置
+    pub async fn delete_config(&self, id: &ConfigId, expected_revision: Option<&str>) -> Result<DdnsConfig, DomainError> {
+        let _guard = self.write_lock.lock().await;
+        let mut configs = self.load_latest().await?;
+        
+        let index = configs.iter().position(|c| &c.id == id)
+            .ok_or_else(|| DomainError::not_found(format!("配置 {} 不存在", id)))?;
+        check_revision(&configs[index], expected_revision)?;
+        
+        let removed = configs.remove(index);
+        self.persist(configs).await?;
+        Ok(removed)
+    }
+    
+    /// 創建或替換單個記錄組
+    /// 
+    /// # 參數
+    /// 
+    /// - `id`: 記錄組 ID
+    /// - `group`: 新記錄組內容（其中的 ID 會被路徑中的 ID 覆蓋）
+    /// - `expected_revision`: 客戶端持有的修訂標記，為 None 時不檢查
+    /// 
+    /// # 返回
+    /// 
+    /// - `Result<(RecordGroup, bool), DomainError>`: 保存後的記錄組及是否為新建
+    pub async fn put_group(&self, id: &ConfigId, mut group: RecordGroup, expected_revision: Option<&str>) -> Result<(RecordGroup, bool), DomainError> {
+        let _guard = self.write_lock.lock().await;
+        let mut groups = self.config_repository.load_document().await?.groups;
+        assign_missing_group_ids(&mut groups);
+        
+        group.id = id.clone();
+        
+        let created = match groups.iter().position(|g| &g.id == id) {
+            Some(index) => {
+                check_group_revision(&groups[index], expected_revision)?;
+                groups[index] = group.clone();
+                false
+            },
+            None => {
+                if expected_revision.is_some() {
+                    return Err(DomainError::not_found(format!("記錄組 {} 不存在", id)));
+                }
+                groups.push(group.clone());
+                true
+            }
+        };
+        
+        self.persist_groups(groups).await?;
+        Ok((group, created))
+    }
+    
+    /// 刪除單個記錄組
+    /// 
+    /// # 返回
+    /// 
+    /// - `Result<RecordGroup, DomainError>`: 被刪除的記錄組
+    pub async fn delete_group(&self, id: &ConfigId, expected_revision: Option<&str>) -> Result<RecordGroup, DomainError> {
+        let _guard = self.write_lock.lock().await;
+        let mut groups = self.config_repository.load_document().await?.groups;
+        assign_missing_group_ids(&mut groups);
+        
+        let index = groups.iter().position(|g| &g.id == id)
+            .ok_or_else(|| DomainError::not_found(format!("記錄組 {} 不存在", id)))?;
+        check_group_revision(&groups[index], expected_revision)?;
+        
+        let removed = groups.remove(index);
+        self.persist_groups(groups).await?;
+        Ok(removed)
+    }
+    
+    /// 從文件讀取最新配置
+    /// 
+    /// 單筆修改前以文件內容為準，避免基於過期緩存覆蓋其他客戶端的修改
+    async fn load_latest(&self) -> Result<Vec<DdnsConfig>, DomainError> {
+        let mut configs = self.config_repository.load_configs().await?;
+        assign_missing_ids(&mut configs, &[]);
+        Ok(configs)
+    }
+    
+    /// 驗證並保存配置，更新緩存並發布變更事件
+    async fn persist(&self, configs: Vec<DdnsConfig>) -> Result<(), DomainError> {
         // 驗證配置
         for config in &configs {
             config.validate()?;
         }
+        let groups = self.group_cache.read().await.clone();
+        check_unique_ids(&configs, &groups)?;
         
         // 保存到文件
         self.config_repository.save_configs(&configs).await?;
@@ -176,15 +468,36 @@ impl ConfigService {
             *cache = configs;
         }
         
-        // 發布配置變更事件
-        self.event_manager.publish(EventData {
-            event_type: EventType::ConfigChanged,
-            data: None,
-        }).await;
+        self.publish_changed().await;
+        
+        Ok(())
+    }
+    
+    /// 驗證並保存記錄組，更新緩存並發布變更事件
+    async fn persist_groups(&self, groups: Vec<RecordGroup>) -> Result<(), DomainError> {
+        for group in &groups {
+            group.validate()?;
+        }
+        let configs = self.config_cache.read().await.clone();
+        check_unique_ids(&configs, &groups)?;
+        
+        self.config_repository.save_groups(&groups).await?;
+        *self.group_cache.write().await = groups;
+        
+        self.publish_changed().await;
         
         Ok(())
     }
     
+    /// 保存配置後發布配置變更事件
+    ///
+    /// 保存文件會通知配置監視任務，監視任務重新加載後發布事件；未監視時直接發布，避免同一次保存發布兩次
+    async fn publish_changed(&self) {
+        if !*self.is_watching.read().await {
+            self.event_manager.publish(Event::ConfigChanged).await;
+        }
+    }
+    
     /// 開始監視配置變更
     async fn start_config_watcher(&self) -> Result<(), DomainError> {
         let mut is_watching = self.is_watching.write().await;
@@ -196,6 +509,8 @@ impl ConfigService {
         
         let config_repository = self.config_repository.clone();
         let config_cache = self.config_cache.clone();
+        let group_cache = self.group_cache.clone();
+        let notification_cache = self.notification_cache.clone();
         let event_manager = self.event_manager.clone();
         let is_watching_clone = self.is_watching.clone();
         
@@ -217,19 +532,38 @@ impl ConfigService {
                         info!("Received config change notification");
                         
                         // 重新加載配置
-                        match config_repository.load_configs().await {
-                            Ok(new_configs) => {
+                        match config_repository.load_document().await {
+                            Ok(document) => {
+                                let mut new_configs = document.configs;
+                                let mut new_groups = document.groups;
+                                
+                                // 更新通知設置緩存
+                                {
+                                    let mut notifications = notification_cache.write().await;
+                                    *notifications = document.notifications;
+                                }
+                                
+                                // 手動編輯的文件可能缺少 ID，生成後寫回
+                                if assign_missing_ids(&mut new_configs, &[]) {
+                                    if let Err(e) = config_repository.save_configs(&new_configs).await {
+                                        warn!("Failed to persist generated config IDs: {}", e);
+                                    }
+                                }
+                                if assign_missing_group_ids(&mut new_groups) {
+                                    if let Err(e) = config_repository.save_groups(&new_groups).await {
+                                        warn!("Failed to persist generated group IDs: {}", e);
+                                    }
+                                }
+                                
                                 // 更新緩存
                                 {
                                     let mut cache = config_cache.write().await;
                                     *cache = new_configs;
                                 }
+                                *group_cache.write().await = new_groups;
                                 
                                 // 發布事件
-                                event_manager.publish(EventData {
-                                    event_type: EventType::ConfigChanged,
-                                    data: None,
-                                }).await;
+                                event_manager.publish(Event::ConfigChanged).await;
                                 
                                 info!("Config reloaded successfully");
                             },
@@ -262,4 +596,134 @@ impl ConfigService {
         
         info!("Config watchers will stop on next check");
     }
-} 
\ No newline at end of file
+} 
+
+/// 為缺少 ID 或 ID 重複的配置分配 ID
+/// 
+/// 優先沿用 `existing` 中指向同一條記錄的配置的 ID，否則生成新 ID
+/// 
+/// # 返回
+/// 
+/// - `bool`: 是否有配置被分配了新 ID
+fn assign_missing_ids(configs: &mut [DdnsConfig], existing: &[DdnsConfig]) -> bool {
+    let mut used: HashSet<ConfigId> = configs.iter()
+        .filter(|c| !c.id.is_empty())
+        .map(|c| c.id.clone())
+        .collect();
+    let mut seen = HashSet::new();
+    let mut changed = false;
+    
+    for config in configs.iter_mut() {
+        if !config.id.is_empty() && seen.insert(config.id.clone()) {
+            continue;
+        }
+        
+        let reused = existing.iter()
+            .find(|e| e.zone_id == config.zone_id
+                && e.record_id == config.record_id
+                && e.record_name == config.record_name
+                && e.ip_type == config.ip_type
+                && !used.contains(&e.id))
+            .map(|e| e.id.clone());
+        
+        config.id = reused.unwrap_or_else(|| loop {
+            let id = ConfigId::generate();
+            if !used.contains(&id) {
+                break id;
+            }
+        });
+        used.insert(config.id.clone());
+        seen.insert(config.id.clone());
+        changed = true;
+    }
+    
+    changed
+}
+
+/// 為缺少 ID 或 ID 重複的記錄組生成 ID
+/// 
+/// # 返回
+/// 
+/// - `bool`: 是否有記錄組被分配了新 ID
+fn assign_missing_group_ids(groups: &mut [RecordGroup]) -> bool {
+    let mut seen = HashSet::new();
+    let mut changed = false;
+    
+    for group in groups.iter_mut() {
+        if !group.id.is_empty() && seen.insert(group.id.clone()) {
+            continue;
+        }
+        group.id = ConfigId::generate();
+        seen.insert(group.id.clone());
+        changed = true;
+    }
+    
+    changed
+}
+
+/// 檢查配置 ID、記錄組 ID 及記錄組展開後的記錄 ID 是否唯一
+fn check_unique_ids(configs: &[DdnsConfig], groups: &[RecordGroup]) -> Result<(), DomainError> {
+    let mut ids = HashSet::new();
+    for config in configs {
+        if !ids.insert(config.id.clone()) {
+            return Err(DomainError::validation(format!("Duplicate config ID: {}", config.id)));
+        }
+    }
+    for group in groups {
+        if !ids.insert(group.id.clone()) {
+            return Err(DomainError::validation(format!("Duplicate group ID: {}", group.id)));
+        }
+        for member in group.expand() {
+            if !ids.insert(member.id.clone()) {
+                return Err(DomainError::validation(format!("Duplicate config ID: {}", member.id)));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 客戶端持有的修訂標記是否與當前標記一致，`*` 匹配任何已存在的資源
+fn revision_matches(expected: &str, current: &str) -> bool {
+    expected == "*" || expected == current
+}
+
+/// 檢查客戶端持有的修訂標記是否與當前記錄組一致
+fn check_group_revision(group: &RecordGroup, expected_revision: Option<&str>) -> Result<(), DomainError> {
+    match expected_revision {
+        Some(expected) if !revision_matches(expected, &group.revision()) => Err(DomainError::conflict(format!(
+            "記錄組 {} 已被修改 (當前版本: {}, 請求版本: {})", group.id, group.revision(), expected
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// 檢查客戶端持有的修訂標記是否與當前配置一致
+fn check_revision(config: &DdnsConfig, expected_revision: Option<&str>) -> Result<(), DomainError> {
+    match expected_revision {
+        Some(expected) if !revision_matches(expected, &config.revision()) => Err(DomainError::conflict(format!(
+            "配置 {} 已被修改 (當前版本: {}, 請求版本: {})", config.id, config.revision(), expected
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// 按 JSON Merge Patch (RFC 7396) 語義合併
+fn merge_patch(target: &mut serde_json::Value, patch: serde_json::Value) {
+    match patch {
+        serde_json::Value::Object(patch_map) => {
+            if !target.is_object() {
+                *target = serde_json::Value::Object(serde_json::Map::new());
+            }
+            if let serde_json::Value::Object(target_map) = target {
+                for (key, value) in patch_map {
+                    if value.is_null() {
+                        target_map.remove(&key);
+                    } else {
+                        merge_patch(target_map.entry(key).or_insert(serde_json::Value::Null), value);
+                    }
+                }
+            }
+        },
+        other => *target = other,
+    }
+}
@@ -1,3 +1,11 @@
+mod outcome;
+mod plan;
+mod scheduler;
 mod service;
+mod status;
 
-pub use service::DdnsApplicationService; 
\ No newline at end of file
+pub use outcome::UpdateOutcome;
+pub use plan::{PlanAction, PlanEntry};
+pub use scheduler::DetectionGroup;
+pub use service::DdnsApplicationService;
+pub use status::{DdnsRecordStatus, RecordHealth};
@@ -0,0 +1,50 @@
+use crate::domain::config::{ConfigId, DdnsConfig};
+use crate::domain::dns::DnsUpdateResult;
+use crate::domain::error::DomainError;
+use serde::Serialize;
+
+/// 單次運行中一條記錄的更新結果
+///
+/// # 欄位
+///
+/// - `content`: 更新成功時記錄的內容
+/// - `updated`: 是否實際寫入了 Cloudflare
+/// - `error`: 更新失敗時的錯誤信息
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateOutcome {
+    pub config_id: ConfigId,
+    pub record_name: String,
+    pub record_type: String,
+    pub content: Option<String>,
+    pub updated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl UpdateOutcome {
+    /// 根據更新結果創建
+    ///
+    /// # 參數
+    ///
+    /// - `config`: 記錄配置
+    /// - `result`: 更新結果
+    pub fn new(config: &DdnsConfig, result: &Result<DnsUpdateResult, DomainError>) -> Self {
+        let (content, updated, error) = match result {
+            Ok(update) => (Some(update.record.content.clone()), update.updated, None),
+            Err(e) => (None, false, Some(e.to_string())),
+        };
+        Self {
+            config_id: config.id.clone(),
+            record_name: config.record_name.clone(),
+            record_type: config.dns_record_type(),
+            content,
+            updated,
+            error,
+        }
+    }
+
+    /// 更新是否失敗
+    pub fn failed(&self) -> bool {
+        self.error.is_some()
+    }
+}
@@ -0,0 +1,54 @@
+use crate::domain::config::ConfigId;
+use serde::Serialize;
+use std::fmt;
+
+/// 試運行時對單條記錄將執行的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlanAction {
+    /// 記錄不存在，將創建
+    Create,
+    /// 記錄內容與期望不一致，將更新
+    Update,
+    /// 記錄內容已是期望值，無需操作
+    Noop,
+    /// 無法確定期望內容或讀取記錄失敗
+    Error,
+}
+
+impl fmt::Display for PlanAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanAction::Create => write!(f, "create"),
+            PlanAction::Update => write!(f, "update"),
+            PlanAction::Noop => write!(f, "noop"),
+            PlanAction::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// 單條記錄的試運行計劃
+///
+/// # 欄位
+///
+/// - `current_content`: Cloudflare 上記錄的當前內容（記錄不存在或讀取失敗時為 None）
+/// - `desired_content`: 本次更新將寫入的內容（無法確定時為 None）
+/// - `error`: `action` 為 `error` 時的錯誤信息
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanEntry {
+    pub config_id: ConfigId,
+    pub record_name: String,
+    pub record_type: String,
+    pub current_content: Option<String>,
+    pub desired_content: Option<String>,
+    pub action: PlanAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl PlanEntry {
+    /// 是否需要寫入 Cloudflare
+    pub fn has_changes(&self) -> bool {
+        matches!(self.action, PlanAction::Create | PlanAction::Update)
+    }
+}
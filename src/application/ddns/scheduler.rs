@@ -0,0 +1,122 @@
+use crate::domain::config::{ConfigId, IpType, SourceSettings};
+use crate::domain::dns::DnsUpdateResult;
+use crate::domain::error::DomainError;
+use super::outcome::UpdateOutcome;
+use super::service::DdnsApplicationService;
+use futures::future::join_all;
+use log::{debug, info};
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// 共用 IP 檢測的記錄組
+///
+/// 檢測來源和更新間隔相同的記錄歸為一組，每次到期時每個協議族只檢測一次 IP，
+/// 再將結果分發給組內該協議族的所有記錄。不同間隔的組使用同一個帶緩存的 IP 服務，
+/// 同時到期時（如 300 秒和 600 秒的組在第 600 秒）也只觸發一次外部查詢
+pub struct DetectionGroup {
+    source: SourceSettings,
+    update_interval: u64,
+    services: Vec<DdnsApplicationService>,
+}
+
+impl DetectionGroup {
+    /// 按檢測來源和更新間隔將記錄分組
+    ///
+    /// # 參數
+    ///
+    /// - `services`: DDNS 應用服務列表
+    ///
+    /// # 返回
+    ///
+    /// - `Vec<DetectionGroup>`: 按首次出現順序排列的記錄組
+    pub fn group(services: Vec<DdnsApplicationService>) -> Vec<DetectionGroup> {
+        let mut groups: Vec<DetectionGroup> = Vec::new();
+        for service in services {
+            let config = service.config();
+            match groups.iter_mut().find(|group| {
+                group.source == config.source && group.update_interval == config.update_interval
+            }) {
+                Some(group) => group.services.push(service),
+                None => groups.push(DetectionGroup {
+                    source: config.source.clone(),
+                    update_interval: config.update_interval,
+                    services: vec![service],
+                }),
+            }
+        }
+        groups
+    }
+
+    /// 組內記錄的配置 ID
+    pub fn config_ids(&self) -> Vec<ConfigId> {
+        self.services.iter().map(|service| service.config_id().clone()).collect()
+    }
+
+    /// 更新間隔
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.update_interval)
+    }
+
+    /// 檢測一次 IP 並更新組內所有記錄
+    ///
+    /// # 返回
+    ///
+    /// - `Duration`: 距下一次更新需要等待的時間（服務端要求限流時可能長於更新間隔）
+    pub async fn run_once(&self) -> Duration {
+        let mut wait = self.interval();
+        for (service, result) in self.update_records().await {
+            // 服務端要求限流時，至少等待其指定的時間
+            if let Some(retry_after) = service.report_update_result(&result) {
+                wait = wait.max(retry_after);
+            }
+        }
+        wait
+    }
+
+    /// 檢測一次 IP 並更新組內所有記錄，返回每條記錄的結果
+    ///
+    /// 供單次運行使用，不記錄限流等待時間
+    pub async fn update_once(&self) -> Vec<UpdateOutcome> {
+        self.update_records().await
+            .into_iter()
+            .map(|(service, result)| UpdateOutcome::new(service.config(), &result))
+            .collect()
+    }
+
+    /// 每個協議族檢測一次 IP，並將結果分發給該協議族的所有記錄
+    async fn update_records(&self) -> Vec<(&DdnsApplicationService, Result<DnsUpdateResult, DomainError>)> {
+        let mut results = Vec::with_capacity(self.services.len());
+        for ip_type in [IpType::IPv4, IpType::IPv6] {
+            let services: Vec<_> = self.services.iter()
+                .filter(|service| service.config().ip_type == ip_type)
+                .collect();
+            let Some(first) = services.first() else {
+                continue;
+            };
+
+            // 組內記錄的檢測來源相同，使用第一條記錄的 IP 服務檢測
+            let detected = first.get_current_ip_for_api().await;
+            debug!("Detected {} address for {} records: {:?}", ip_type, services.len(), detected);
+
+            results.extend(join_all(services.into_iter().map(|service| {
+                let detected = detected.clone();
+                async move { (service, service.apply_detected_ip(detected).await) }
+            })).await);
+        }
+        results
+    }
+
+    /// 按更新間隔循環檢測並更新組內記錄
+    pub async fn run(self) {
+        info!("Starting DDNS auto-update for {} records, update interval: {} seconds",
+              self.services.len(), self.update_interval);
+
+        loop {
+            let wait = self.run_once().await;
+
+            // 等待下一次更新
+            debug!("Waiting {} seconds for next update", wait.as_secs());
+            sleep(wait).await;
+        }
+    }
+}
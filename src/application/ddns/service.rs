@@ -1,14 +1,46 @@
-use crate::domain::config::DdnsConfig;
-use crate::domain::dns::{DnsRecord, DnsService, DnsUpdateResult};
+use crate::domain::config::{ConfigId, DdnsConfig, HookCommand, IpType, Ipv6LossAction};
+use crate::domain::dns::{render_template, template_placeholders, DnsRecord, DnsService, DnsUpdateResult, TemplateContext};
 use crate::domain::error::DomainError;
+use crate::domain::failover::{FailoverState, FailoverTarget, HealthProbe};
+use crate::domain::hook::{HookOutput, HookRunner, HookStage};
 use crate::domain::ip::IpService;
 use crate::domain::state::StateRepository;
-use std::sync::Arc;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use chrono::{Utc, DateTime};
-use log::{info, error, debug};
+use log::{info, error, debug, warn};
+use super::plan::{PlanAction, PlanEntry};
+use super::status::DdnsRecordStatus;
+use crate::application::events::{
+    DriftDetectedEvent, Event, EventManager, FailoverSwitchedEvent, HookExecutedEvent, IpChangedEvent, RecordRef,
+    RecordWithdrawnEvent, UpdateFailedEvent, UpdateSucceededEvent,
+};
 use std::time::Duration as StdDuration;
 use tokio::time::sleep;
 
+/// 輸出帶記錄上下文（配置 ID、記錄名稱、IP 類型）的結構化日誌
+///
+/// 用法：`record_log!(warn, self.config, event = "...", error_kind = e.kind(); "message", args)`
+macro_rules! record_log {
+    ($level:ident, $config:expr $(, $key:ident $(:$capture:tt)? = $value:expr)*; $($arg:tt)+) => {
+        $level!(
+            config_id:% = $config.id, record_name = $config.record_name.as_str(), ip_type:% = $config.ip_type
+            $(, $key $(:$capture)? = $value)*; $($arg)+
+        )
+    };
+}
+
+/// 記錄地址的變更
+///
+/// 配置了內容模板時記錄內容不是地址本身，IP 變更事件和鉤子使用地址而不是記錄內容
+struct AddressChange {
+    /// 更新前的地址（首次更新時為 None）
+    old: Option<String>,
+    /// 新地址
+    new: String,
+}
+
 /// DDNS 應用服務
 #[derive(Clone)]
 pub struct DdnsApplicationService {
@@ -16,6 +48,19 @@ pub struct DdnsApplicationService {
     ip_service: Arc<dyn IpService>,
     state_repository: Arc<dyn StateRepository>,
     config: DdnsConfig,
+    event_manager: Option<Arc<EventManager>>,
+    hook_runner: Option<Arc<dyn HookRunner>>,
+    /// 按名稱查找到的記錄 ID（配置中未設置記錄 ID 時使用）
+    resolved_record_id: Arc<Mutex<Option<String>>>,
+    /// 連續 IP 檢測失敗次數
+    detection_failures: Arc<AtomicU32>,
+    /// AAAA 記錄是否已因 IPv6 連通性消失而撤回
+    withdrawn: Arc<AtomicBool>,
+    health_probe: Option<Arc<dyn HealthProbe>>,
+    /// 故障轉移狀態（首次檢查時根據記錄是否已指向備用地址初始化）
+    failover_state: Arc<Mutex<Option<FailoverState>>>,
+    /// 上次檢查或寫入記錄的時間，用於按間隔進行漂移檢查
+    last_drift_check: Arc<Mutex<Option<DateTime<Utc>>>>,
 }
 
 impl DdnsApplicationService {
@@ -38,14 +83,57 @@ impl DdnsApplicationService {
             ip_service,
             state_repository,
             config,
+            event_manager: None,
+            hook_runner: None,
+            resolved_record_id: Arc::new(Mutex::new(None)),
+            detection_failures: Arc::new(AtomicU32::new(0)),
+            withdrawn: Arc::new(AtomicBool::new(false)),
+            health_probe: None,
+            failover_state: Arc::new(Mutex::new(None)),
+            last_drift_check: Arc::new(Mutex::new(None)),
         }
     }
     
+    /// 設置事件管理器，用於發布 IP 變更及更新結果事件
+    ///
+    /// # 參數
+    ///
+    /// - `event_manager`: 事件管理器
+    pub fn with_event_manager(mut self, event_manager: Arc<EventManager>) -> Self {
+        self.event_manager = Some(event_manager);
+        self
+    }
+    
+    /// 設置鉤子執行器，用於執行配置中的更新鉤子
+    ///
+    /// # 參數
+    ///
+    /// - `hook_runner`: 鉤子執行器
+    pub fn with_hook_runner(mut self, hook_runner: Arc<dyn HookRunner>) -> Self {
+        self.hook_runner = Some(hook_runner);
+        self
+    }
+    
+    /// 設置健康檢查執行器，用於配置中的故障轉移
+    ///
+    /// # 參數
+    ///
+    /// - `health_probe`: 健康檢查執行器
+    pub fn with_health_probe(mut self, health_probe: Arc<dyn HealthProbe>) -> Self {
+        self.health_probe = Some(health_probe);
+        self
+    }
+    
     /// 獲取配置
     pub fn config(&self) -> &DdnsConfig {
         &self.config
     }
     
+    /// 獲取配置 ID
+    pub fn config_id(&self) -> &ConfigId {
+        &self.config.id
+    }
+    
     /// 強制更新 DNS 記錄
     ///
     /// # 返回
@@ -67,34 +155,66 @@ impl DdnsApplicationService {
     
     /// 為 API 獲取最後更新時間 (供 Status API 使用)
     ///
-    /// # 參數
-    ///
-    /// - `config_id`: 配置 ID
-    ///
     /// # 返回
     ///
     /// - `Result<Option<DateTime<Utc>>, DomainError>`: 成功時返回最後更新時間，失敗時返回錯誤
-    pub async fn get_last_update_for_api(&self, config_id: &str) -> Result<Option<DateTime<Utc>>, DomainError> {
-        self.state_repository.get_last_update_time(config_id).await
+    pub async fn get_last_update_for_api(&self) -> Result<Option<DateTime<Utc>>, DomainError> {
+        self.state_repository.get_last_update_time(&self.config.id).await
     }
     
-    /// 為 API 獲取最後的 IP 地址 (可以是曾經更新過的或當前的)
+    /// 獲取記錄狀態快照 (供 Status API 使用)
     ///
-    /// # 參數
+    /// 僅讀取狀態存儲，不會觸發 IP 查詢
+    ///
+    /// # 返回
     ///
-    /// - `config_id`: 配置 ID
+    /// - `Result<DdnsRecordStatus, DomainError>`: 成功時返回狀態快照，失敗時返回錯誤
+    pub async fn status_snapshot(&self) -> Result<DdnsRecordStatus, DomainError> {
+        let state = self.state_repository.get_state(&self.config.id).await?.unwrap_or_default();
+        
+        Ok(DdnsRecordStatus {
+            config_id: self.config.id.clone(),
+            record_name: self.config.record_name.clone(),
+            ip_type: self.config.ip_type.clone(),
+            health: DdnsRecordStatus::compute_health(state.last_check_time, state.last_error.as_deref()),
+            next_check: DdnsRecordStatus::compute_next_check(state.last_check_time, self.config.update_interval),
+            last_ip: state.last_ip,
+            last_update: state.last_update_time,
+            last_check: state.last_check_time,
+            last_error: state.last_error,
+            last_error_time: state.last_error_time,
+            last_success: state.last_success_time,
+        })
+    }
+    
+    /// 為 API 獲取最後的 IP 地址 (可以是曾經更新過的或當前的)
     ///
     /// # 返回
     ///
     /// - `Result<String, DomainError>`: 成功時返回 IP 地址，失敗時返回錯誤
-    pub async fn get_last_or_current_ip(&self, config_id: &str) -> Result<String, DomainError> {
+    pub async fn get_last_or_current_ip(&self) -> Result<String, DomainError> {
         // 先嘗試獲取最後更新的IP
-        if let Ok(Some(last_ip)) = self.state_repository.get_last_ip(config_id).await {
+        if let Ok(Some(last_ip)) = self.state_repository.get_last_ip(&self.config.id).await {
             return Ok(last_ip);
         }
         
         // 如果沒有最後更新的IP，獲取當前IP
-        self.get_current_ip().await
+        let detected = self.get_current_ip().await?;
+        self.record_address(detected)
+    }
+    
+    /// 由檢測到的 IP 得到記錄地址
+    ///
+    /// 配置了前綴委派時將檢測到的前綴與主機後綴組合，否則直接使用檢測到的 IP
+    ///
+    /// # 參數
+    ///
+    /// - `detected`: 檢測到的 IP
+    fn record_address(&self, detected: String) -> Result<String, DomainError> {
+        match &self.config.prefix_delegation {
+            Some(prefix_delegation) => prefix_delegation.apply(&detected),
+            None => Ok(detected),
+        }
     }
     
     /// 獲取當前 IP 地址
@@ -110,88 +230,775 @@ impl DdnsApplicationService {
         }
     }
     
-    /// 檢查 IP 是否變更
+    /// 更新 DNS 記錄
+    ///
+    /// # 返回
+    ///
+    /// - `Result<DnsUpdateResult, DomainError>`: 成功時返回更新結果，失敗時返回錯誤
+    pub async fn update_dns_record(&self) -> Result<DnsUpdateResult, DomainError> {
+        self.run_update(None).await
+    }
+    
+    /// 使用已檢測到的 IP 更新 DNS 記錄
+    ///
+    /// 供檢測調度器將一次檢測的結果分發給多條記錄，檢測失敗時同樣記錄為本記錄的檢查失敗
+    ///
+    /// # 參數
+    ///
+    /// - `detected`: IP 檢測結果
+    ///
+    /// # 返回
+    ///
+    /// - `Result<DnsUpdateResult, DomainError>`: 成功時返回更新結果，失敗時返回錯誤
+    pub async fn apply_detected_ip(&self, detected: Result<String, DomainError>) -> Result<DnsUpdateResult, DomainError> {
+        self.run_update(Some(detected)).await
+    }
+    
+    /// 本地沒有上次更新的內容時，以 Cloudflare 上記錄的當前內容作為上次更新的內容
+    ///
+    /// 單次運行的進程不保留狀態，先讀取記錄可避免每次運行都重寫未變化的記錄並發送 IP 變更通知；
+    /// 記錄不存在時不做處理，更新時會創建記錄
+    pub async fn load_last_content(&self) -> Result<(), DomainError> {
+        if self.state_repository.get_last_ip(&self.config.id).await?.is_some() {
+            return Ok(());
+        }
+        let Some(record_id) = self.record_id().await? else {
+            return Ok(());
+        };
+        let record = self.dns_service.get_record(&self.config.zone_id, &record_id).await?;
+        debug!("Loaded current content of {} from Cloudflare: {}", self.config.record_name, record.content);
+        self.state_repository.set_last_ip(&self.config.id, &record.content).await
+    }
+    
+    /// 試運行：計算本次更新的期望內容並與 Cloudflare 上的記錄比較，不寫入任何記錄或本地狀態
+    ///
+    /// 與實際更新一樣會檢測 IP、應用前綴委派、故障轉移和內容模板；故障轉移的健康檢查會實際執行，
+    /// 但不改變故障轉移狀態
     ///
     /// # 參數
     ///
-    /// - `current_ip`: 當前 IP 地址
+    /// - `detected`: 已檢測到的 IP，為 None 時由本服務檢測
     ///
     /// # 返回
     ///
-    /// - `Result<bool, DomainError>`: 成功時返回是否變更，失敗時返回錯誤
-    async fn is_ip_changed(&self, current_ip: &str) -> Result<bool, DomainError> {
-        let config_id = format!("{}-{}", self.config.zone_id, self.config.record_id);
-        let last_ip = self.state_repository.get_last_ip(&config_id).await?;
+    /// - `PlanEntry`: 記錄的當前內容、期望內容和將執行的操作，出錯時操作為 `error`
+    pub async fn plan(&self, detected: Option<Result<String, DomainError>>) -> PlanEntry {
+        let mut entry = PlanEntry {
+            config_id: self.config.id.clone(),
+            record_name: self.config.record_name.clone(),
+            record_type: self.config.dns_record_type(),
+            current_content: None,
+            desired_content: None,
+            action: PlanAction::Error,
+            error: None,
+        };
         
-        match last_ip {
-            Some(ip) => Ok(ip != current_ip),
-            None => Ok(true), // 沒有記錄，視為變更
+        match self.plan_contents(detected).await {
+            Ok((current, desired)) => {
+                entry.action = match current.as_deref() {
+                    None => PlanAction::Create,
+                    Some(current) if current == desired => PlanAction::Noop,
+                    Some(_) => PlanAction::Update,
+                };
+                entry.current_content = current;
+                entry.desired_content = Some(desired);
+            },
+            Err(e) => {
+                record_log!(warn, self.config, event = "plan-failed", error_kind = e.kind();
+                            "Failed to plan update for {}: {}", self.config.record_name, e);
+                entry.error = Some(e.to_string());
+            }
         }
+        entry
     }
     
-    /// 更新 DNS 記錄
+    /// 讀取記錄的當前內容並計算期望內容
     ///
     /// # 返回
     ///
-    /// - `Result<DnsUpdateResult, DomainError>`: 成功時返回更新結果，失敗時返回錯誤
-    pub async fn update_dns_record(&self) -> Result<DnsUpdateResult, DomainError> {
+    /// - `Result<(Option<String>, String), DomainError>`: 成功時返回當前內容（記錄不存在時為 None）和期望內容
+    async fn plan_contents(&self, detected: Option<Result<String, DomainError>>) -> Result<(Option<String>, String), DomainError> {
+        let detected = match detected {
+            Some(detected) => detected,
+            None => self.get_current_ip().await,
+        };
+        let primary = detected.and_then(|ip| self.record_address(ip));
+        let address = self.preview_failover_address(primary).await?;
+        let desired = self.render_content(address).await?;
+        
+        let current = match self.record_id().await? {
+            Some(record_id) => Some(self.dns_service.get_record(&self.config.zone_id, &record_id).await?.content),
+            None => None,
+        };
+        Ok((current, desired))
+    }
+    
+    /// 執行一次更新並記錄檢查結果、發布事件
+    ///
+    /// # 參數
+    ///
+    /// - `detected`: 已檢測到的 IP，為 None 時由本服務檢測
+    #[tracing::instrument(
+        name = "ddns.update_dns_record",
+        skip_all,
+        fields(
+            config_id = %self.config.id,
+            record_name = %self.config.record_name,
+            ip_type = %self.config.ip_type,
+            updated = tracing::field::Empty,
+        ),
+        err(Display),
+    )]
+    async fn run_update(&self, detected: Option<Result<String, DomainError>>) -> Result<DnsUpdateResult, DomainError> {
+        let result = self.sync_dns_record(detected).await;
+        if let Ok((update, _)) = &result {
+            tracing::Span::current().record("updated", update.updated);
+        }
+        
+        // 記錄檢查結果供狀態查詢使用
+        let error = result.as_ref().err().map(|e| e.to_string());
+        if let Err(e) = self.state_repository.set_check_result(&self.config.id, Utc::now(), error).await {
+            record_log!(warn, self.config, error_kind = e.kind();
+                        "Failed to record check result for {}: {}", self.config.record_name, e);
+        }
+        
+        self.publish_result_events(&result).await;
+        
+        result.map(|(result, _)| result)
+    }
+    
+    /// 根據更新結果發布事件
+    ///
+    /// # 參數
+    ///
+    /// - `result`: 同步結果及地址變更
+    async fn publish_result_events(&self, result: &Result<(DnsUpdateResult, Option<AddressChange>), DomainError>) {
+        match result {
+            Ok((update, address_change)) if update.updated => {
+                // 修正記錄漂移或只有模板內容變化時地址並未變更，只發布更新成功事件
+                if let Some(change) = address_change {
+                    self.publish(Event::IpChanged(IpChangedEvent {
+                        record: RecordRef::from(&self.config),
+                        old_ip: change.old.clone(),
+                        new_ip: change.new.clone(),
+                        timestamp: Utc::now(),
+                    })).await;
+                }
+                self.publish(Event::UpdateSucceeded(UpdateSucceededEvent {
+                    record: RecordRef::from(&self.config),
+                    ip: update.record.content.clone(),
+                    timestamp: Utc::now(),
+                })).await;
+            },
+            Ok(_) => {},
+            Err(e) => {
+                self.publish(Event::UpdateFailed(UpdateFailedEvent::from_error(RecordRef::from(&self.config), e))).await;
+            },
+        }
+    }
+    
+    /// 發布事件（未設置事件管理器時忽略）
+    async fn publish(&self, event: Event) {
+        if let Some(event_manager) = &self.event_manager {
+            event_manager.publish(event).await;
+        }
+    }
+    
+    /// 是否到了漂移檢查的時間，是則記錄本次檢查時間
+    ///
+    /// 漂移檢查需要查詢 Cloudflare，按配置的間隔進行而不是每次檢查都查詢
+    fn drift_check_due(&self) -> bool {
+        let Some(interval) = self.config.drift_check_interval() else {
+            return false;
+        };
+        let now = Utc::now();
+        let mut last_drift_check = self.last_drift_check.lock().unwrap();
+        if last_drift_check.is_some_and(|last| now - last < interval) {
+            return false;
+        }
+        *last_drift_check = Some(now);
+        true
+    }
+    
+    /// 檢查 DNS 服務商上的記錄是否與本地記錄的 IP 一致
+    ///
+    /// # 參數
+    ///
+    /// - `expected_ip`: 本地記錄的 IP
+    ///
+    /// # 返回
+    ///
+    /// - `Option<String>`: 記錄發生漂移時返回實際內容，一致或查詢失敗時返回 None
+    async fn detect_drift(&self, expected_ip: &str) -> Option<String> {
+        let record = match self.record_id().await {
+            Ok(Some(record_id)) => self.dns_service.get_record(&self.config.zone_id, &record_id).await,
+            // 記錄已被刪除時視為漂移，重新創建
+            Ok(None) => return Some(String::new()),
+            Err(e) => Err(e),
+        };
+        match record {
+            Ok(record) if record.content != expected_ip => Some(record.content),
+            Ok(_) => None,
+            Err(e) => {
+                // 查詢失敗不影響本次檢查結果
+                record_log!(warn, self.config, event = "drift-check", error_kind = e.kind();
+                            "Failed to fetch {} DNS record for drift check: {}", self.config.record_name, e);
+                None
+            }
+        }
+    }
+    
+    /// 獲取要更新的記錄 ID
+    ///
+    /// 配置中設置了記錄 ID 時直接使用；否則（如記錄組展開的配置）按名稱和類型查找並緩存
+    ///
+    /// # 返回
+    ///
+    /// - `Result<Option<String>, DomainError>`: 記錄不存在時返回 None
+    async fn record_id(&self) -> Result<Option<String>, DomainError> {
+        if !self.config.record_id.is_empty() {
+            return Ok(Some(self.config.record_id.clone()));
+        }
+        if let Some(record_id) = self.resolved_record_id.lock().unwrap().clone() {
+            return Ok(Some(record_id));
+        }
+        
+        let record_type = self.config.dns_record_type();
+        let records = self.dns_service
+            .find_records(&self.config.zone_id, &self.config.record_name, &record_type)
+            .await?;
+        let record_id = records.into_iter().find_map(|record| record.id);
+        if let Some(record_id) = &record_id {
+            debug!("Resolved {} record {} to ID {}", record_type, self.config.record_name, record_id);
+            *self.resolved_record_id.lock().unwrap() = Some(record_id.clone());
+        }
+        Ok(record_id)
+    }
+    
+    /// 寫入 DNS 記錄，按名稱查找的記錄不存在時創建
+    ///
+    /// # 參數
+    ///
+    /// - `record`: 要寫入的記錄內容
+    async fn write_record(&self, mut record: DnsRecord) -> Result<DnsUpdateResult, DomainError> {
+        let Some(record_id) = self.record_id().await? else {
+            record_log!(info, self.config, event = "record-created";
+                        "Creating {} DNS record {}", record.record_type, self.config.record_name);
+            record.id = None;
+            let created = self.dns_service.create_record(&self.config.zone_id, record).await?;
+            *self.resolved_record_id.lock().unwrap() = created.id.clone();
+            return Ok(DnsUpdateResult { record: created, updated: true });
+        };
+        
+        record.id = Some(record_id);
+        let result = self.dns_service.update_record(record).await;
+        if result.is_err() && self.config.record_id.is_empty() {
+            // 記錄可能已在 Cloudflare 上被刪除，下次重新查找
+            *self.resolved_record_id.lock().unwrap() = None;
+        }
+        result
+    }
+    
+    /// 記錄一次 IP 檢測失敗，IPv6 連續失敗達到閾值時按配置撤回 AAAA 記錄
+    ///
+    /// 撤回失敗時下一次檢測失敗會重試；IPv6 恢復後正常更新流程會重新創建或改回記錄
+    async fn on_detection_failure(&self) {
+        let failures = self.detection_failures.fetch_add(1, Ordering::Relaxed).saturating_add(1);
+        let settings = &self.config.ipv6_loss;
+        if self.config.ip_type != IpType::IPv6
+            || settings.action == Ipv6LossAction::Keep
+            || failures < settings.after_failures
+            || self.withdrawn.load(Ordering::Relaxed)
+        {
+            return;
+        }
+        
+        match self.withdraw_record().await {
+            Ok(previous_ip) => {
+                self.withdrawn.store(true, Ordering::Relaxed);
+                record_log!(warn, self.config, event = "record-withdrawn", action:% = settings.action;
+                            "IPv6 detection failed {} times in a row, withdrew AAAA record {} ({})", failures, self.config.record_name, settings.action);
+                self.publish(Event::RecordWithdrawn(RecordWithdrawnEvent {
+                    record: RecordRef::from(&self.config),
+                    action: settings.action,
+                    previous_ip,
+                    failures,
+                    timestamp: Utc::now(),
+                })).await;
+            },
+            Err(e) => {
+                record_log!(error, self.config, event = "record-withdrawn", error_kind = e.kind();
+                            "Failed to withdraw AAAA record {}: {}", self.config.record_name, e);
+            }
+        }
+    }
+    
+    /// 刪除 AAAA 記錄或改為停放地址，並更新本地狀態
+    ///
+    /// # 返回
+    ///
+    /// - `Result<Option<String>, DomainError>`: 成功時返回撤回前記錄的 IP
+    async fn withdraw_record(&self) -> Result<Option<String>, DomainError> {
+        let mut state = self.state_repository.get_state(&self.config.id).await?.unwrap_or_default();
+        let previous_ip = state.last_ip.clone();
+        
+        match self.config.ipv6_loss.action {
+            Ipv6LossAction::Keep => return Ok(previous_ip),
+            Ipv6LossAction::Delete => {
+                if let Some(record_id) = self.record_id().await? {
+                    self.dns_service.delete_record(&self.config.zone_id, &record_id).await?;
+                }
+                *self.resolved_record_id.lock().unwrap() = None;
+                // 清除本地 IP，恢復時視為 IP 變更並重新創建記錄
+                state.last_ip = None;
+            },
+            Ipv6LossAction::Park => {
+                let park_address = self.config.ipv6_loss.park_address.to_string();
+                let record = DnsRecord {
+                    id: Some(self.config.record_id.clone()),
+                    name: self.config.record_name.clone(),
+                    record_type: self.config.dns_record_type(),
+                    content: park_address.clone(),
+                    ttl: 120,
+                    proxied: false,
+                };
+                self.write_record(record).await?;
+                state.last_ip = Some(park_address);
+            },
+        }
+        
+        state.last_address = None;
+        state.last_update_time = Some(Utc::now());
+        self.state_repository.set_state(&self.config.id, state).await?;
+        Ok(previous_ip)
+    }
+    
+    /// 按故障轉移狀態選擇記錄地址
+    ///
+    /// 未配置故障轉移時直接返回主目標；否則檢查主目標健康狀態（主目標不可檢測視為不健康），
+    /// 當前目標為備用地址時返回備用地址
+    ///
+    /// # 參數
+    ///
+    /// - `primary`: 主目標地址或檢測錯誤
+    async fn failover_address(&self, primary: Result<String, DomainError>) -> Result<String, DomainError> {
+        let (Some(failover), Some(health_probe)) = (&self.config.failover, &self.health_probe) else {
+            return primary;
+        };
+        let backup = failover.backup.to_string();
+        
+        let healthy = match &primary {
+            Ok(address) => self.primary_healthy(health_probe.as_ref(), address).await,
+            Err(_) => false,
+        };
+        
+        let initial = self.initial_failover_state(&backup).await;
+        let (switched, active) = {
+            let mut state = self.failover_state.lock().unwrap();
+            let state = state.get_or_insert(initial);
+            let switched = state.observe(healthy, failover.failure_threshold, failover.recovery_threshold);
+            (switched, state.active())
+        };
+        
+        if let Some(target) = switched {
+            let address = match target {
+                FailoverTarget::Backup => backup.clone(),
+                FailoverTarget::Primary => primary.as_deref().unwrap_or_default().to_string(),
+            };
+            record_log!(warn, self.config, event = "failover-switched", target:% = target;
+                        "Failover for {} switched to {} ({})", self.config.record_name, target, address);
+            self.publish(Event::FailoverSwitched(FailoverSwitchedEvent {
+                record: RecordRef::from(&self.config),
+                target,
+                address,
+                timestamp: Utc::now(),
+            })).await;
+        }
+        
+        match active {
+            FailoverTarget::Primary => primary,
+            FailoverTarget::Backup => Ok(backup),
+        }
+    }
+    
+    /// 預覽故障轉移的記錄地址，不改變故障轉移狀態，也不發布切換事件
+    ///
+    /// # 參數
+    ///
+    /// - `primary`: 主目標地址或檢測錯誤
+    async fn preview_failover_address(&self, primary: Result<String, DomainError>) -> Result<String, DomainError> {
+        let (Some(failover), Some(health_probe)) = (&self.config.failover, &self.health_probe) else {
+            return primary;
+        };
+        let backup = failover.backup.to_string();
+        
+        let healthy = match &primary {
+            Ok(address) => self.primary_healthy(health_probe.as_ref(), address).await,
+            Err(_) => false,
+        };
+        
+        let initial = self.initial_failover_state(&backup).await;
+        let mut state = self.failover_state.lock().unwrap().clone().unwrap_or(initial);
+        state.observe(healthy, failover.failure_threshold, failover.recovery_threshold);
+        
+        match state.active() {
+            FailoverTarget::Primary => primary,
+            FailoverTarget::Backup => Ok(backup),
+        }
+    }
+    
+    /// 首次檢查時的故障轉移狀態
+    ///
+    /// 服務重建（如配置變更）時記錄可能已指向備用地址，此時從備用狀態開始
+    ///
+    /// # 參數
+    ///
+    /// - `backup`: 備用地址
+    async fn initial_failover_state(&self, backup: &str) -> FailoverState {
+        let last_ip = self.state_repository.get_last_ip(&self.config.id).await.ok().flatten();
+        if last_ip.as_deref() == Some(backup) {
+            FailoverState::new(FailoverTarget::Backup)
+        } else {
+            FailoverState::default()
+        }
+    }
+    
+    /// 對主目標執行所有健康檢查
+    ///
+    /// # 參數
+    ///
+    /// - `health_probe`: 健康檢查執行器
+    /// - `address`: 主目標地址
+    ///
+    /// # 返回
+    ///
+    /// - `bool`: 所有檢查通過時返回 true
+    async fn primary_healthy(&self, health_probe: &dyn HealthProbe, address: &str) -> bool {
+        let Some(failover) = &self.config.failover else {
+            return true;
+        };
+        let target: IpAddr = match address.parse() {
+            Ok(target) => target,
+            Err(_) => return false,
+        };
+        
+        for check in &failover.checks {
+            if let Err(e) = health_probe.probe(check, target).await {
+                record_log!(info, self.config, event = "health-check-failed", error_kind = e.kind();
+                            "Health check for {} failed: {}", self.config.record_name, e);
+                return false;
+            }
+        }
+        true
+    }
+    
+    /// 生成記錄內容
+    ///
+    /// 未配置內容模板時記錄內容即為記錄地址；模板引用另一地址族時額外檢測該地址族（檢測結果有緩存）
+    ///
+    /// # 參數
+    ///
+    /// - `address`: 記錄地址
+    async fn render_content(&self, address: String) -> Result<String, DomainError> {
+        let Some(template) = &self.config.content else {
+            return Ok(address);
+        };
+        
+        let placeholders = template_placeholders(template)?;
+        let context = TemplateContext {
+            ipv4: self.template_address(IpType::IPv4, &address, &placeholders).await?,
+            ipv6: self.template_address(IpType::IPv6, &address, &placeholders).await?,
+            ip: address,
+            hostname: self.config.record_name.clone(),
+            time: Utc::now(),
+        };
+        render_template(template, &context)
+    }
+    
+    /// 獲取內容模板中某一地址族的地址
+    ///
+    /// # 參數
+    ///
+    /// - `ip_type`: 地址族
+    /// - `address`: 本記錄的地址
+    /// - `placeholders`: 模板引用的佔位符，未引用另一地址族時不檢測
+    async fn template_address(&self, ip_type: IpType, address: &str, placeholders: &[String]) -> Result<Option<String>, DomainError> {
+        if self.config.ip_type == ip_type {
+            return Ok(Some(address.to_string()));
+        }
+        if !placeholders.iter().any(|name| *name == ip_type.to_string()) {
+            return Ok(None);
+        }
+        match ip_type {
+            IpType::IPv4 => self.ip_service.get_ipv4().await.map(Some),
+            IpType::IPv6 => self.ip_service.get_ipv6().await.map(Some),
+        }
+    }
+    
+    /// 構建傳給鉤子命令的環境變量
+    ///
+    /// # 參數
+    ///
+    /// - `old_ip`: 更新前的地址（首次更新時為 None）
+    /// - `new_ip`: 新地址
+    /// - `reason`: 更新原因（`ip-changed` 或 `drift`）
+    fn hook_environment(&self, old_ip: Option<&str>, new_ip: &str, reason: &str) -> Vec<(String, String)> {
+        let record = RecordRef::from(&self.config);
+        vec![
+            ("DDNS_CONFIG_ID".to_string(), record.config_id.to_string()),
+            ("DDNS_RECORD_NAME".to_string(), record.record_name),
+            ("DDNS_RECORD_TYPE".to_string(), record.record_type),
+            ("DDNS_IP_TYPE".to_string(), record.ip_type.to_string()),
+            ("DDNS_OLD_IP".to_string(), old_ip.unwrap_or_default().to_string()),
+            ("DDNS_NEW_IP".to_string(), new_ip.to_string()),
+            ("DDNS_REASON".to_string(), reason.to_string()),
+        ]
+    }
+    
+    /// 執行鉤子命令，記錄輸出並發布鉤子執行事件
+    ///
+    /// # 參數
+    ///
+    /// - `stage`: 執行階段
+    /// - `hook`: 鉤子命令
+    /// - `env`: 環境變量
+    ///
+    /// # 返回
+    ///
+    /// - `bool`: 命令是否執行成功
+    async fn run_hook(&self, stage: HookStage, hook: &HookCommand, env: &[(String, String)]) -> bool {
+        let Some(hook_runner) = &self.hook_runner else {
+            warn!("No hook runner configured, skipping {}-update hook for {}", stage, self.config.record_name);
+            return true;
+        };
+        
+        let mut env = env.to_vec();
+        env.push(("DDNS_HOOK_STAGE".to_string(), stage.to_string()));
+        
+        let output = match hook_runner.run(hook, &env).await {
+            Ok(output) => output,
+            Err(e) => HookOutput {
+                exit_code: None,
+                stdout: String::new(),
+                stderr: e.to_string(),
+                duration: StdDuration::ZERO,
+                timed_out: false,
+            },
+        };
+        
+        if output.success() {
+            record_log!(info, self.config, event = "hook-executed";
+                        "{}-update hook for {} succeeded in {}ms", stage, self.config.record_name, output.duration.as_millis());
+        } else if output.timed_out {
+            record_log!(warn, self.config, event = "hook-executed", error_kind = "timeout";
+                        "{}-update hook for {} timed out after {}s", stage, self.config.record_name, hook.timeout_seconds);
+        } else {
+            record_log!(warn, self.config, event = "hook-executed", error_kind = "hook";
+                        "{}-update hook for {} failed with exit code {:?}", stage, self.config.record_name, output.exit_code);
+        }
+        if !output.stdout.is_empty() {
+            info!("{}-update hook stdout: {}", stage, output.stdout);
+        }
+        if !output.stderr.is_empty() {
+            warn!("{}-update hook stderr: {}", stage, output.stderr);
+        }
+        
+        let success = output.success();
+        self.publish(Event::HookExecuted(HookExecutedEvent {
+            record: RecordRef::from(&self.config),
+            stage,
+            command: hook.command.clone(),
+            success,
+            exit_code: output.exit_code,
+            timed_out: output.timed_out,
+            stdout: output.stdout,
+            stderr: output.stderr,
+            duration_ms: output.duration.as_millis() as u64,
+            timestamp: Utc::now(),
+        })).await;
+        
+        success
+    }
+    
+    /// 檢查 IP 並在需要時同步 DNS 記錄
+    ///
+    /// # 參數
+    ///
+    /// - `detected`: 已檢測到的 IP，為 None 時由本服務檢測
+    ///
+    /// # 返回
+    ///
+    /// - `Result<(DnsUpdateResult, Option<AddressChange>), DomainError>`: 成功時返回更新結果及地址變更（地址未變時為 None），失敗時返回錯誤
+    async fn sync_dns_record(&self, detected: Option<Result<String, DomainError>>) -> Result<(DnsUpdateResult, Option<AddressChange>), DomainError> {
         // 獲取當前 IP
-        let current_ip = self.get_current_ip().await?;
+        let detected = match detected {
+            Some(detected) => detected,
+            None => self.get_current_ip().await,
+        };
+        let primary = match detected {
+            Ok(ip) => {
+                self.detection_failures.store(0, Ordering::Relaxed);
+                if self.withdrawn.swap(false, Ordering::Relaxed) {
+                    record_log!(info, self.config, event = "record-restored";
+                                "IPv6 connectivity restored for {}, restoring AAAA record", self.config.record_name);
+                }
+                self.record_address(ip)
+            },
+            Err(e) => {
+                self.on_detection_failure().await;
+                Err(e)
+            }
+        };
+        let current_ip = self.failover_address(primary).await?;
         debug!("Current {} address: {}", self.config.ip_type, current_ip);
+        let content = self.render_content(current_ip.clone()).await?;
         
-        // 檢查 IP 是否變更
-        let config_id = format!("{}-{}", self.config.zone_id, self.config.record_id);
-        let is_changed = self.is_ip_changed(&current_ip).await?;
+        // 檢查記錄內容是否變更（沒有記錄時視為變更）
+        let last_ip = self.state_repository.get_last_ip(&self.config.id).await?;
+        let is_changed = last_ip.as_deref() != Some(content.as_str());
         
-        if !is_changed {
-            debug!("IP has not changed, skipping DNS update");
+        // 內容模板可能引用時間，內容變化不代表地址變化；未配置模板時記錄內容即為地址
+        let last_address = match &self.config.content {
+            Some(_) if is_changed => self.state_repository.get_state(&self.config.id).await?.and_then(|state| state.last_address),
+            Some(_) => Some(current_ip.clone()),
+            None => last_ip.clone(),
+        };
+        let address_changed = last_address.as_deref() != Some(current_ip.as_str());
+        
+        let drifted_ip = if is_changed || !self.drift_check_due() { None } else { self.detect_drift(&content).await };
+        
+        if let Some(actual_ip) = &drifted_ip {
+            record_log!(warn, self.config, event = "drift-detected";
+                        "DNS record {} drifted: expected {}, found {}, restoring", 
+                        self.config.record_name, content, actual_ip);
+            self.publish(Event::DriftDetected(DriftDetectedEvent {
+                record: RecordRef::from(&self.config),
+                expected_ip: content.clone(),
+                actual_ip: actual_ip.clone(),
+                timestamp: Utc::now(),
+            })).await;
+        } else if !is_changed {
+            record_log!(debug, self.config, event = "ip-unchanged"; "IP has not changed, skipping DNS update");
             
             // 創建一個更新結果，但標記為未實際更新
             let record = DnsRecord {
                 id: Some(self.config.record_id.clone()),
                 name: self.config.record_name.clone(),
-                record_type: match self.config.ip_type.to_string().as_str() {
-                    "ipv4" => "A".to_string(),
-                    "ipv6" => "AAAA".to_string(),
-                    _ => return Err(DomainError::validation("Invalid IP type".to_string())),
-                },
-                content: current_ip,
+                record_type: self.config.dns_record_type(),
+                content,
                 ttl: 120,
                 proxied: false,
             };
             
-            return Ok(DnsUpdateResult {
+            return Ok((DnsUpdateResult {
                 record,
                 updated: false,
-            });
+            }, None));
         }
         
         // 創建 DNS 記錄對象
         let record = DnsRecord {
             id: Some(self.config.record_id.clone()),
             name: self.config.record_name.clone(),
-            record_type: match self.config.ip_type.to_string().as_str() {
-                "ipv4" => "A".to_string(),
-                "ipv6" => "AAAA".to_string(),
-                _ => return Err(DomainError::validation("Invalid IP type".to_string())),
-            },
-            content: current_ip.clone(),
+            record_type: self.config.dns_record_type(),
+            content: content.clone(),
             ttl: 120,
             proxied: false,
         };
         
-        info!("Updating {} DNS record: {} to {}", self.config.ip_type, self.config.record_name, current_ip);
+        // 只有模板內容變化（如引用的時間）時不執行鉤子
+        let reason = if drifted_ip.is_some() {
+            Some("drift")
+        } else if address_changed {
+            Some("ip-changed")
+        } else {
+            None
+        };
+        let mut hook_env = reason.map(|reason| self.hook_environment(last_address.as_deref(), &current_ip, reason));
+        
+        // 執行前置鉤子
+        if let (Some(hook), Some(hook_env)) = (&self.config.hooks.pre_update, &hook_env) {
+            let succeeded = self.run_hook(HookStage::Pre, hook, hook_env).await;
+            if !succeeded {
+                if self.config.hooks.abort_on_pre_hook_failure {
+                    return Err(DomainError::hook(format!(
+                        "Pre-update hook failed for {}, update aborted", self.config.record_name
+                    )));
+                }
+                warn!("Pre-update hook failed for {}, continuing with update", self.config.record_name);
+            }
+        }
+        
+        if reason.is_some() {
+            record_log!(info, self.config, event = "ip-changed";
+                        "Updating {} DNS record: {} to {}", self.config.ip_type, self.config.record_name, content);
+        } else {
+            record_log!(info, self.config, event = "content-refreshed";
+                        "Refreshing {} DNS record content: {} to {}", self.config.ip_type, self.config.record_name, content);
+        }
         
         // 更新 DNS 記錄
-        let result = self.dns_service.update_record(record).await?;
+        let result = self.write_record(record).await;
+        
+        // 執行後置鉤子（無論更新是否成功）
+        if let (Some(hook), Some(hook_env)) = (&self.config.hooks.post_update, &mut hook_env) {
+            match &result {
+                Ok(_) => hook_env.push(("DDNS_UPDATE_STATUS".to_string(), "success".to_string())),
+                Err(e) => {
+                    hook_env.push(("DDNS_UPDATE_STATUS".to_string(), "failure".to_string()));
+                    hook_env.push(("DDNS_UPDATE_ERROR".to_string(), e.to_string()));
+                },
+            }
+            self.run_hook(HookStage::Post, hook, hook_env).await;
+        }
+        
+        let result = result?;
         
         // 更新狀態
         if result.updated {
-            self.state_repository.set_last_ip(&config_id, &current_ip).await?;
-            self.state_repository.set_last_update_time(&config_id, Utc::now()).await?;
+            // 剛寫入的記錄不需要馬上檢查漂移
+            *self.last_drift_check.lock().unwrap() = Some(Utc::now());
+            self.state_repository.set_last_ip(&self.config.id, &content).await?;
+            self.state_repository.set_last_update_time(&self.config.id, Utc::now()).await?;
+            if self.config.content.is_some() {
+                let mut state = self.state_repository.get_state(&self.config.id).await?.unwrap_or_default();
+                state.last_address = Some(current_ip.clone());
+                self.state_repository.set_state(&self.config.id, state).await?;
+            }
         }
         
-        Ok(result)
+        let address_change = address_changed.then_some(AddressChange { old: last_address, new: current_ip });
+        Ok((result, address_change))
+    }
+    
+    /// 記錄一次自動更新的結果
+    ///
+    /// # 參數
+    ///
+    /// - `result`: 更新結果
+    ///
+    /// # 返回
+    ///
+    /// - `Option<StdDuration>`: 服務端要求限流時，下一次更新前至少需要等待的時間
+    pub fn report_update_result(&self, result: &Result<DnsUpdateResult, DomainError>) -> Option<StdDuration> {
+        match result {
+            Ok(result) => {
+                if result.updated {
+                    record_log!(info, self.config, event = "update-succeeded";
+                                "Successfully updated {} DNS record for {} to {}", 
+                                self.config.ip_type, self.config.record_name, result.record.content);
+                } else {
+                    debug!("No update needed for {} DNS record {}", 
+                          self.config.ip_type, self.config.record_name);
+                }
+                None
+            },
+            Err(e) => {
+                record_log!(error, self.config, event = "update-failed", error_kind = e.kind();
+                            "Failed to update {} DNS record: {}", self.config.ip_type, e);
+                e.retry_after()
+            }
+        }
     }
     
     /// 啟動自動更新服務
@@ -206,24 +1013,12 @@ impl DdnsApplicationService {
               self.config.ip_type, self.config.record_name, self.config.update_interval);
         
         loop {
-            match self.update_dns_record().await {
-                Ok(result) => {
-                    if result.updated {
-                        info!("Successfully updated {} DNS record for {} to {}", 
-                             self.config.ip_type, self.config.record_name, result.record.content);
-                    } else {
-                        debug!("No update needed for {} DNS record {}", 
-                              self.config.ip_type, self.config.record_name);
-                    }
-                },
-                Err(e) => {
-                    error!("Failed to update {} DNS record: {}", self.config.ip_type, e);
-                }
-            }
+            let result = self.update_dns_record().await;
+            let wait = self.report_update_result(&result).unwrap_or_default().max(interval);
             
             // 等待下一次更新
-            debug!("Waiting {} seconds for next update", self.config.update_interval);
-            sleep(interval).await;
+            debug!("Waiting {} seconds for next update", wait.as_secs());
+            sleep(wait).await;
         }
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,73 @@
+use crate::domain::config::{ConfigId, IpType};
+use crate::domain::error::DomainError;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::fmt;
+
+/// DDNS 記錄健康狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordHealth {
+    /// 最後一次檢查成功
+    Healthy,
+    /// 最後一次檢查失敗
+    Failing,
+    /// 尚未進行過檢查
+    Pending,
+}
+
+impl fmt::Display for RecordHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordHealth::Healthy => write!(f, "healthy"),
+            RecordHealth::Failing => write!(f, "failing"),
+            RecordHealth::Pending => write!(f, "pending"),
+        }
+    }
+}
+
+impl TryFrom<&str> for RecordHealth {
+    type Error = DomainError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "healthy" => Ok(RecordHealth::Healthy),
+            "failing" => Ok(RecordHealth::Failing),
+            "pending" => Ok(RecordHealth::Pending),
+            _ => Err(DomainError::validation(format!("Invalid health: {}", value))),
+        }
+    }
+}
+
+/// 單條 DDNS 記錄的狀態快照
+#[derive(Debug, Clone, Serialize)]
+pub struct DdnsRecordStatus {
+    pub config_id: ConfigId,
+    pub record_name: String,
+    pub ip_type: IpType,
+    pub last_ip: Option<String>,
+    pub last_update: Option<DateTime<Utc>>,
+    pub last_check: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub last_error_time: Option<DateTime<Utc>>,
+    /// 最後一次成功檢查的時間
+    pub last_success: Option<DateTime<Utc>>,
+    pub next_check: Option<DateTime<Utc>>,
+    pub health: RecordHealth,
+}
+
+impl DdnsRecordStatus {
+    /// 根據最後檢查時間和錯誤信息計算健康狀態
+    pub fn compute_health(last_check: Option<DateTime<Utc>>, last_error: Option<&str>) -> RecordHealth {
+        match (last_check, last_error) {
+            (_, Some(_)) => RecordHealth::Failing,
+            (None, None) => RecordHealth::Pending,
+            (Some(_), None) => RecordHealth::Healthy,
+        }
+    }
+
+    /// 根據最後檢查時間和更新間隔計算下一次檢查時間
+    pub fn compute_next_check(last_check: Option<DateTime<Utc>>, update_interval: u64) -> Option<DateTime<Utc>> {
+        last_check.map(|time| time + Duration::seconds(update_interval as i64))
+    }
+}
@@ -1,9 +1,17 @@
+use crate::application::background::{background_tasks, PendingGuard};
+use crate::domain::config::{ConfigId, DdnsConfig, IpType, Ipv6LossAction};
+use crate::domain::failover::FailoverTarget;
+use crate::domain::error::DomainError;
+use crate::domain::hook::HookStage;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
 use std::collections::HashMap;
 use std::fmt;
 use log::{info, warn};
-use tokio::sync::broadcast;
+use once_cell::sync::OnceCell;
+use tokio::sync::{broadcast, mpsc};
 use tokio::sync::broadcast::{Receiver, Sender};
 
 /// 事件類型枚舉
@@ -17,6 +25,20 @@ pub enum EventType {
     ConfigChanged,
     /// 強制更新所有DNS記錄
     ForceUpdateAll,
+    /// IP 地址變更
+    IpChanged,
+    /// DNS 記錄更新成功
+    UpdateSucceeded,
+    /// DNS 記錄更新失敗
+    UpdateFailed,
+    /// DNS 記錄內容與本地狀態不一致
+    DriftDetected,
+    /// 更新鉤子已執行
+    HookExecuted,
+    /// IPv6 連通性消失，AAAA 記錄已刪除或停放
+    RecordWithdrawn,
+    /// 故障轉移目標已切換
+    FailoverSwitched,
 }
 
 impl fmt::Display for EventType {
@@ -26,17 +48,222 @@ impl fmt::Display for EventType {
             EventType::ForceUpdateDns => write!(f, "ForceUpdateDns"),
             EventType::ConfigChanged => write!(f, "ConfigChanged"),
             EventType::ForceUpdateAll => write!(f, "ForceUpdateAll"),
+            EventType::IpChanged => write!(f, "IpChanged"),
+            EventType::UpdateSucceeded => write!(f, "UpdateSucceeded"),
+            EventType::UpdateFailed => write!(f, "UpdateFailed"),
+            EventType::DriftDetected => write!(f, "DriftDetected"),
+            EventType::HookExecuted => write!(f, "HookExecuted"),
+            EventType::RecordWithdrawn => write!(f, "RecordWithdrawn"),
+            EventType::FailoverSwitched => write!(f, "FailoverSwitched"),
         }
     }
 }
 
-/// 事件數據
-#[derive(Debug, Clone)]
-pub struct EventData {
-    /// 事件類型
-    pub event_type: EventType,
-    /// 附加數據（可選）
-    pub data: Option<String>,
+impl EventType {
+    /// 所有事件類型
+    pub const ALL: [EventType; 11] = [
+        EventType::RestartDdnsService,
+        EventType::ForceUpdateDns,
+        EventType::ConfigChanged,
+        EventType::ForceUpdateAll,
+        EventType::IpChanged,
+        EventType::UpdateSucceeded,
+        EventType::UpdateFailed,
+        EventType::DriftDetected,
+        EventType::HookExecuted,
+        EventType::RecordWithdrawn,
+        EventType::FailoverSwitched,
+    ];
+    
+    /// 對外使用的事件名稱（事件流、通知配置等）
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventType::RestartDdnsService => "restart",
+            EventType::ForceUpdateDns => "force-update",
+            EventType::ConfigChanged => "config-changed",
+            EventType::ForceUpdateAll => "force-update-all",
+            EventType::IpChanged => "ip-changed",
+            EventType::UpdateSucceeded => "update-succeeded",
+            EventType::UpdateFailed => "update-failed",
+            EventType::DriftDetected => "drift-detected",
+            EventType::HookExecuted => "hook-executed",
+            EventType::RecordWithdrawn => "record-withdrawn",
+            EventType::FailoverSwitched => "failover-switched",
+        }
+    }
+    
+    /// 根據對外名稱查找事件類型
+    pub fn from_name(name: &str) -> Option<EventType> {
+        Self::ALL.into_iter().find(|event_type| event_type.name() == name)
+    }
+}
+
+/// 全局事件通道容量
+const ALL_EVENTS_CHANNEL_CAPACITY: usize = 64;
+
+/// 事件涉及的 DNS 記錄
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecordRef {
+    pub config_id: ConfigId,
+    pub record_name: String,
+    /// DNS 記錄類型（如 A、AAAA、TXT）
+    pub record_type: String,
+    pub ip_type: IpType,
+}
+
+impl From<&DdnsConfig> for RecordRef {
+    fn from(config: &DdnsConfig) -> Self {
+        Self {
+            config_id: config.id.clone(),
+            record_name: config.record_name.clone(),
+            record_type: config.dns_record_type(),
+            ip_type: config.ip_type.clone(),
+        }
+    }
+}
+
+/// IP 變更事件內容
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IpChangedEvent {
+    pub record: RecordRef,
+    /// 變更前的 IP（首次更新時為 None）
+    pub old_ip: Option<String>,
+    pub new_ip: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// 更新成功事件內容
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UpdateSucceededEvent {
+    pub record: RecordRef,
+    pub ip: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// 更新失敗事件內容
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UpdateFailedEvent {
+    pub record: RecordRef,
+    pub error: String,
+    /// 錯誤分類，見 `DomainError::kind`
+    pub error_kind: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl UpdateFailedEvent {
+    /// 從領域錯誤創建更新失敗事件
+    pub fn from_error(record: RecordRef, error: &DomainError) -> Self {
+        Self {
+            record,
+            error: error.to_string(),
+            error_kind: error.kind().to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// 記錄漂移事件內容
+///
+/// DNS 服務商上的記錄內容與本地記錄的 IP 不一致（例如被手動修改）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DriftDetectedEvent {
+    pub record: RecordRef,
+    /// 本地記錄的 IP
+    pub expected_ip: String,
+    /// DNS 服務商上的實際內容
+    pub actual_ip: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// 鉤子執行事件內容
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HookExecutedEvent {
+    pub record: RecordRef,
+    pub stage: HookStage,
+    pub command: String,
+    pub success: bool,
+    /// 退出碼（超時或無法啟動時為 None）
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration_ms: u64,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// 記錄撤回事件內容
+///
+/// IPv6 連續檢測失敗達到閾值後，AAAA 記錄被刪除或改為停放地址
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecordWithdrawnEvent {
+    pub record: RecordRef,
+    /// 處理方式（delete 或 park）
+    pub action: Ipv6LossAction,
+    /// 撤回前記錄的 IP
+    pub previous_ip: Option<String>,
+    /// 連續檢測失敗次數
+    pub failures: u32,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// 故障轉移切換事件內容
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FailoverSwitchedEvent {
+    pub record: RecordRef,
+    /// 切換後的目標
+    pub target: FailoverTarget,
+    /// 切換後記錄指向的地址
+    pub address: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// 事件
+///
+/// 序列化時只輸出事件內容，事件類型由 `event_type()` 提供
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Event {
+    /// 重啟 DDNS 服務
+    RestartDdnsService,
+    /// 強制更新 DNS 記錄（config_id 為 None 時更新所有記錄）
+    ForceUpdateDns { config_id: Option<ConfigId> },
+    /// 配置改變
+    ConfigChanged,
+    /// 強制更新所有DNS記錄
+    ForceUpdateAll,
+    /// IP 地址變更
+    IpChanged(IpChangedEvent),
+    /// DNS 記錄更新成功
+    UpdateSucceeded(UpdateSucceededEvent),
+    /// DNS 記錄更新失敗
+    UpdateFailed(UpdateFailedEvent),
+    /// DNS 記錄漂移
+    DriftDetected(DriftDetectedEvent),
+    /// 更新鉤子已執行
+    HookExecuted(HookExecutedEvent),
+    /// AAAA 記錄已撤回
+    RecordWithdrawn(RecordWithdrawnEvent),
+    /// 故障轉移目標已切換
+    FailoverSwitched(FailoverSwitchedEvent),
+}
+
+impl Event {
+    /// 獲取事件類型
+    pub fn event_type(&self) -> EventType {
+        match self {
+            Event::RestartDdnsService => EventType::RestartDdnsService,
+            Event::ForceUpdateDns { .. } => EventType::ForceUpdateDns,
+            Event::ConfigChanged => EventType::ConfigChanged,
+            Event::ForceUpdateAll => EventType::ForceUpdateAll,
+            Event::IpChanged(_) => EventType::IpChanged,
+            Event::UpdateSucceeded(_) => EventType::UpdateSucceeded,
+            Event::UpdateFailed(_) => EventType::UpdateFailed,
+            Event::DriftDetected(_) => EventType::DriftDetected,
+            Event::HookExecuted(_) => EventType::HookExecuted,
+            Event::RecordWithdrawn(_) => EventType::RecordWithdrawn,
+            Event::FailoverSwitched(_) => EventType::FailoverSwitched,
+        }
+    }
 }
 
 /// 事件監聽器特性
@@ -46,24 +273,49 @@ pub trait EventListener: Send + Sync {
     ///
     /// # 參數
     ///
-    /// - `event`: 事件數據
-    async fn handle_event(&self, event: EventData);
+    /// - `event`: 事件
+    async fn handle_event(&self, event: Event);
+    
+    /// 是否處理指定類型的事件，默認處理所有事件
+    ///
+    /// # 參數
+    ///
+    /// - `event_type`: 事件類型
+    fn interested_in(&self, _event_type: &EventType) -> bool {
+        true
+    }
+}
+
+/// 等待分發給監聽器的事件
+struct QueuedEvent {
+    event: Event,
+    /// 發布時已註冊的監聽器
+    listeners: Vec<Arc<Mutex<dyn EventListener>>>,
+    /// 分發完成前單次運行不退出
+    _pending: PendingGuard,
 }
 
 /// 事件管理器
 pub struct EventManager {
     /// 事件發送器
-    senders: RwLock<HashMap<EventType, Sender<EventData>>>,
+    senders: RwLock<HashMap<EventType, Sender<Event>>>,
+    /// 所有事件的廣播發送器（供事件流等需要接收全部事件的訂閱者使用）
+    all_sender: Sender<Event>,
     /// 事件監聽器
     listeners: RwLock<Vec<Arc<Mutex<dyn EventListener>>>>,
+    /// 監聽器分發隊列（首次發布時啟動分發任務）
+    dispatch_queue: OnceCell<mpsc::UnboundedSender<QueuedEvent>>,
 }
 
 impl EventManager {
     /// 創建新的事件管理器
     pub fn new() -> Self {
+        let (all_sender, _) = broadcast::channel(ALL_EVENTS_CHANNEL_CAPACITY);
         Self {
             senders: RwLock::new(HashMap::new()),
+            all_sender,
             listeners: RwLock::new(Vec::new()),
+            dispatch_queue: OnceCell::new(),
         }
     }
     
@@ -87,7 +339,7 @@ impl EventManager {
     /// # 返回
     ///
     /// - 事件接收器
-    pub async fn subscribe(&self, event_type: EventType) -> Option<Receiver<EventData>> {
+    pub async fn subscribe(&self, event_type: EventType) -> Option<Receiver<Event>> {
         // 嘗試從現有發送器中獲取
         {
             let senders = self.senders.read().await;
@@ -104,64 +356,93 @@ impl EventManager {
         Some(receiver)
     }
     
+    /// 訂閱所有事件
+    ///
+    /// # 返回
+    ///
+    /// - 接收所有已發布事件的接收器
+    pub fn subscribe_all(&self) -> Receiver<Event> {
+        self.all_sender.subscribe()
+    }
+    
     /// 發布事件
     ///
     /// # 參數
     ///
-    /// - `event`: 事件數據
-    pub async fn publish(&self, event: EventData) {
-        info!("發布事件: {}", event.event_type);
+    /// - `event`: 事件
+    pub async fn publish(&self, event: Event) {
+        let event_type = event.event_type();
+        info!("發布事件: {}", event_type);
         
         // 1. 通過發送器發送事件
         {
             let senders = self.senders.read().await;
-            if let Some(sender) = senders.get(&event.event_type) {
+            if let Some(sender) = senders.get(&event_type) {
                 if let Err(e) = sender.send(event.clone()) {
-                    warn!("發送事件 {} 失敗: {}", event.event_type, e);
+                    warn!("發送事件 {} 失敗: {}", event_type, e);
                 }
             }
         }
         
+        // 沒有訂閱者時發送失敗是正常情況，忽略錯誤
+        let _ = self.all_sender.send(event.clone());
+        
         // 2. 通知所有註冊的監聽器
-        let listeners = self.listeners.read().await;
-        for listener in listeners.iter() {
-            let listener_guard = listener.lock().await;
-            listener_guard.handle_event(event.clone()).await;
+        // 放入隊列由分發任務按發布順序處理，避免監聽器處理事件時再次發布事件造成死鎖
+        let listeners = self.listeners.read().await.clone();
+        let queued = QueuedEvent { event, listeners, _pending: background_tasks().track() };
+        if self.dispatch_queue().send(queued).is_err() {
+            warn!("事件分發任務已停止，丟棄事件: {}", event_type);
         }
     }
     
+    /// 獲取監聽器分發隊列，首次調用時啟動分發任務
+    ///
+    /// 事件逐個分發，前一個事件的所有監聽器處理完成後才分發下一個，保證監聽器按發布順序收到事件，
+    /// 也避免配置變更等事件的處理並發執行
+    fn dispatch_queue(&self) -> &mpsc::UnboundedSender<QueuedEvent> {
+        self.dispatch_queue.get_or_init(|| {
+            let (sender, mut receiver) = mpsc::unbounded_channel::<QueuedEvent>();
+            tokio::spawn(async move {
+                while let Some(queued) = receiver.recv().await {
+                    // 在獨立任務中處理，監聽器 panic 時不影響後續事件的分發
+                    let dispatch = tokio::spawn(async move {
+                        let event_type = queued.event.event_type();
+                        for listener in &queued.listeners {
+                            let listener_guard = listener.lock().await;
+                            if listener_guard.interested_in(&event_type) {
+                                listener_guard.handle_event(queued.event.clone()).await;
+                            }
+                        }
+                    });
+                    if let Err(e) = dispatch.await {
+                        warn!("事件監聽器處理失敗: {}", e);
+                    }
+                }
+            });
+            sender
+        })
+    }
+    
     /// 發布重啟 DDNS 服務事件
     pub async fn restart_ddns_service(&self) {
-        let event = EventData {
-            event_type: EventType::RestartDdnsService,
-            data: None,
-        };
-        self.publish(event).await;
+        self.publish(Event::RestartDdnsService).await;
     }
     
     /// 發布強制更新 DNS 記錄事件
     ///
     /// # 參數
     ///
-    /// - `record_name`: 記錄名稱（可選）
-    pub async fn force_update_dns(&self, record_name: Option<String>) {
-        let event = EventData {
-            event_type: EventType::ForceUpdateDns,
-            data: record_name,
-        };
-        self.publish(event).await;
+    /// - `config_id`: 配置 ID（可選，為 None 時更新所有記錄）
+    pub async fn force_update_dns(&self, config_id: Option<&ConfigId>) {
+        self.publish(Event::ForceUpdateDns { config_id: config_id.cloned() }).await;
     }
     
     /// 強制更新所有DNS記錄
     pub async fn force_update_all_dns(&self) {
         info!("觸發事件: 強制更新所有DNS記錄");
         
-        let event = EventData {
-            event_type: EventType::ForceUpdateAll,
-            data: None,
-        };
-        
-        self.publish(event).await;
+        self.publish(Event::ForceUpdateAll).await;
     }
 }
 
@@ -1,20 +1,29 @@
-use crate::domain::config::DdnsConfig;
+use crate::domain::config::{ConfigId, DdnsConfig, HttpClientSettings, IpType, Ipv6LossAction, SourceSettings};
+use crate::domain::error::DomainError;
 use crate::domain::dns::DnsService;
+use crate::domain::failover::HealthProbe;
+use crate::domain::hook::HookRunner;
 use crate::domain::ip::IpService;
 use crate::domain::state::StateRepository;
-use crate::infrastructure::http::{ReqwestHttpClient, RetryableHttpClient};
-use crate::infrastructure::ip::PublicIpService;
-use crate::infrastructure::dns::CloudflareDnsService;
+use crate::infrastructure::http::{circuit_breakers, ReqwestHttpClient, RetryableHttpClient};
+use crate::infrastructure::ip::{CachedIpService, PublicIpService, SourceBoundIpService};
+use crate::infrastructure::dns::{cloudflare_reachability, CloudflareDnsService};
 use crate::infrastructure::state::InMemoryStateRepository;
-use crate::application::ddns::DdnsApplicationService;
+use crate::infrastructure::failover::NetworkHealthProbe;
+use crate::infrastructure::hook::ShellHookRunner;
+use crate::application::ddns::{DdnsApplicationService, DetectionGroup, PlanEntry, UpdateOutcome};
 use crate::application::config::ConfigService;
-use crate::application::events::{EventManager, EventType, EventData, EventListener};
+use crate::application::events::{Event, EventManager, EventType, EventListener};
+use crate::application::notification::NotificationService;
+use crate::application::metrics::MetricsEventListener;
+use crate::application::health::{record_freshness, CheckStatus, ReadinessCheck, ReadinessReport, RecordReadiness};
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
 use std::collections::HashMap;
-use std::hash::{Hash, Hasher};
-use std::collections::hash_map::DefaultHasher;
 use std::time::Duration;
+use chrono::{DateTime, Utc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::task::JoinHandle;
 use log::{info, error, warn};
 use async_trait::async_trait;
 
@@ -25,35 +34,47 @@ struct DdnsServiceEventListener {
 
 #[async_trait]
 impl EventListener for DdnsServiceEventListener {
-    async fn handle_event(&self, event: EventData) {
-        match event.event_type {
-            EventType::RestartDdnsService => {
+    async fn handle_event(&self, event: Event) {
+        match event {
+            Event::RestartDdnsService => {
                 info!("處理重啟 DDNS 服務事件");
                 // 重啟所有 DDNS 服務
                 self.service_factory.restart_all_ddns_services().await;
             },
-            EventType::ForceUpdateDns => {
+            Event::ForceUpdateDns { config_id } => {
                 info!("處理強制更新 DNS 記錄事件");
                 // 強制更新指定的 DNS 記錄或全部記錄
-                if let Some(record_name) = event.data.clone() {
-                    self.service_factory.update_specific_record(&record_name).await;
+                if let Some(config_id) = config_id {
+                    self.service_factory.update_specific_record(&config_id).await;
                 } else {
                     // 更新所有記錄
                     self.service_factory.force_update_all_dns_records().await;
                 }
             },
-            EventType::ConfigChanged => {
+            Event::ConfigChanged => {
                 info!("處理配置變更事件");
                 // 重新加載配置並更新服務
                 self.service_factory.reload_configs_and_restart_services().await;
             },
-            EventType::ForceUpdateAll => {
+            Event::ForceUpdateAll => {
                 info!("處理強制更新所有 DNS 記錄事件");
                 // 更新所有記錄
                 self.service_factory.force_update_all_dns_records().await;
             },
+            _ => {},
         }
     }
+    
+    fn interested_in(&self, event_type: &EventType) -> bool {
+        // 只處理控制類事件，更新結果事件由事件流等訂閱者處理
+        matches!(
+            event_type,
+            EventType::RestartDdnsService
+                | EventType::ForceUpdateDns
+                | EventType::ConfigChanged
+                | EventType::ForceUpdateAll
+        )
+    }
 }
 
 /// 服務工廠，用於創建和組裝服務
@@ -61,10 +82,33 @@ impl EventListener for DdnsServiceEventListener {
 pub struct ServiceFactory {
     http_client: Arc<dyn crate::domain::http::HttpClient>,
     ip_service: Arc<dyn IpService>,
+    /// HTTP 客戶端設置，用於為綁定檢測來源的記錄創建客戶端
+    http_settings: HttpClientSettings,
+    /// 按檢測來源共用的 IP 服務
+    source_ip_services: Arc<std::sync::Mutex<HashMap<SourceSettings, Arc<dyn IpService>>>>,
     state_repository: Arc<dyn StateRepository>,
-    ddns_services: Arc<RwLock<HashMap<u64, Arc<Mutex<DdnsApplicationService>>>>>,
+    hook_runner: Arc<dyn HookRunner>,
+    health_probe: Arc<dyn HealthProbe>,
+    ddns_services: Arc<RwLock<HashMap<ConfigId, Arc<Mutex<DdnsApplicationService>>>>>,
     event_manager: Arc<EventManager>,
     config_service: Arc<ConfigService>,
+    /// 是否在服務重建後自動啟動更新任務
+    auto_update_enabled: Arc<AtomicBool>,
+    /// 運行中的自動更新任務
+    update_tasks: Arc<Mutex<Vec<UpdateTask>>>,
+    /// 最近一次加載配置失敗的錯誤信息
+    config_load_error: Arc<RwLock<Option<String>>>,
+    /// 是否監視配置文件變更並在沒有配置時創建示例配置
+    watch_config: bool,
+    /// 環境變量中的記錄配置，每次加載配置時與配置文件中的記錄合併
+    env_configs: Vec<DdnsConfig>,
+}
+
+/// 一組記錄的自動更新任務
+struct UpdateTask {
+    config_ids: Vec<ConfigId>,
+    started_at: DateTime<Utc>,
+    handle: JoinHandle<()>,
 }
 
 impl Default for ServiceFactory {
@@ -78,35 +122,102 @@ impl ServiceFactory {
     pub fn new() -> Self {
         // 創建基礎的 HTTP 客戶端
         let base_http_client = Arc::new(ReqwestHttpClient::new());
+        let ip_service = PublicIpService::new(
+            base_http_client.clone(), // IP 服務使用基礎 HTTP 客戶端
+            None,
+            None,
+        );
+        
+        Self::with_http_clients(HttpClientSettings::default(), base_http_client, ip_service)
+    }
+    
+    /// 使用 HTTP 客戶端設置創建服務工廠
+    ///
+    /// 代理、超時、CA 證書和本地地址應用於所有出站請求；啟用 `pin_ip_family` 時，
+    /// IPv4 和 IPv6 查詢分別使用只走對應協議族的客戶端
+    ///
+    /// # 參數
+    ///
+    /// - `settings`: HTTP 客戶端設置
+    ///
+    /// # 返回
+    ///
+    /// - `Result<Self, DomainError>`: 設置無效時返回錯誤
+    pub fn with_http_settings(settings: &HttpClientSettings) -> Result<Self, DomainError> {
+        let base_http_client = Arc::new(ReqwestHttpClient::from_settings(settings)?);
+        let mut ip_service = PublicIpService::new(base_http_client.clone(), None, None);
+        if settings.pin_ip_family {
+            ip_service = ip_service.with_family_clients(
+                Arc::new(ReqwestHttpClient::for_ip_type(settings, IpType::IPv4)?),
+                Arc::new(ReqwestHttpClient::for_ip_type(settings, IpType::IPv6)?),
+            );
+        }
         
+        Ok(Self::with_http_clients(settings.clone(), base_http_client, ip_service))
+    }
+    
+    /// 使用指定的基礎 HTTP 客戶端和 IP 服務組裝服務工廠
+    fn with_http_clients(
+        http_settings: HttpClientSettings,
+        base_http_client: Arc<ReqwestHttpClient>,
+        ip_service: PublicIpService,
+    ) -> Self {
         // 創建帶有重試機制的 HTTP 客戶端
         let http_client = Arc::new(RetryableHttpClient::new(
             base_http_client.clone(),
             3, // 最大重試次數
-            Duration::from_millis(500), // 重試間隔
+            Duration::from_millis(500), // 首次重試間隔
         ));
         
-        let ip_service = Arc::new(PublicIpService::new(
-            base_http_client.clone(), // IP 服務使用基礎 HTTP 客戶端
-            None,
-            None,
-        ));
+        let ip_service = Arc::new(CachedIpService::new(Arc::new(ip_service), CachedIpService::DEFAULT_TTL));
         let state_repository = Arc::new(InMemoryStateRepository::new());
         let event_manager = Arc::new(EventManager::new());
         
         // 創建配置服務
         let config_service = Arc::new(ConfigService::new(event_manager.clone()));
         
-        let factory = Self {
+        Self {
             http_client,
             ip_service,
+            http_settings,
+            source_ip_services: Arc::new(std::sync::Mutex::new(HashMap::new())),
             state_repository,
+            hook_runner: Arc::new(ShellHookRunner::new()),
+            health_probe: Arc::new(NetworkHealthProbe::new()),
             ddns_services: Arc::new(RwLock::new(HashMap::new())),
             event_manager,
             config_service,
-        };
-        
-        factory
+            auto_update_enabled: Arc::new(AtomicBool::new(false)),
+            update_tasks: Arc::new(Mutex::new(Vec::new())),
+            config_load_error: Arc::new(RwLock::new(None)),
+            watch_config: true,
+            env_configs: Vec::new(),
+        }
+    }
+    
+    /// 設置是否監視配置文件
+    ///
+    /// 關閉時只讀加載配置：不監視文件變更、沒有配置時不創建示例配置，用於單次運行和試運行
+    ///
+    /// # 參數
+    ///
+    /// - `enabled`: 是否監視配置文件
+    pub fn with_config_watching(mut self, enabled: bool) -> Self {
+        self.watch_config = enabled;
+        self
+    }
+    
+    /// 設置環境變量中的記錄配置
+    ///
+    /// 這些記錄不保存到配置文件，每次加載或重新加載配置時加入記錄列表；
+    /// 配置文件中已有相同 ID 的記錄時以配置文件為準
+    ///
+    /// # 參數
+    ///
+    /// - `configs`: 環境變量中的記錄配置
+    pub fn with_env_configs(mut self, configs: Vec<DdnsConfig>) -> Self {
+        self.env_configs = configs;
+        self
     }
     
     /// 初始化事件監聽系統
@@ -118,6 +229,16 @@ impl ServiceFactory {
         
         self.event_manager.register_listener(listener).await;
         
+        // 註冊通知服務，基於 HTTP 的通知通過帶重試的 HTTP 客戶端發送
+        let notification_service = Arc::new(Mutex::new(NotificationService::new(
+            self.config_service.clone(),
+            self.http_client.clone(),
+        )));
+        self.event_manager.register_listener(notification_service.clone()).await;
+        
+        // 註冊指標監聽器，統計更新結果
+        self.event_manager.register_listener(Arc::new(Mutex::new(MetricsEventListener::new()))).await;
+        
         // 訂閱事件
         let _ = self.event_manager.subscribe(EventType::RestartDdnsService).await;
         let _ = self.event_manager.subscribe(EventType::ForceUpdateDns).await;
@@ -125,54 +246,69 @@ impl ServiceFactory {
         let _ = self.event_manager.subscribe(EventType::ForceUpdateAll).await;
         
         // 初始化配置服務
-        match self.config_service.initialize().await {
-            Ok(_) => info!("配置服務初始化成功"),
+        let init_result = if self.watch_config {
+            self.config_service.initialize().await
+        } else {
+            self.config_service.load().await
+        };
+        match &init_result {
+            Ok(_) => {
+                info!("配置服務初始化成功");
+                notification_service.lock().await.reload_settings().await;
+            },
             Err(e) => error!("配置服務初始化失敗: {}", e),
         }
         
         // 加載配置並創建服務
         self.load_configs_and_create_services().await;
+        if let Err(e) = init_result {
+            *self.config_load_error.write().await = Some(e.to_string());
+        }
         
         info!("已初始化事件監聽系統");
     }
     
     /// 從配置加載並創建服務
     async fn load_configs_and_create_services(&self) {
-        match self.config_service.get_configs().await {
-            Ok(configs) => {
-                if configs.is_empty() {
-                    info!("沒有找到配置，不創建 DDNS 服務");
-                    return;
+        match self.config_service.get_record_configs().await {
+            Ok(mut configs) => {
+                *self.config_load_error.write().await = None;
+                for env_config in &self.env_configs {
+                    if !configs.iter().any(|config| config.id == env_config.id) {
+                        configs.push(env_config.clone());
+                    }
                 }
                 
-                info!("從文件加載了 {} 個配置", configs.len());
-                
                 // 清空現有服務
                 {
                     let mut services = self.ddns_services.write().await;
                     services.clear();
                 }
                 
+                if configs.is_empty() {
+                    info!("沒有找到配置，不創建 DDNS 服務");
+                    self.respawn_update_tasks().await;
+                    return;
+                }
+                
+                info!("從文件加載了 {} 個配置", configs.len());
+                
                 // 創建服務
                 for config in configs {
-                    let dns_service = self.create_dns_service(&config);
-                    let service = DdnsApplicationService::new(
-                        dns_service,
-                        self.ip_service.clone(),
-                        self.state_repository.clone(),
-                        config.clone(),
-                    );
+                    let service = self.build_ddns_service(config.clone());
                     
                     // 儲存服務實例
-                    let key = self.generate_config_key(&config);
                     let mut services = self.ddns_services.write().await;
-                    services.insert(key, Arc::new(Mutex::new(service)));
+                    services.insert(config.id.clone(), Arc::new(Mutex::new(service)));
                     
                     info!("創建了 DDNS 服務: {}", config.record_name);
                 }
+                
+                self.respawn_update_tasks().await;
             },
             Err(e) => {
                 error!("加載配置失敗: {}", e);
+                *self.config_load_error.write().await = Some(e.to_string());
             }
         }
     }
@@ -200,6 +336,45 @@ impl ServiceFactory {
         ))
     }
     
+    /// 創建記錄使用的 IP 服務
+    ///
+    /// 配置了檢測來源的記錄使用綁定到該接口或地址的 IP 服務，其他記錄共用默認 IP 服務；
+    /// 檢測來源相同的記錄共用同一個帶緩存的服務
+    ///
+    /// # 參數
+    ///
+    /// - `config`: DDNS 配置
+    fn create_ip_service(&self, config: &DdnsConfig) -> Arc<dyn IpService> {
+        if config.source.is_default() {
+            return self.ip_service.clone();
+        }
+        self.source_ip_services.lock().unwrap()
+            .entry(config.source.clone())
+            .or_insert_with(|| {
+                let service = SourceBoundIpService::new(self.http_settings.clone(), config.source.clone());
+                Arc::new(CachedIpService::new(Arc::new(service), CachedIpService::DEFAULT_TTL))
+            })
+            .clone()
+    }
+    
+    /// 組裝 DDNS 應用服務（不註冊到服務列表）
+    ///
+    /// # 參數
+    ///
+    /// - `config`: DDNS 配置
+    fn build_ddns_service(&self, config: DdnsConfig) -> DdnsApplicationService {
+        let dns_service = self.create_dns_service(&config);
+        DdnsApplicationService::new(
+            dns_service,
+            self.create_ip_service(&config),
+            self.state_repository.clone(),
+            config,
+        )
+        .with_event_manager(self.event_manager.clone())
+        .with_hook_runner(self.hook_runner.clone())
+        .with_health_probe(self.health_probe.clone())
+    }
+    
     /// 創建 DDNS 應用服務
     ///
     /// # 參數
@@ -210,19 +385,11 @@ impl ServiceFactory {
     ///
     /// - DDNS 應用服務實例
     pub async fn create_ddns_service(&self, config: DdnsConfig) -> DdnsApplicationService {
-        let dns_service = self.create_dns_service(&config);
-        
-        let service = DdnsApplicationService::new(
-            dns_service,
-            self.ip_service.clone(),
-            self.state_repository.clone(),
-            config.clone(),
-        );
+        let service = self.build_ddns_service(config.clone());
         
         // 儲存服務實例以供 API 使用
-        let key = self.generate_config_key(&config);
         let mut services = self.ddns_services.write().await;
-        services.insert(key, Arc::new(Mutex::new(service.clone())));
+        services.insert(config.id.clone(), Arc::new(Mutex::new(service.clone())));
         
         service
     }
@@ -242,37 +409,49 @@ impl ServiceFactory {
         self.config_service.clone()
     }
     
-    /// 根據配置查找 DDNS 服務
+    /// 根據配置 ID 查找 DDNS 服務
     ///
     /// # 參數
     ///
-    /// - `record_name`: 要查找的 DNS 記錄名稱
+    /// - `config_id`: 配置 ID
     ///
     /// # 返回
     ///
     /// - Option<Arc<Mutex<DdnsApplicationService>>>: 找到的服務實例，如果不存在則返回 None
-    pub async fn find_ddns_service(&self, record_name: &str) -> Option<Arc<Mutex<DdnsApplicationService>>> {
+    pub async fn find_ddns_service(&self, config_id: &ConfigId) -> Option<Arc<Mutex<DdnsApplicationService>>> {
         let services = self.ddns_services.read().await;
-        for (_, service) in services.iter() {
+        services.get(config_id).cloned()
+    }
+    
+    /// 根據記錄名稱查找 DDNS 服務
+    ///
+    /// 同一名稱可能同時存在 IPv4 和 IPv6 配置，因此返回所有匹配的服務
+    ///
+    /// # 參數
+    ///
+    /// - `record_name`: 要查找的 DNS 記錄名稱
+    pub async fn find_ddns_services_by_name(&self, record_name: &str) -> Vec<Arc<Mutex<DdnsApplicationService>>> {
+        let services = self.ddns_services.read().await;
+        let mut found = Vec::new();
+        for service in services.values() {
             let service_guard = service.lock().await;
-            if &service_guard.config().record_name == record_name {
-                return Some(service.clone());
+            if service_guard.config().record_name == record_name {
+                found.push(service.clone());
             }
         }
-        None
+        found
     }
     
-    /// 獲取第一個 DDNS 服務
+    /// 獲取所有 DDNS 服務
     ///
     /// # 返回
     ///
-    /// - Option<Arc<Mutex<DdnsApplicationService>>>: 第一個可用的服務實例，如果不存在則返回 None
-    pub async fn get_first_ddns_service(&self) -> Option<Arc<Mutex<DdnsApplicationService>>> {
+    /// - Vec<Arc<Mutex<DdnsApplicationService>>>: 按配置 ID 排序的服務實例列表
+    pub async fn list_ddns_services(&self) -> Vec<Arc<Mutex<DdnsApplicationService>>> {
         let services = self.ddns_services.read().await;
-        if let Some((_, service)) = services.iter().next() {
-            return Some(service.clone());
-        }
-        None
+        let mut entries: Vec<_> = services.iter().collect();
+        entries.sort_by_key(|(id, _)| *id);
+        entries.into_iter().map(|(_, service)| service.clone()).collect()
     }
     
     /// 重啟所有 DDNS 服務
@@ -305,25 +484,155 @@ impl ServiceFactory {
         
         // 重新創建服務
         for config in configs {
-            let dns_service = self.create_dns_service(&config);
-            let service = DdnsApplicationService::new(
-                dns_service,
-                self.ip_service.clone(),
-                self.state_repository.clone(),
-                config.clone(),
-            );
+            let service = self.build_ddns_service(config.clone());
             
             // 儲存服務實例
-            let key = self.generate_config_key(&config);
             let mut services = self.ddns_services.write().await;
-            services.insert(key, Arc::new(Mutex::new(service)));
+            services.insert(config.id.clone(), Arc::new(Mutex::new(service)));
             
             info!("重新創建了 DDNS 服務: {}", config.record_name);
         }
         
+        self.respawn_update_tasks().await;
+        
         info!("所有 DDNS 服務已重啟");
     }
     
+    /// 啟動所有 DDNS 服務的自動更新任務
+    ///
+    /// 啟動後，配置重新加載或服務重啟時會自動重建更新任務
+    pub async fn start_auto_update(&self) {
+        self.auto_update_enabled.store(true, Ordering::SeqCst);
+        self.respawn_update_tasks().await;
+    }
+    
+    /// 停止所有 DDNS 服務的自動更新任務
+    pub async fn stop_auto_update(&self) {
+        self.auto_update_enabled.store(false, Ordering::SeqCst);
+        self.respawn_update_tasks().await;
+    }
+    
+    /// 中止現有的自動更新任務，並在啟用時為當前服務重新創建
+    async fn respawn_update_tasks(&self) {
+        let mut tasks = self.update_tasks.lock().await;
+        for task in tasks.drain(..) {
+            task.handle.abort();
+        }
+        
+        if !self.auto_update_enabled.load(Ordering::SeqCst) {
+            return;
+        }
+        
+        // 複製服務實例，避免更新循環長期持有服務鎖
+        let mut services = Vec::new();
+        for service in self.list_ddns_services().await {
+            services.push(service.lock().await.clone());
+        }
+        
+        // 檢測來源和更新間隔相同的記錄共用一個任務，每次只檢測一次 IP
+        let record_count = services.len();
+        for group in DetectionGroup::group(services) {
+            tasks.push(UpdateTask {
+                config_ids: group.config_ids(),
+                started_at: Utc::now(),
+                handle: tokio::spawn(group.run()),
+            });
+        }
+        
+        info!("已為 {} 條記錄啟動 {} 個 DDNS 自動更新任務", record_count, tasks.len());
+    }
+    
+    /// 檢查服務是否就緒
+    ///
+    /// 檢查配置是否加載成功、每條記錄的更新任務是否存活、最近一次成功檢查是否在
+    /// 允許的間隔內，以及 Cloudflare API 最近是否可達；報告中附帶各上游主機的熔斷器狀態
+    ///
+    /// # 參數
+    ///
+    /// - `max_missed_intervals`: 允許錯過的更新間隔數
+    ///
+    /// # 返回
+    ///
+    /// - `ReadinessReport`: 就緒檢查報告
+    pub async fn readiness(&self, max_missed_intervals: u32) -> ReadinessReport {
+        let mut checks = Vec::new();
+        
+        checks.push(match self.config_load_error.read().await.clone() {
+            Some(error) => ReadinessCheck::new("configs", CheckStatus::Degraded, format!("加載配置失敗: {}", error)),
+            None => ReadinessCheck::new("configs", CheckStatus::Ok, "配置已加載"),
+        });
+        
+        // 收集更新任務狀態
+        let auto_update = self.auto_update_enabled.load(Ordering::SeqCst);
+        let workers: HashMap<ConfigId, (DateTime<Utc>, bool)> = self.update_tasks.lock().await
+            .iter()
+            .flat_map(|task| {
+                let worker = (task.started_at, !task.handle.is_finished());
+                task.config_ids.iter().map(move |config_id| (config_id.clone(), worker))
+            })
+            .collect();
+        
+        let now = Utc::now();
+        let mut records = Vec::new();
+        for service in self.list_ddns_services().await {
+            let service = service.lock().await;
+            let config = service.config();
+            let status = match service.status_snapshot().await {
+                Ok(status) => status,
+                Err(e) => {
+                    warn!("讀取記錄 {} 狀態失敗: {}", config.record_name, e);
+                    continue;
+                }
+            };
+            
+            let worker = workers.get(&config.id);
+            let worker_status = match (auto_update, worker) {
+                (false, _) => CheckStatus::Skipped,
+                (true, Some((_, true))) => CheckStatus::Ok,
+                (true, _) => CheckStatus::Degraded,
+            };
+            let freshness = record_freshness(
+                status.last_success,
+                worker.filter(|_| auto_update).map(|(started_at, _)| *started_at),
+                config.update_interval,
+                max_missed_intervals,
+                now,
+            );
+            
+            records.push(RecordReadiness {
+                config_id: status.config_id,
+                record_name: status.record_name,
+                ip_type: status.ip_type,
+                worker: worker_status,
+                freshness,
+                last_success: status.last_success,
+                last_error: status.last_error,
+            });
+        }
+        
+        let unhealthy = records.iter().filter(|record| !record.is_ready()).count();
+        checks.push(if !auto_update {
+            ReadinessCheck::new("records", CheckStatus::Skipped, "未啟用自動更新")
+        } else if unhealthy > 0 {
+            ReadinessCheck::new("records", CheckStatus::Degraded, format!("{}/{} 條記錄異常", unhealthy, records.len()))
+        } else {
+            ReadinessCheck::new("records", CheckStatus::Ok, format!("{} 條記錄正常", records.len()))
+        });
+        
+        let cloudflare = cloudflare_reachability().snapshot();
+        checks.push(match cloudflare.is_reachable() {
+            None => ReadinessCheck::new("cloudflare", CheckStatus::Skipped, "尚未調用 Cloudflare API"),
+            Some(true) => ReadinessCheck::new("cloudflare", CheckStatus::Ok, "Cloudflare API 可達"),
+            Some(false) => ReadinessCheck::new(
+                "cloudflare",
+                CheckStatus::Degraded,
+                format!("Cloudflare API 不可達: {}", cloudflare.last_error.unwrap_or_default()),
+            ),
+        });
+        
+        ReadinessReport::new(checks, records).with_circuits(circuit_breakers().snapshot())
+    }
+    
     /// 強制更新所有 DNS 記錄
     pub async fn force_update_all_dns_records(&self) {
         info!("強制更新所有 DNS 記錄");
@@ -347,10 +656,108 @@ impl ServiceFactory {
         }
     }
     
+    /// 按選擇器查找 DDNS 服務
+    ///
+    /// 選擇器可以是配置 ID、記錄名稱（不區分大小寫）或記錄組 ID；沒有匹配任何記錄的選擇器會記錄警告
+    ///
+    /// # 參數
+    ///
+    /// - `selectors`: 選擇器，為空時返回所有服務
+    ///
+    /// # 返回
+    ///
+    /// - Vec<Arc<Mutex<DdnsApplicationService>>>: 按配置 ID 排序的服務實例列表
+    pub async fn select_ddns_services(&self, selectors: &[String]) -> Vec<Arc<Mutex<DdnsApplicationService>>> {
+        let services = self.list_ddns_services().await;
+        if selectors.is_empty() {
+            return services;
+        }
+        
+        let mut matched = vec![false; selectors.len()];
+        let mut selected = Vec::new();
+        for service in services {
+            let is_selected = {
+                let service_guard = service.lock().await;
+                let config = service_guard.config();
+                let mut is_selected = false;
+                for (index, selector) in selectors.iter().enumerate() {
+                    let selector = selector.trim();
+                    if config.id.as_str() == selector
+                        || config.record_name.eq_ignore_ascii_case(selector)
+                        || config.id.as_str().starts_with(&format!("{}:", selector))
+                    {
+                        matched[index] = true;
+                        is_selected = true;
+                    }
+                }
+                is_selected
+            };
+            if is_selected {
+                selected.push(service);
+            }
+        }
+        
+        for (selector, matched) in selectors.iter().zip(matched) {
+            if !matched {
+                warn!("沒有與 {} 匹配的 DDNS 記錄", selector);
+            }
+        }
+        selected
+    }
+    
+    /// 試運行選中的 DDNS 記錄，不寫入 Cloudflare
+    ///
+    /// # 參數
+    ///
+    /// - `selectors`: 記錄選擇器，為空時試運行所有記錄
+    ///
+    /// # 返回
+    ///
+    /// - `Vec<PlanEntry>`: 按配置 ID 排序的計劃
+    pub async fn plan_dns_records(&self, selectors: &[String]) -> Vec<PlanEntry> {
+        let mut plan = Vec::new();
+        for service in self.select_ddns_services(selectors).await {
+            let service_guard = service.lock().await;
+            plan.push(service_guard.plan(None).await);
+        }
+        plan
+    }
+    
+    /// 對選中的 DDNS 記錄執行一次檢測和更新
+    ///
+    /// 與自動更新一樣，檢測來源相同的記錄共用一次 IP 檢測；更新前先讀取記錄的當前內容，
+    /// 內容未變化的記錄不會被重寫
+    ///
+    /// # 參數
+    ///
+    /// - `selectors`: 記錄選擇器，為空時更新所有記錄
+    ///
+    /// # 返回
+    ///
+    /// - `Vec<UpdateOutcome>`: 按配置 ID 排序的更新結果
+    pub async fn update_dns_records_once(&self, selectors: &[String]) -> Vec<UpdateOutcome> {
+        let mut services = Vec::new();
+        for service in self.select_ddns_services(selectors).await {
+            let service = service.lock().await.clone();
+            warn_stateless_thresholds(service.config());
+            if let Err(e) = service.load_last_content().await {
+                warn!("讀取記錄 {} 的當前內容失敗: {}", service.config().record_name, e);
+            }
+            services.push(service);
+        }
+        
+        let mut outcomes = Vec::new();
+        for group in DetectionGroup::group(services) {
+            outcomes.extend(group.update_once().await);
+        }
+        outcomes.sort_by(|a, b| a.config_id.cmp(&b.config_id));
+        outcomes
+    }
+    
     /// 更新特定DNS記錄
-    pub async fn update_specific_record(&self, record_name: &str) -> bool {
+    pub async fn update_specific_record(&self, config_id: &ConfigId) -> bool {
         // 找到對應的服務
-        let service_opt = self.find_ddns_service(record_name).await;
+        let service_opt = self.find_ddns_service(config_id).await;
         
         if let Some(service) = service_opt {
             let service_guard = service.lock().await;
@@ -365,27 +772,49 @@ impl ServiceFactory {
                 }
             }
         } else {
-            warn!("找不到記錄名稱為 {} 的DDNS服務", record_name);
+            warn!("找不到配置 ID 為 {} 的DDNS服務", config_id);
             false
         }
     }
     
     /// 保存配置並應用變更
-    pub async fn save_configs_and_apply(&self, configs: Vec<DdnsConfig>) -> Result<(), crate::domain::error::DomainError> {
+    ///
+    /// # 參數
+    ///
+    /// - `configs`: 新的完整配置列表
+    /// - `expected_revision`: 客戶端持有的整份配置修訂標記（可選）
+    pub async fn save_configs_and_apply(&self, configs: Vec<DdnsConfig>, expected_revision: Option<&str>) -> Result<(), crate::domain::error::DomainError> {
         // 保存配置
-        self.config_service.save_configs(configs).await?;
+        self.config_service.save_configs_if_match(configs, expected_revision).await?;
         
         // 重新加載配置並重啟服務
         self.reload_configs_and_restart_services().await;
         
         Ok(())
     }
-    
-    // 為配置生成唯一的鍵值
-    fn generate_config_key(&self, config: &DdnsConfig) -> u64 {
-        let mut hasher = DefaultHasher::new();
-        config.record_name.hash(&mut hasher);
-        config.ip_type.hash(&mut hasher);
-        hasher.finish()
-    }
-} 
\ No newline at end of file
+}
+
+/// 單次運行時提示依賴連續檢查次數的設置不會生效
+///
+/// 故障轉移的連續健康檢查次數和 IPv6 連續檢測失敗次數只保存在進程內存中，單次運行每次從零開始，
+/// 閾值大於 1 時永遠不會達到
+///
+/// # 參數
+///
+/// - `config`: 記錄配置
+fn warn_stateless_thresholds(config: &DdnsConfig) {
+    if let Some(failover) = &config.failover {
+        if failover.failure_threshold > 1 || failover.recovery_threshold > 1 {
+            warn!(
+                "單次運行不保留連續健康檢查次數，記錄 {} 的故障轉移閾值大於 1，不會切換目標",
+                config.record_name
+            );
+        }
+    }
+    if config.ipv6_loss.action != Ipv6LossAction::Keep && config.ipv6_loss.after_failures > 1 {
+        warn!(
+            "單次運行不保留連續檢測失敗次數，記錄 {} 的 ipv6_loss 需要連續 {} 次失敗，不會撤回記錄",
+            config.record_name, config.ipv6_loss.after_failures
+        );
+    }
+}
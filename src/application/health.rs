@@ -0,0 +1,123 @@
+use crate::domain::config::{ConfigId, IpType};
+use crate::infrastructure::http::CircuitSnapshot;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+/// 就緒檢查默認允許錯過的更新間隔數
+pub const DEFAULT_MAX_MISSED_INTERVALS: u32 = 3;
+
+/// 單項檢查結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    /// 檢查通過
+    Ok,
+    /// 檢查未通過
+    Degraded,
+    /// 當前運行模式下不適用
+    Skipped,
+}
+
+/// 服務級別的就緒檢查項
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessCheck {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+impl ReadinessCheck {
+    /// 創建檢查項
+    pub fn new(name: &'static str, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// 單條記錄的就緒狀態
+///
+/// # 欄位
+///
+/// - `worker`: 自動更新任務是否仍在運行
+/// - `freshness`: 最近一次成功檢查是否在允許的間隔內
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordReadiness {
+    pub config_id: ConfigId,
+    pub record_name: String,
+    pub ip_type: IpType,
+    pub worker: CheckStatus,
+    pub freshness: CheckStatus,
+    pub last_success: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+impl RecordReadiness {
+    /// 記錄是否就緒
+    pub fn is_ready(&self) -> bool {
+        self.worker != CheckStatus::Degraded && self.freshness != CheckStatus::Degraded
+    }
+}
+
+/// 就緒檢查報告
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessReport {
+    pub ready: bool,
+    pub checks: Vec<ReadinessCheck>,
+    pub records: Vec<RecordReadiness>,
+    /// 記錄過失敗的上游主機的熔斷器狀態，僅供參考，不影響就緒結果
+    pub circuits: Vec<CircuitSnapshot>,
+}
+
+impl ReadinessReport {
+    /// 根據檢查項和記錄狀態創建報告，任一項未通過時報告為未就緒
+    pub fn new(checks: Vec<ReadinessCheck>, records: Vec<RecordReadiness>) -> Self {
+        let ready = checks.iter().all(|check| check.status != CheckStatus::Degraded)
+            && records.iter().all(RecordReadiness::is_ready);
+        Self { ready, checks, records, circuits: Vec::new() }
+    }
+    
+    /// 附加熔斷器狀態
+    ///
+    /// # 參數
+    ///
+    /// - `circuits`: 熔斷器快照
+    pub fn with_circuits(mut self, circuits: Vec<CircuitSnapshot>) -> Self {
+        self.circuits = circuits;
+        self
+    }
+}
+
+/// 判斷記錄最近一次成功檢查是否在允許的間隔內
+///
+/// 從未成功過的記錄從更新任務啟動時開始計時，沒有更新任務時不適用
+///
+/// # 參數
+///
+/// - `last_success`: 最近一次成功檢查的時間
+/// - `worker_started`: 更新任務啟動時間
+/// - `update_interval`: 更新間隔（秒）
+/// - `max_missed_intervals`: 允許錯過的更新間隔數
+/// - `now`: 當前時間
+pub fn record_freshness(
+    last_success: Option<DateTime<Utc>>,
+    worker_started: Option<DateTime<Utc>>,
+    update_interval: u64,
+    max_missed_intervals: u32,
+    now: DateTime<Utc>,
+) -> CheckStatus {
+    let Some(worker_started) = worker_started else {
+        return CheckStatus::Skipped;
+    };
+
+    let since = last_success.map_or(worker_started, |success| success.max(worker_started));
+    let allowed = Duration::seconds(update_interval.saturating_mul(max_missed_intervals.max(1) as u64) as i64);
+    if now - since <= allowed {
+        CheckStatus::Ok
+    } else {
+        CheckStatus::Degraded
+    }
+}
@@ -0,0 +1,128 @@
+use crate::application::events::{Event, EventListener, EventType, RecordRef};
+use crate::infrastructure::metrics::{metrics, MetricsRegistry};
+use async_trait::async_trait;
+
+/// 更新結果指標監聽器
+///
+/// 根據更新結果事件統計每條記錄的更新次數、失敗類型、IP 變更和記錄漂移
+pub struct MetricsEventListener {
+    registry: &'static MetricsRegistry,
+}
+
+impl MetricsEventListener {
+    /// 創建寫入全局指標註冊表的監聽器
+    pub fn new() -> Self {
+        Self::with_registry(metrics())
+    }
+
+    /// 創建寫入指定指標註冊表的監聽器
+    ///
+    /// # 參數
+    ///
+    /// - `registry`: 指標註冊表
+    pub fn with_registry(registry: &'static MetricsRegistry) -> Self {
+        Self { registry }
+    }
+
+    /// 記錄事件對應的指標
+    ///
+    /// # 參數
+    ///
+    /// - `event`: 事件
+    pub fn record(&self, event: &Event) {
+        match event {
+            Event::UpdateSucceeded(e) => self.registry.inc_counter(
+                "ddns_record_updates_total",
+                "DNS record update attempts by record and result",
+                &record_labels(&e.record, &[("result", "success")]),
+            ),
+            Event::UpdateFailed(e) => {
+                self.registry.inc_counter(
+                    "ddns_record_updates_total",
+                    "DNS record update attempts by record and result",
+                    &record_labels(&e.record, &[("result", "failure")]),
+                );
+                self.registry.inc_counter(
+                    "ddns_update_failures_total",
+                    "DNS record update failures by record and error kind",
+                    &record_labels(&e.record, &[("kind", e.error_kind.as_str())]),
+                );
+            },
+            Event::IpChanged(e) => self.registry.inc_counter(
+                "ddns_ip_changes_total",
+                "Public IP changes applied to DNS records",
+                &record_labels(&e.record, &[]),
+            ),
+            Event::DriftDetected(e) => self.registry.inc_counter(
+                "ddns_drift_detected_total",
+                "DNS records found modified outside this service",
+                &record_labels(&e.record, &[]),
+            ),
+            Event::HookExecuted(e) => {
+                let stage = e.stage.to_string();
+                self.registry.inc_counter(
+                    "ddns_hook_executions_total",
+                    "Update hook executions by stage and result",
+                    &record_labels(&e.record, &[
+                        ("stage", stage.as_str()),
+                        ("result", if e.success { "success" } else { "failure" }),
+                    ]),
+                );
+            },
+            Event::RecordWithdrawn(e) => {
+                let action = e.action.to_string();
+                self.registry.inc_counter(
+                    "ddns_records_withdrawn_total",
+                    "AAAA records deleted or parked after IPv6 connectivity was lost",
+                    &record_labels(&e.record, &[("action", action.as_str())]),
+                );
+            },
+            Event::FailoverSwitched(e) => {
+                let target = e.target.to_string();
+                self.registry.inc_counter(
+                    "ddns_failover_switches_total",
+                    "Failover switches between primary target and backup address",
+                    &record_labels(&e.record, &[("target", target.as_str())]),
+                );
+            },
+            _ => {},
+        }
+    }
+}
+
+impl Default for MetricsEventListener {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 記錄標籤
+fn record_labels<'a>(record: &'a RecordRef, extra: &[(&'a str, &'a str)]) -> Vec<(&'a str, &'a str)> {
+    let mut labels = vec![
+        ("config_id", record.config_id.as_str()),
+        ("record_name", record.record_name.as_str()),
+        ("record_type", record.record_type.as_str()),
+    ];
+    labels.extend_from_slice(extra);
+    labels
+}
+
+#[async_trait]
+impl EventListener for MetricsEventListener {
+    async fn handle_event(&self, event: Event) {
+        self.record(&event);
+    }
+
+    fn interested_in(&self, event_type: &EventType) -> bool {
+        matches!(
+            event_type,
+            EventType::UpdateSucceeded
+                | EventType::UpdateFailed
+                | EventType::IpChanged
+                | EventType::DriftDetected
+                | EventType::HookExecuted
+                | EventType::RecordWithdrawn
+                | EventType::FailoverSwitched
+        )
+    }
+}
@@ -1,8 +1,12 @@
+pub mod background;
 pub mod error;
 pub mod factories;
 pub mod config;
 pub mod ddns;
 pub mod events;
+pub mod health;
+pub mod notification;
+pub mod metrics;
 
 pub use factories::ServiceFactory;
 pub use error::ApplicationError;
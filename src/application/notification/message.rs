@@ -0,0 +1,110 @@
+use crate::application::events::Event;
+use crate::domain::config::Ipv6LossAction;
+use crate::domain::failover::FailoverTarget;
+use crate::domain::notification::Notification;
+use serde_json::Value;
+
+/// 將事件轉換為通知
+///
+/// # 參數
+///
+/// - `event`: 事件
+///
+/// # 返回
+///
+/// - `Notification`: 包含標題、正文和原始內容的通知
+pub fn notification_from_event(event: &Event) -> Notification {
+    let (title, message, record_key) = match event {
+        Event::IpChanged(e) => (
+            format!("{} IP 已變更", e.record.record_name),
+            format!(
+                "{} ({}) 的 IP 從 {} 變更為 {}",
+                e.record.record_name,
+                e.record.record_type,
+                e.old_ip.as_deref().unwrap_or("無"),
+                e.new_ip,
+            ),
+            Some(e.record.config_id.to_string()),
+        ),
+        Event::UpdateSucceeded(e) => (
+            format!("{} 更新成功", e.record.record_name),
+            format!("{} ({}) 已更新為 {}", e.record.record_name, e.record.record_type, e.ip),
+            Some(e.record.config_id.to_string()),
+        ),
+        Event::UpdateFailed(e) => (
+            format!("{} 更新失敗", e.record.record_name),
+            format!(
+                "{} ({}) 更新失敗 [{}]: {}",
+                e.record.record_name, e.record.record_type, e.error_kind, e.error,
+            ),
+            Some(e.record.config_id.to_string()),
+        ),
+        Event::DriftDetected(e) => (
+            format!("{} 記錄被修改", e.record.record_name),
+            format!(
+                "{} ({}) 的內容為 {}，與預期的 {} 不一致，正在恢復",
+                e.record.record_name, e.record.record_type, e.actual_ip, e.expected_ip,
+            ),
+            Some(e.record.config_id.to_string()),
+        ),
+        Event::HookExecuted(e) => (
+            format!("{} {} 鉤子{}", e.record.record_name, e.stage, if e.success { "執行成功" } else { "執行失敗" }),
+            match (e.timed_out, e.exit_code) {
+                (true, _) => format!("命令 `{}` 執行超時", e.command),
+                (false, Some(code)) => format!("命令 `{}` 退出碼 {}\n{}", e.command, code, e.stderr),
+                (false, None) => format!("命令 `{}` 無法完成: {}", e.command, e.stderr),
+            },
+            Some(e.record.config_id.to_string()),
+        ),
+        Event::RecordWithdrawn(e) => (
+            format!("{} 記錄已撤回", e.record.record_name),
+            format!(
+                "IPv6 連續 {} 次檢測失敗，{} ({}) 已{}",
+                e.failures,
+                e.record.record_name,
+                e.record.record_type,
+                match e.action {
+                    Ipv6LossAction::Park => "改為停放地址",
+                    _ => "刪除",
+                },
+            ),
+            Some(e.record.config_id.to_string()),
+        ),
+        Event::FailoverSwitched(e) => (
+            match e.target {
+                FailoverTarget::Backup => format!("{} 已切換到備用地址", e.record.record_name),
+                FailoverTarget::Primary => format!("{} 已切回主目標", e.record.record_name),
+            },
+            format!(
+                "{} ({}) 的健康檢查{}，記錄改為 {}",
+                e.record.record_name,
+                e.record.record_type,
+                match e.target {
+                    FailoverTarget::Backup => "連續失敗",
+                    FailoverTarget::Primary => "連續恢復",
+                },
+                e.address,
+            ),
+            Some(e.record.config_id.to_string()),
+        ),
+        Event::ConfigChanged => ("配置已變更".to_string(), "DDNS 配置已重新加載".to_string(), None),
+        Event::RestartDdnsService => ("服務重啟".to_string(), "DDNS 服務已重啟".to_string(), None),
+        Event::ForceUpdateDns { config_id } => (
+            "強制更新".to_string(),
+            match config_id {
+                Some(config_id) => format!("已請求強制更新記錄 {}", config_id),
+                None => "已請求強制更新所有記錄".to_string(),
+            },
+            config_id.as_ref().map(|config_id| config_id.to_string()),
+        ),
+        Event::ForceUpdateAll => ("強制更新".to_string(), "已請求強制更新所有記錄".to_string(), None),
+    };
+
+    Notification {
+        event: event.event_type().name().to_string(),
+        title,
+        message,
+        record_key,
+        payload: serde_json::to_value(event).unwrap_or(Value::Null),
+    }
+}
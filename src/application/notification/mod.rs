@@ -0,0 +1,7 @@
+mod message;
+mod rate_limit;
+mod service;
+
+pub use message::notification_from_event;
+pub use rate_limit::{NotificationRateLimiter, RateLimitDecision};
+pub use service::{NotificationService, DEFAULT_NOTIFY_EVENTS};
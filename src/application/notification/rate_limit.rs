@@ -0,0 +1,76 @@
+use crate::domain::config::RateLimitSettings;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 限流判斷結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitDecision {
+    /// 允許發送，附帶上次發送後被抑制的通知數量
+    Allowed { suppressed: u32 },
+    /// 超出限制，不發送
+    Limited,
+}
+
+/// 每個限流鍵的發送記錄
+#[derive(Default)]
+struct RateLimitEntry {
+    sent: VecDeque<Instant>,
+    suppressed: u32,
+}
+
+/// 通知限流器
+///
+/// 使用滑動窗口，每個鍵（通知目標 + 記錄）在窗口內最多發送固定數量的通知
+pub struct NotificationRateLimiter {
+    max_messages: usize,
+    window: Duration,
+    entries: Mutex<HashMap<String, RateLimitEntry>>,
+}
+
+impl NotificationRateLimiter {
+    /// 創建新的限流器
+    ///
+    /// # 參數
+    ///
+    /// - `settings`: 限流設置
+    pub fn new(settings: &RateLimitSettings) -> Self {
+        Self {
+            max_messages: settings.max_messages.max(1) as usize,
+            window: Duration::from_secs(settings.window_seconds),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 檢查是否允許發送，允許時記錄本次發送
+    ///
+    /// # 參數
+    ///
+    /// - `key`: 限流鍵
+    pub fn check(&self, key: &str) -> RateLimitDecision {
+        self.check_at(key, Instant::now())
+    }
+
+    /// 在指定時間點檢查是否允許發送
+    ///
+    /// # 參數
+    ///
+    /// - `key`: 限流鍵
+    /// - `now`: 當前時間
+    pub fn check_at(&self, key: &str, now: Instant) -> RateLimitDecision {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.entry(key.to_string()).or_default();
+
+        while entry.sent.front().is_some_and(|sent| now.duration_since(*sent) >= self.window) {
+            entry.sent.pop_front();
+        }
+
+        if entry.sent.len() >= self.max_messages {
+            entry.suppressed += 1;
+            return RateLimitDecision::Limited;
+        }
+
+        entry.sent.push_back(now);
+        RateLimitDecision::Allowed { suppressed: std::mem::take(&mut entry.suppressed) }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::application::background::background_tasks;
+use crate::application::config::ConfigService;
+use crate::application::events::{Event, EventListener, EventType};
+use crate::application::notification::message::notification_from_event;
+use crate::application::notification::rate_limit::{NotificationRateLimiter, RateLimitDecision};
+use crate::domain::config::NotificationSettings;
+use crate::domain::http::HttpClient;
+use crate::domain::notification::{Notification, Notifier};
+use crate::infrastructure::notification::build_notifiers;
+use async_trait::async_trait;
+use log::{error, info, warn};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// 未配置事件過濾時默認發送的事件
+pub const DEFAULT_NOTIFY_EVENTS: [EventType; 3] = [
+    EventType::IpChanged,
+    EventType::UpdateFailed,
+    EventType::DriftDetected,
+];
+
+/// 根據當前設置創建的通知目標
+struct NotifierSet {
+    settings: NotificationSettings,
+    notifiers: Vec<Arc<dyn Notifier>>,
+    rate_limiter: Arc<NotificationRateLimiter>,
+}
+
+impl NotifierSet {
+    fn new(settings: NotificationSettings, notifiers: Vec<Arc<dyn Notifier>>) -> Self {
+        Self {
+            rate_limiter: Arc::new(NotificationRateLimiter::new(&settings.rate_limit)),
+            settings,
+            notifiers,
+        }
+    }
+}
+
+/// 通知服務
+///
+/// 監聽應用事件，並按配置將事件發送到 Webhook、Slack、Discord、Telegram 和郵件
+pub struct NotificationService {
+    config_service: Arc<ConfigService>,
+    http_client: Arc<dyn HttpClient>,
+    notifier_set: Mutex<NotifierSet>,
+}
+
+impl NotificationService {
+    /// 創建新的通知服務
+    ///
+    /// # 參數
+    ///
+    /// - `config_service`: 配置服務，用於讀取最新的通知設置
+    /// - `http_client`: 基於 HTTP 的通知目標使用的客戶端（通常為帶重試的客戶端）
+    pub fn new(config_service: Arc<ConfigService>, http_client: Arc<dyn HttpClient>) -> Self {
+        Self {
+            config_service,
+            http_client,
+            notifier_set: Mutex::new(NotifierSet::new(NotificationSettings::default(), Vec::new())),
+        }
+    }
+
+    /// 通知目標是否訂閱了指定類型的事件
+    ///
+    /// # 參數
+    ///
+    /// - `events`: 通知目標配置的事件名稱，為空時使用默認事件
+    /// - `event_type`: 事件類型
+    pub fn should_notify(events: &[String], event_type: &EventType) -> bool {
+        if events.is_empty() {
+            return DEFAULT_NOTIFY_EVENTS.contains(event_type);
+        }
+        events.iter().any(|name| EventType::from_name(name).as_ref() == Some(event_type))
+    }
+
+    /// 檢查通知設置並記錄問題
+    ///
+    /// 無效的設置不會阻止其他通知目標工作，只記錄警告
+    ///
+    /// # 參數
+    ///
+    /// - `settings`: 通知設置
+    pub fn check_settings(settings: &NotificationSettings) {
+        if let Err(e) = settings.rate_limit.validate() {
+            warn!("通知限流設置無效: {}", e);
+        }
+
+        let event_lists = settings.webhooks.iter().map(|c| (c.display_name(), &c.events))
+            .chain(settings.slack.iter().chain(&settings.discord).map(|c| (c.display_name(), &c.events)))
+            .chain(settings.telegram.iter().map(|c| (c.display_name(), &c.events)))
+            .chain(settings.email.iter().map(|c| (c.display_name(), &c.events)));
+        for (name, events) in event_lists {
+            for event in events {
+                if EventType::from_name(event).is_none() {
+                    warn!("通知目標 {} 配置了未知事件: {}", name, event);
+                }
+            }
+        }
+    }
+
+    /// 重新讀取通知設置，設置變更時重建通知目標
+    pub async fn reload_settings(&self) {
+        let settings = self.config_service.get_notification_settings().await;
+        let mut notifier_set = self.notifier_set.lock().await;
+        if notifier_set.settings == settings {
+            return;
+        }
+
+        Self::check_settings(&settings);
+        let notifiers = build_notifiers(&settings, self.http_client.clone());
+        info!("已加載 {} 個通知目標", notifiers.len());
+        *notifier_set = NotifierSet::new(settings, notifiers);
+    }
+
+    /// 將通知發送到訂閱了該事件的通知目標
+    ///
+    /// 每個目標在獨立任務中發送，避免慢速目標阻塞事件分發
+    ///
+    /// # 參數
+    ///
+    /// - `notifiers`: 通知目標
+    /// - `rate_limiter`: 限流器
+    /// - `event_type`: 事件類型
+    /// - `notification`: 通知內容
+    pub fn dispatch(
+        notifiers: &[Arc<dyn Notifier>],
+        rate_limiter: &NotificationRateLimiter,
+        event_type: &EventType,
+        notification: &Notification,
+    ) {
+        for notifier in notifiers {
+            if !Self::should_notify(notifier.events(), event_type) {
+                continue;
+            }
+
+            let key = format!("{}|{}", notifier.name(), notification.record_key.as_deref().unwrap_or_default());
+            let mut notification = notification.clone();
+            match rate_limiter.check(&key) {
+                RateLimitDecision::Limited => {
+                    warn!("通知目標 {} 發送過於頻繁，已抑制 {} 通知", notifier.name(), notification.event);
+                    continue;
+                },
+                RateLimitDecision::Allowed { suppressed } if suppressed > 0 => {
+                    notification.message.push_str(&format!("\n（期間有 {} 條通知因限流被抑制）", suppressed));
+                },
+                RateLimitDecision::Allowed { .. } => {},
+            }
+
+            let notifier = notifier.clone();
+            background_tasks().spawn(async move {
+                match notifier.send(&notification).await {
+                    Ok(_) => info!("已發送 {} 通知到 {}", notification.event, notifier.name()),
+                    Err(e) => error!("發送 {} 通知到 {} 失敗: {}", notification.event, notifier.name(), e),
+                }
+            });
+        }
+    }
+}
+
+#[async_trait]
+impl EventListener for NotificationService {
+    async fn handle_event(&self, event: Event) {
+        self.reload_settings().await;
+
+        let notifier_set = self.notifier_set.lock().await;
+        if notifier_set.notifiers.is_empty() {
+            return;
+        }
+
+        let notification = notification_from_event(&event);
+        Self::dispatch(&notifier_set.notifiers, &notifier_set.rate_limiter, &event.event_type(), &notification);
+    }
+}
@@ -1,7 +1,13 @@
 use serde::{Deserialize, Serialize};
 use crate::domain::error::DomainError;
+use crate::domain::dns::template_placeholders;
+use crate::domain::config::{
+    content_revision, ConfigId, FailoverSettings, HookSettings, Ipv6LossAction, Ipv6LossSettings, PrefixDelegation, SourceSettings,
+};
 use std::fmt;
-use std::hash::Hash;
+
+/// 默認漂移檢查間隔（秒）
+const DEFAULT_DRIFT_CHECK_INTERVAL: u64 = 3600;
 
 /// IP 類型枚舉
 /// 
@@ -42,14 +48,25 @@ impl TryFrom<&str> for IpType {
 /// 
 /// # 欄位
 /// 
+/// - `id`: 穩定的配置 ID（缺少時由配置服務生成並寫回文件）
 /// - `api_token`: Cloudflare API 令牌
 /// - `zone_id`: Cloudflare 區域 ID
 /// - `record_id`: DNS 記錄 ID
 /// - `record_name`: DNS 記錄名稱
 /// - `update_interval`: 更新間隔（秒）
 /// - `ip_type`: IP 類型（IPv4 或 IPv6）
+/// - `hooks`: 更新前後執行的鉤子命令
+/// - `source`: IP 檢測綁定的網絡接口或本地地址（多出口環境）
+/// - `ipv6_loss`: IPv6 連通性消失時對 AAAA 記錄的處理方式
+/// - `prefix_delegation`: 將檢測到的 IPv6 前綴與主機後綴組合為記錄地址（為局域網內其他主機更新）
+/// - `record_type`: DNS 記錄類型（如 TXT、CNAME），默認按 IP 類型為 A 或 AAAA
+/// - `content`: 記錄內容模板，可引用 `{ip}`、`{ipv4}`、`{ipv6}`、`{hostname}`、`{timestamp}`、`{unix_time}`
+/// - `failover`: 主目標健康檢查失敗時將記錄切換到備用地址
+/// - `drift_check_interval`: 漂移檢查間隔（秒），未設置時為一小時，0 表示不檢查
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DdnsConfig {
+    #[serde(default)]
+    pub id: ConfigId,
     pub api_token: String,
     pub zone_id: String,
     pub record_id: String, 
@@ -57,9 +74,59 @@ pub struct DdnsConfig {
     pub update_interval: u64,  // 更新間隔（秒）
     #[serde(rename = "ip_type")]
     pub ip_type: IpType,
+    #[serde(default, skip_serializing_if = "HookSettings::is_empty")]
+    pub hooks: HookSettings,
+    #[serde(default, skip_serializing_if = "SourceSettings::is_default")]
+    pub source: SourceSettings,
+    #[serde(default, skip_serializing_if = "Ipv6LossSettings::is_default")]
+    pub ipv6_loss: Ipv6LossSettings,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix_delegation: Option<PrefixDelegation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failover: Option<FailoverSettings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drift_check_interval: Option<u64>,
 }
 
 impl DdnsConfig {
+    /// 獲取配置修訂標記
+    /// 
+    /// 根據配置內容計算，內容不變時標記不變，用於 API 的樂觀併發控制 (ETag)
+    pub fn revision(&self) -> String {
+        content_revision(self)
+    }
+    
+    /// 漂移檢查間隔
+    ///
+    /// IP 未變化時每隔此間隔查詢一次 Cloudflare 上的記錄，檢查是否被外部修改
+    ///
+    /// # 返回
+    ///
+    /// - `Option<chrono::Duration>`: 檢查間隔，關閉漂移檢查時為 None
+    pub fn drift_check_interval(&self) -> Option<chrono::Duration> {
+        match self.drift_check_interval.unwrap_or(DEFAULT_DRIFT_CHECK_INTERVAL) {
+            0 => None,
+            seconds => Some(chrono::Duration::seconds(seconds as i64)),
+        }
+    }
+    
+    /// 實際的 DNS 記錄類型
+    ///
+    /// 配置了 `record_type` 時使用其大寫形式，否則 IPv4 為 A、IPv6 為 AAAA
+    pub fn dns_record_type(&self) -> String {
+        match &self.record_type {
+            Some(record_type) => record_type.trim().to_ascii_uppercase(),
+            None => match self.ip_type {
+                IpType::IPv4 => "A".to_string(),
+                IpType::IPv6 => "AAAA".to_string(),
+            },
+        }
+    }
+    
     /// 驗證 DDNS 配置
     /// 
     /// # 返回
@@ -91,6 +158,63 @@ impl DdnsConfig {
             return Err(DomainError::validation("Update interval cannot be less than 5 seconds".to_string()));
         }
         
+        self.hooks.validate()?;
+        self.source.validate(&self.ip_type)?;
+        self.validate_record_content()?;
+        self.ipv6_loss.validate(self.ip_type == IpType::IPv6)?;
+        if let Some(prefix_delegation) = &self.prefix_delegation {
+            if self.ip_type != IpType::IPv6 {
+                return Err(DomainError::validation("prefix_delegation only applies to IPv6 records"));
+            }
+            prefix_delegation.validate()?;
+        }
+        // 固定記錄 ID 的記錄刪除後無法重新創建，只能停放
+        if self.ipv6_loss.action == Ipv6LossAction::Delete {
+            return Err(DomainError::validation(
+                "ipv6_loss action delete requires a record group or ip_type both; use park for records with a fixed record_id"
+            ));
+        }
+        Ok(())
+    }
+    
+    /// 驗證記錄類型和內容模板
+    fn validate_record_content(&self) -> Result<(), DomainError> {
+        let record_type = self.dns_record_type();
+        if record_type.is_empty() || !record_type.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(DomainError::validation(format!("Invalid record type: {:?}", self.record_type.as_deref().unwrap_or_default())));
+        }
+        
+        match (record_type.as_str(), &self.ip_type) {
+            ("A", IpType::IPv6) | ("AAAA", IpType::IPv4) => {
+                return Err(DomainError::validation(format!(
+                    "Record type {} does not match IP type {}", record_type, self.ip_type
+                )));
+            },
+            ("A", _) | ("AAAA", _) => {},
+            _ if self.content.is_none() => {
+                return Err(DomainError::validation(format!("Record type {} requires a content template", record_type)));
+            },
+            _ => {},
+        }
+        
+        if let Some(content) = &self.content {
+            if content.trim().is_empty() {
+                return Err(DomainError::validation("Content template cannot be empty"));
+            }
+            template_placeholders(content)?;
+        }
+        
+        if let Some(failover) = &self.failover {
+            if self.content.is_some() || !matches!(record_type.as_str(), "A" | "AAAA") {
+                return Err(DomainError::validation("failover only applies to A and AAAA records without a content template"));
+            }
+            failover.validate(&self.ip_type)?;
+        }
+        
+        // 撤回時寫入的停放地址只適用於 AAAA 記錄
+        if !self.ipv6_loss.is_default() && record_type != "AAAA" {
+            return Err(DomainError::validation("ipv6_loss only applies to AAAA records"));
+        }
         Ok(())
     }
 }
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::config::{DdnsConfig, HttpClientSettings, NotificationSettings, RecordGroup};
+use crate::domain::error::DomainError;
+
+/// 配置文件內容
+///
+/// 新格式為包含各個配置段的對象：
+///
+/// ```json
+/// { "configs": [...], "groups": [...], "notifications": { "webhooks": [...] }, "http": { "proxy": "..." } }
+/// ```
+///
+/// 同時兼容只包含 DDNS 配置數組的舊格式，以及單個 DDNS 配置對象。
+/// `ip_type` 為 `both` 的單條配置解析為只有一個主機名的記錄組，保存時寫入 `groups` 段
+///
+/// # 欄位
+///
+/// - `configs`: DDNS 配置列表
+/// - `groups`: 記錄組配置列表
+/// - `notifications`: 通知設置
+/// - `http`: HTTP 客戶端設置
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ConfigDocument {
+    #[serde(default)]
+    pub configs: Vec<DdnsConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<RecordGroup>,
+    #[serde(default, skip_serializing_if = "NotificationSettings::is_empty")]
+    pub notifications: NotificationSettings,
+    #[serde(default, skip_serializing_if = "HttpClientSettings::is_default")]
+    pub http: HttpClientSettings,
+}
+
+impl ConfigDocument {
+    /// 從 JSON 文本解析配置文件
+    pub fn parse(contents: &str) -> Result<Self, DomainError> {
+        let value: serde_json::Value = serde_json::from_str(contents)
+            .map_err(|e| DomainError::config(format!("Failed to parse config file: {}", e)))?;
+
+        match value {
+            // 舊格式：DDNS 配置數組
+            serde_json::Value::Array(entries) => {
+                let (configs, groups) = parse_configs(entries)?;
+                Ok(Self { configs, groups, ..Default::default() })
+            },
+            serde_json::Value::Object(ref map) if !map.contains_key("configs") && map.contains_key("zone_id") => {
+                // 舊格式：單個 DDNS 配置
+                let (configs, groups) = parse_configs(vec![value])?;
+                Ok(Self { configs, groups, ..Default::default() })
+            },
+            serde_json::Value::Object(mut map) => {
+                let entries = match map.remove("configs") {
+                    Some(serde_json::Value::Array(entries)) => entries,
+                    Some(other) => vec![other],
+                    None => Vec::new(),
+                };
+                let mut document: Self = serde_json::from_value(serde_json::Value::Object(map))
+                    .map_err(|e| DomainError::config(format!("Failed to parse config file: {}", e)))?;
+                let (configs, groups) = parse_configs(entries)?;
+                document.configs = configs;
+                document.groups.extend(groups);
+                Ok(document)
+            },
+            _ => serde_json::from_value(value)
+                .map_err(|e| DomainError::config(format!("Failed to parse config file: {}", e))),
+        }
+    }
+
+    /// 序列化為 JSON 文本
+    ///
+    /// 只有 DDNS 配置時寫出舊的數組格式，保持與舊版本的兼容
+    pub fn to_json(&self) -> Result<String, DomainError> {
+        let result = if self.has_only_configs() {
+            serde_json::to_string_pretty(&self.configs)
+        } else {
+            serde_json::to_string_pretty(self)
+        };
+        result.map_err(|e| DomainError::config(format!("Failed to serialize configs: {}", e)))
+    }
+
+    /// 是否只包含 DDNS 配置
+    fn has_only_configs(&self) -> bool {
+        self.groups.is_empty() && self.notifications.is_empty() && self.http.is_default()
+    }
+}
+
+/// 只適用於固定記錄的單條配置欄位，雙棧配置按名稱查找 A 和 AAAA 記錄，不能設置
+const SINGLE_RECORD_FIELDS: &[&str] = &["record_id", "record_type", "content", "failover"];
+
+/// 解析 DDNS 配置列表
+///
+/// `ip_type` 為 `both` 的配置轉換為只有一個主機名的記錄組，展開後與記錄組一樣管理 A 和 AAAA 兩條記錄
+///
+/// # 返回
+///
+/// - `Result<(Vec<DdnsConfig>, Vec<RecordGroup>), DomainError>`: 單條配置和由雙棧配置轉換的記錄組
+fn parse_configs(entries: Vec<serde_json::Value>) -> Result<(Vec<DdnsConfig>, Vec<RecordGroup>), DomainError> {
+    let mut configs = Vec::new();
+    let mut groups = Vec::new();
+    for entry in entries {
+        match entry {
+            serde_json::Value::Object(map) if map.get("ip_type").and_then(|v| v.as_str()) == Some("both") => {
+                groups.push(dual_stack_group(map)?);
+            },
+            entry => configs.push(serde_json::from_value(entry)
+                .map_err(|e| DomainError::config(format!("Failed to parse config file: {}", e)))?),
+        }
+    }
+    Ok((configs, groups))
+}
+
+/// 將雙棧單條配置轉換為只有一個主機名的記錄組
+fn dual_stack_group(mut map: serde_json::Map<String, serde_json::Value>) -> Result<RecordGroup, DomainError> {
+    let record_name = map.remove("record_name").unwrap_or_default();
+    for field in SINGLE_RECORD_FIELDS {
+        match map.remove(*field) {
+            None | Some(serde_json::Value::Null) => {},
+            Some(serde_json::Value::String(value)) if value.trim().is_empty() => {},
+            Some(_) => return Err(DomainError::config(format!(
+                "Dual-stack config {} looks up A and AAAA records by name, remove {}", record_name, field
+            ))),
+        }
+    }
+    map.insert("hostnames".to_string(), serde_json::Value::Array(vec![record_name]));
+    serde_json::from_value(serde_json::Value::Object(map))
+        .map_err(|e| DomainError::config(format!("Failed to parse config file: {}", e)))
+}
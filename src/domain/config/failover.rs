@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::config::IpType;
+use crate::domain::error::DomainError;
+use std::net::IpAddr;
+
+fn default_threshold() -> u32 {
+    3
+}
+
+fn default_check_timeout_seconds() -> u64 {
+    5
+}
+
+/// 故障轉移設置
+///
+/// 每次更新時對主目標（檢測到的地址）執行健康檢查，連續失敗達到閾值後將記錄改為備用地址，
+/// 連續恢復達到閾值後改回主目標，兩個閾值避免記錄在兩個地址之間反覆切換
+///
+/// # 欄位
+///
+/// - `backup`: 備用地址（如 VPS 的地址），必須與記錄的 IP 類型一致
+/// - `checks`: 健康檢查，全部通過時主目標視為健康
+/// - `failure_threshold`: 連續多少次檢查失敗後切換到備用地址，默認 3
+/// - `recovery_threshold`: 切換後連續多少次檢查成功才切回主目標，默認 3
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FailoverSettings {
+    pub backup: IpAddr,
+    pub checks: Vec<HealthCheck>,
+    #[serde(default = "default_threshold")]
+    pub failure_threshold: u32,
+    #[serde(default = "default_threshold")]
+    pub recovery_threshold: u32,
+}
+
+impl FailoverSettings {
+    /// 驗證故障轉移設置
+    ///
+    /// # 參數
+    ///
+    /// - `ip_type`: 記錄的 IP 類型，備用地址必須屬於同一協議族
+    pub fn validate(&self, ip_type: &IpType) -> Result<(), DomainError> {
+        let matches_family = match ip_type {
+            IpType::IPv4 => self.backup.is_ipv4(),
+            IpType::IPv6 => self.backup.is_ipv6(),
+        };
+        if !matches_family {
+            return Err(DomainError::validation(format!(
+                "Failover backup address {} does not match IP type {}",
+                self.backup, ip_type
+            )));
+        }
+        if self.checks.is_empty() {
+            return Err(DomainError::validation("Failover requires at least one health check"));
+        }
+        if self.failure_threshold == 0 || self.recovery_threshold == 0 {
+            return Err(DomainError::validation("Failover thresholds must be at least 1"));
+        }
+        for check in &self.checks {
+            check.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// 健康檢查
+///
+/// # 變體
+///
+/// - `Tcp`: 連接主目標的指定端口
+/// - `Http`: 發送 GET 請求，`url` 中的 `{ip}` 替換為主目標地址；
+///   未設置 `expected_status` 時任何 2xx 狀態碼視為成功
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum HealthCheck {
+    Tcp {
+        port: u16,
+        #[serde(default = "default_check_timeout_seconds")]
+        timeout_seconds: u64,
+    },
+    Http {
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expected_status: Option<u16>,
+        #[serde(default = "default_check_timeout_seconds")]
+        timeout_seconds: u64,
+    },
+}
+
+impl HealthCheck {
+    /// 超時時間（秒）
+    pub fn timeout_seconds(&self) -> u64 {
+        match self {
+            HealthCheck::Tcp { timeout_seconds, .. } | HealthCheck::Http { timeout_seconds, .. } => *timeout_seconds,
+        }
+    }
+
+    /// 將 HTTP 檢查的 URL 中的 `{ip}` 替換為目標地址（IPv6 地址加方括號）
+    ///
+    /// # 參數
+    ///
+    /// - `url`: URL 模板
+    /// - `target`: 主目標地址
+    pub fn target_url(url: &str, target: IpAddr) -> String {
+        let host = match target {
+            IpAddr::V4(address) => address.to_string(),
+            IpAddr::V6(address) => format!("[{}]", address),
+        };
+        url.replace("{ip}", &host)
+    }
+
+    /// 驗證健康檢查
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.timeout_seconds() == 0 {
+            return Err(DomainError::validation("Health check timeout must be at least 1 second"));
+        }
+        match self {
+            HealthCheck::Tcp { port, .. } => {
+                if *port == 0 {
+                    return Err(DomainError::validation("Health check port cannot be 0"));
+                }
+            },
+            HealthCheck::Http { url, expected_status, .. } => {
+                let example = Self::target_url(url, IpAddr::from([192, 0, 2, 1]));
+                let parsed = url::Url::parse(&example).map_err(|e| {
+                    DomainError::validation(format!("Invalid health check URL {}: {}", url, e))
+                })?;
+                if !matches!(parsed.scheme(), "http" | "https") {
+                    return Err(DomainError::validation(format!("Health check URL must use http or https: {}", url)));
+                }
+                if expected_status.is_some_and(|status| !(100..=599).contains(&status)) {
+                    return Err(DomainError::validation(format!("Invalid expected status for {}", url)));
+                }
+            },
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::config::{
+    content_revision, ConfigId, DdnsConfig, GroupPrefixDelegation, HookSettings, IpType, Ipv6LossSettings, SourceSettings,
+};
+use crate::domain::error::DomainError;
+use std::collections::HashSet;
+use std::fmt;
+
+/// 記錄組管理的地址族
+///
+/// # 變體
+///
+/// - `IPv4`: 只管理 A 記錄
+/// - `IPv6`: 只管理 AAAA 記錄
+/// - `Both`: 同時管理 A 和 AAAA 記錄
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum IpFamilies {
+    #[default]
+    #[serde(rename = "ipv4")]
+    IPv4,
+    #[serde(rename = "ipv6")]
+    IPv6,
+    #[serde(rename = "both")]
+    Both,
+}
+
+impl IpFamilies {
+    /// 包含的 IP 類型
+    pub fn ip_types(&self) -> Vec<IpType> {
+        match self {
+            IpFamilies::IPv4 => vec![IpType::IPv4],
+            IpFamilies::IPv6 => vec![IpType::IPv6],
+            IpFamilies::Both => vec![IpType::IPv4, IpType::IPv6],
+        }
+    }
+}
+
+impl fmt::Display for IpFamilies {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IpFamilies::IPv4 => write!(f, "ipv4"),
+            IpFamilies::IPv6 => write!(f, "ipv6"),
+            IpFamilies::Both => write!(f, "both"),
+        }
+    }
+}
+
+/// 記錄組配置
+///
+/// 使用同一個令牌和區域管理多個主機名，可同時管理 A 和 AAAA 記錄。
+/// 運行時展開為每個主機名、每個地址族一條記錄任務，記錄 ID 按名稱和類型從 Cloudflare 查找
+///
+/// # 欄位
+///
+/// - `id`: 穩定的記錄組 ID（缺少時由配置服務生成並寫回文件）
+/// - `name`: 顯示名稱（可選）
+/// - `api_token`: Cloudflare API 令牌
+/// - `zone_id`: Cloudflare 區域 ID
+/// - `hostnames`: 主機名列表
+/// - `ip_type`: 管理的地址族（ipv4、ipv6 或 both）
+/// - `update_interval`: 更新間隔（秒）
+/// - `hooks`: 每條記錄更新前後執行的鉤子命令
+/// - `source`: IP 檢測綁定的網絡接口或本地地址
+/// - `ipv6_loss`: IPv6 連通性消失時對 AAAA 記錄的處理方式（刪除後 IPv6 恢復時重新創建）
+/// - `prefix_delegation`: 各主機名的 IPv6 主機後綴，與檢測到的前綴組合為 AAAA 記錄地址
+/// - `drift_check_interval`: 漂移檢查間隔（秒），同單條記錄配置
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecordGroup {
+    #[serde(default)]
+    pub id: ConfigId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub api_token: String,
+    pub zone_id: String,
+    pub hostnames: Vec<String>,
+    #[serde(default)]
+    pub ip_type: IpFamilies,
+    pub update_interval: u64,
+    #[serde(default, skip_serializing_if = "HookSettings::is_empty")]
+    pub hooks: HookSettings,
+    #[serde(default, skip_serializing_if = "SourceSettings::is_default")]
+    pub source: SourceSettings,
+    #[serde(default, skip_serializing_if = "Ipv6LossSettings::is_default")]
+    pub ipv6_loss: Ipv6LossSettings,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix_delegation: Option<GroupPrefixDelegation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drift_check_interval: Option<u64>,
+}
+
+impl RecordGroup {
+    /// 獲取記錄組修訂標記
+    ///
+    /// 根據配置內容計算，用於 API 的樂觀併發控制 (ETag)
+    pub fn revision(&self) -> String {
+        content_revision(self)
+    }
+
+    /// 驗證記錄組配置
+    ///
+    /// # 返回
+    ///
+    /// - `Result<(), DomainError>`: 成功時返回 ()，失敗時返回錯誤
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.api_token.trim().is_empty() {
+            return Err(DomainError::validation("API token cannot be empty".to_string()));
+        }
+        if self.zone_id.trim().is_empty() {
+            return Err(DomainError::validation("Zone ID cannot be empty".to_string()));
+        }
+        if self.hostnames.is_empty() {
+            return Err(DomainError::validation("Record group must contain at least one hostname".to_string()));
+        }
+
+        let mut seen = HashSet::new();
+        for hostname in &self.hostnames {
+            if hostname.trim().is_empty() {
+                return Err(DomainError::validation("Hostname cannot be empty".to_string()));
+            }
+            if !seen.insert(hostname.trim().to_lowercase()) {
+                return Err(DomainError::validation(format!("Duplicate hostname in record group: {}", hostname)));
+            }
+        }
+
+        if self.update_interval < 5 {
+            return Err(DomainError::validation("Update interval cannot be less than 5 seconds".to_string()));
+        }
+
+        self.hooks.validate()?;
+        for ip_type in self.ip_type.ip_types() {
+            self.source.validate(&ip_type)?;
+        }
+        let manages_ipv6 = self.ip_type.ip_types().contains(&IpType::IPv6);
+        self.ipv6_loss.validate(manages_ipv6)?;
+        if let Some(prefix_delegation) = &self.prefix_delegation {
+            if !manages_ipv6 {
+                return Err(DomainError::validation("prefix_delegation only applies to IPv6 records"));
+            }
+            prefix_delegation.validate(&self.hostnames)?;
+        }
+        Ok(())
+    }
+
+    /// 展開後單條記錄的配置 ID
+    ///
+    /// # 參數
+    ///
+    /// - `hostname`: 主機名
+    /// - `ip_type`: IP 類型
+    pub fn member_id(&self, hostname: &str, ip_type: &IpType) -> ConfigId {
+        ConfigId::new(format!("{}:{}:{}", self.id, hostname.trim().to_lowercase(), ip_type))
+    }
+
+    /// 展開為每個主機名、每個地址族一條的記錄配置
+    ///
+    /// 展開後的配置沒有記錄 ID，更新時按名稱和類型查找，不存在時創建
+    pub fn expand(&self) -> Vec<DdnsConfig> {
+        let mut configs = Vec::new();
+        for hostname in &self.hostnames {
+            for ip_type in self.ip_type.ip_types() {
+                let (ipv6_loss, prefix_delegation) = match ip_type {
+                    IpType::IPv6 => (
+                        self.ipv6_loss.clone(),
+                        self.prefix_delegation.as_ref().and_then(|delegation| delegation.for_hostname(hostname)),
+                    ),
+                    IpType::IPv4 => (Ipv6LossSettings::default(), None),
+                };
+                configs.push(DdnsConfig {
+                    id: self.member_id(hostname, &ip_type),
+                    api_token: self.api_token.clone(),
+                    zone_id: self.zone_id.clone(),
+                    record_id: String::new(),
+                    record_name: hostname.trim().to_string(),
+                    update_interval: self.update_interval,
+                    ip_type,
+                    hooks: self.hooks.clone(),
+                    source: self.source.clone(),
+                    ipv6_loss,
+                    prefix_delegation,
+                    record_type: None,
+                    content: None,
+                    failover: None,
+                    drift_check_interval: self.drift_check_interval,
+                });
+            }
+        }
+        configs
+    }
+}
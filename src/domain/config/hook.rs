@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::error::DomainError;
+
+/// 更新鉤子設置
+///
+/// 鉤子只在 DNS 記錄需要更新時執行（IP 變更或記錄漂移）
+///
+/// # 欄位
+///
+/// - `pre_update`: 更新 DNS 記錄前執行的命令
+/// - `post_update`: 更新 DNS 記錄後執行的命令（無論更新成功與否）
+/// - `abort_on_pre_hook_failure`: 前置鉤子失敗時是否放棄本次更新
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct HookSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pre_update: Option<HookCommand>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_update: Option<HookCommand>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub abort_on_pre_hook_failure: bool,
+}
+
+impl HookSettings {
+    /// 是否沒有配置任何鉤子
+    pub fn is_empty(&self) -> bool {
+        self.pre_update.is_none() && self.post_update.is_none() && !self.abort_on_pre_hook_failure
+    }
+
+    /// 驗證鉤子設置
+    pub fn validate(&self) -> Result<(), DomainError> {
+        for hook in self.pre_update.iter().chain(&self.post_update) {
+            hook.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// 鉤子命令
+///
+/// 命令通過系統 shell 執行（Unix 為 `sh -c`，Windows 為 `cmd /C`）
+///
+/// # 欄位
+///
+/// - `command`: 要執行的命令
+/// - `timeout_seconds`: 超時時間（秒），超時後終止命令
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HookCommand {
+    pub command: String,
+    #[serde(default = "HookCommand::default_timeout_seconds")]
+    pub timeout_seconds: u64,
+}
+
+impl HookCommand {
+    fn default_timeout_seconds() -> u64 {
+        30
+    }
+
+    /// 創建使用默認超時的鉤子命令
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            timeout_seconds: Self::default_timeout_seconds(),
+        }
+    }
+
+    /// 驗證鉤子命令
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.command.trim().is_empty() {
+            return Err(DomainError::validation("Hook command cannot be empty"));
+        }
+        if self.timeout_seconds == 0 {
+            return Err(DomainError::validation("Hook timeout must be greater than zero"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::error::DomainError;
+use std::net::IpAddr;
+
+/// 支持的代理協議
+const PROXY_SCHEMES: [&str; 4] = ["http", "https", "socks5", "socks5h"];
+
+/// HTTP 客戶端設置
+///
+/// 用於所有出站請求（Cloudflare API、IP 查詢服務和通知）
+///
+/// # 欄位
+///
+/// - `proxy`: 代理地址，支持 `http://`、`https://`、`socks5://` 和 `socks5h://`；未設置時使用 `HTTP_PROXY`/`HTTPS_PROXY` 環境變量
+/// - `connect_timeout_secs`: 建立連接的超時時間（秒）
+/// - `timeout_secs`: 單次請求的總超時時間（秒）
+/// - `ca_bundle`: 額外信任的 CA 證書文件（PEM 格式，可包含多個證書）
+/// - `bind_address`: 出站連接使用的本地地址
+/// - `pin_ip_family`: 查詢公網 IP 時是否強制使用對應協議族連接（IPv4 記錄只走 IPv4，IPv6 記錄只走 IPv6）
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct HttpClientSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bind_address: Option<IpAddr>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pin_ip_family: bool,
+}
+
+impl HttpClientSettings {
+    /// 是否為默認設置
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 驗證 HTTP 客戶端設置
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if let Some(proxy) = &self.proxy {
+            let parsed = url::Url::parse(proxy)
+                .map_err(|e| DomainError::validation(format!("Invalid proxy URL {}: {}", proxy, e)))?;
+            if !PROXY_SCHEMES.contains(&parsed.scheme()) {
+                return Err(DomainError::validation(format!(
+                    "Proxy URL must use one of {}: {}",
+                    PROXY_SCHEMES.join(", "),
+                    proxy
+                )));
+            }
+        }
+        if self.connect_timeout_secs == Some(0) || self.timeout_secs == Some(0) {
+            return Err(DomainError::validation("HTTP timeouts must be greater than zero"));
+        }
+        if self.ca_bundle.as_deref().is_some_and(|path| path.trim().is_empty()) {
+            return Err(DomainError::validation("CA bundle path must not be empty"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use rand::Rng;
+use std::fmt;
+
+/// 配置 ID
+///
+/// 每個 DDNS 配置的穩定標識，保存在配置文件中，
+/// 供服務工廠、狀態存儲和 API 統一使用
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ConfigId(String);
+
+impl ConfigId {
+    /// 從字符串創建配置 ID
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// 生成新的隨機配置 ID
+    pub fn generate() -> Self {
+        Self(format!("{:016x}", rand::thread_rng().gen::<u64>()))
+    }
+
+    /// 獲取字符串形式
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// 是否為空（配置文件中未設置）
+    pub fn is_empty(&self) -> bool {
+        self.0.trim().is_empty()
+    }
+}
+
+impl fmt::Display for ConfigId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for ConfigId {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for ConfigId {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl AsRef<str> for ConfigId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::error::DomainError;
+use std::fmt;
+use std::net::Ipv6Addr;
+
+/// IPv6 連通性消失時對 AAAA 記錄的處理方式
+///
+/// # 變體
+///
+/// - `Keep`: 保留記錄原有地址（默認）
+/// - `Delete`: 刪除記錄，IPv6 恢復後重新創建（只適用於記錄組，記錄按名稱查找）
+/// - `Park`: 將記錄改為停放地址，IPv6 恢復後改回實際地址
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Ipv6LossAction {
+    #[default]
+    Keep,
+    Delete,
+    Park,
+}
+
+impl fmt::Display for Ipv6LossAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ipv6LossAction::Keep => write!(f, "keep"),
+            Ipv6LossAction::Delete => write!(f, "delete"),
+            Ipv6LossAction::Park => write!(f, "park"),
+        }
+    }
+}
+
+/// 默認連續檢測失敗次數
+const DEFAULT_AFTER_FAILURES: u32 = 3;
+
+fn default_after_failures() -> u32 {
+    DEFAULT_AFTER_FAILURES
+}
+
+/// 默認停放地址（RFC 6666 丟棄前綴）
+fn default_park_address() -> Ipv6Addr {
+    Ipv6Addr::new(0x100, 0, 0, 0, 0, 0, 0, 0)
+}
+
+/// IPv6 連通性消失時的 AAAA 記錄處理設置
+///
+/// 雙棧主機失去 IPv6 後，保留失效的 AAAA 記錄會讓 Happy Eyeballs 客戶端先嘗試不可達的地址，
+/// 可在連續多次檢測失敗後刪除記錄或改為停放地址
+///
+/// # 欄位
+///
+/// - `action`: 處理方式
+/// - `after_failures`: 連續檢測失敗多少次後處理
+/// - `park_address`: `park` 時寫入的地址，默認為 `100::`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ipv6LossSettings {
+    #[serde(default)]
+    pub action: Ipv6LossAction,
+    #[serde(default = "default_after_failures")]
+    pub after_failures: u32,
+    #[serde(default = "default_park_address")]
+    pub park_address: Ipv6Addr,
+}
+
+impl Default for Ipv6LossSettings {
+    fn default() -> Self {
+        Self {
+            action: Ipv6LossAction::Keep,
+            after_failures: DEFAULT_AFTER_FAILURES,
+            park_address: default_park_address(),
+        }
+    }
+}
+
+impl Ipv6LossSettings {
+    /// 是否為默認設置（保留記錄）
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 驗證設置
+    ///
+    /// # 參數
+    ///
+    /// - `manages_ipv6`: 是否管理 AAAA 記錄，否則只允許默認設置
+    pub fn validate(&self, manages_ipv6: bool) -> Result<(), DomainError> {
+        if !manages_ipv6 && !self.is_default() {
+            return Err(DomainError::validation("ipv6_loss only applies to IPv6 records"));
+        }
+        if self.after_failures == 0 {
+            return Err(DomainError::validation("ipv6_loss.after_failures must be at least 1"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::domain::error::DomainError;
+use std::env;
+use std::fmt;
+
+/// 日誌輸出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// 人類可讀的文本行
+    #[default]
+    Text,
+    /// 每行一個 JSON 對象，供日誌管道解析
+    Json,
+}
+
+impl LogFormat {
+    /// 從 `DDNS_LOG_FORMAT` 環境變量讀取日誌格式，未設置時為文本格式
+    pub fn from_env() -> Result<Self, DomainError> {
+        match env::var("DDNS_LOG_FORMAT") {
+            Ok(value) => LogFormat::try_from(value.as_str()),
+            Err(_) => Ok(LogFormat::Text),
+        }
+    }
+}
+
+impl TryFrom<&str> for LogFormat {
+    type Error = DomainError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(DomainError::validation(format!("Invalid log format: {}", value))),
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogFormat::Text => write!(f, "text"),
+            LogFormat::Json => write!(f, "json"),
+        }
+    }
+}
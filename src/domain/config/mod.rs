@@ -1,13 +1,58 @@
 pub mod ddns;
+pub mod document;
+pub mod failover;
+pub mod group;
+pub mod hook;
+pub mod http;
+pub mod id;
+pub mod ipv6_loss;
+pub mod logging;
+pub mod notification;
+pub mod prefix;
 pub mod settings;
+pub mod source;
+pub mod telemetry;
 
 pub use ddns::{DdnsConfig, IpType, DdnsConfigError};
+pub use document::ConfigDocument;
+pub use failover::{FailoverSettings, HealthCheck};
+pub use group::{IpFamilies, RecordGroup};
+pub use hook::{HookCommand, HookSettings};
+pub use http::HttpClientSettings;
+pub use id::ConfigId;
+pub use ipv6_loss::{Ipv6LossAction, Ipv6LossSettings};
+pub use logging::LogFormat;
+pub use notification::{
+    ChatWebhookConfig, EmailConfig, NotificationSettings, RateLimitSettings, SmtpSecurity, TelegramConfig,
+    WebhookConfig,
+};
+pub use prefix::{GroupPrefixDelegation, PrefixDelegation};
 pub use settings::{Settings, ServerSettings};
+pub use source::SourceSettings;
+pub use telemetry::{TelemetrySettings, TraceExporter};
 
 use std::sync::Arc;
 use config::{Config, ConfigError, Environment};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 use crate::domain::error::DomainError;
 
+/// 根據序列化後的配置內容計算修訂標記
+///
+/// 使用 SHA-256，結果不隨 Rust 版本或進程變化，可在重啟後繼續作為 ETag 使用
+///
+/// # 參數
+///
+/// - `value`: 要計算標記的配置
+///
+/// # 返回
+///
+/// - `String`: 十六進制摘要
+pub(crate) fn content_revision<T: Serialize>(value: &T) -> String {
+    let serialized = serde_json::to_vec(value).unwrap_or_default();
+    hex::encode(Sha256::digest(&serialized))
+}
+
 /// 配置加載器
 #[derive(Debug, Clone)]
 pub struct ConfigLoader {
@@ -59,13 +104,24 @@ impl ConfigLoader {
         let ip_type_str = self.config.get_string("ip_type").unwrap_or_else(|_| "ipv4".to_string());
         let ip_type = IpType::try_from(ip_type_str.as_str())?;
         
+        let id = ConfigId::new(self.config.get_string("id").unwrap_or_else(|_| format!("env-{}", ip_type)));
+        
         Ok(DdnsConfig {
+            id,
             api_token,
             zone_id,
             record_id,
             record_name,
             update_interval,
             ip_type,
+            hooks: HookSettings::default(),
+            source: Default::default(),
+            ipv6_loss: Default::default(),
+            prefix_delegation: None,
+            record_type: None,
+            content: None,
+            failover: None,
+            drift_check_interval: None,
         })
     }
     
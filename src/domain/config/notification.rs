@@ -0,0 +1,307 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::error::DomainError;
+use std::collections::HashMap;
+
+/// 通知設置
+///
+/// # 欄位
+///
+/// - `webhooks`: Webhook 通知目標列表
+/// - `slack`: Slack Incoming Webhook 列表
+/// - `discord`: Discord Webhook 列表
+/// - `telegram`: Telegram 機器人列表
+/// - `email`: SMTP 郵件通知列表
+/// - `rate_limit`: 每個通知目標的限流設置
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct NotificationSettings {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<WebhookConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub slack: Vec<ChatWebhookConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub discord: Vec<ChatWebhookConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub telegram: Vec<TelegramConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub email: Vec<EmailConfig>,
+    #[serde(default, skip_serializing_if = "RateLimitSettings::is_default")]
+    pub rate_limit: RateLimitSettings,
+}
+
+impl NotificationSettings {
+    /// 是否沒有配置任何通知目標
+    pub fn is_empty(&self) -> bool {
+        self.webhooks.is_empty()
+            && self.slack.is_empty()
+            && self.discord.is_empty()
+            && self.telegram.is_empty()
+            && self.email.is_empty()
+            && self.rate_limit.is_default()
+    }
+
+    /// 驗證通知設置
+    pub fn validate(&self) -> Result<(), DomainError> {
+        for webhook in &self.webhooks {
+            webhook.validate()?;
+        }
+        for chat in self.slack.iter().chain(&self.discord) {
+            chat.validate()?;
+        }
+        for telegram in &self.telegram {
+            telegram.validate()?;
+        }
+        for email in &self.email {
+            email.validate()?;
+        }
+        self.rate_limit.validate()
+    }
+}
+
+/// 驗證 URL 使用 http 或 https
+fn validate_http_url(url: &str) -> Result<(), DomainError> {
+    let parsed = url::Url::parse(url)
+        .map_err(|e| DomainError::validation(format!("Invalid notification URL {}: {}", url, e)))?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return Err(DomainError::validation(format!("Notification URL must use http or https: {}", url)));
+    }
+    Ok(())
+}
+
+/// Webhook 通知配置
+///
+/// # 欄位
+///
+/// - `name`: 顯示名稱（可選，用於日誌）
+/// - `url`: 接收通知的 URL
+/// - `events`: 要發送的事件名稱（如 `ip-changed`、`update-failed`），為空時發送默認事件
+/// - `template`: JSON 模板，字符串中的 `{{變量}}` 會被事件內容替換；為空時發送默認格式
+/// - `secret`: HMAC-SHA256 簽名密鑰（可選）
+/// - `headers`: 額外的請求頭
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WebhookConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+}
+
+impl WebhookConfig {
+    /// 用於日誌的顯示名稱
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.url)
+    }
+
+    /// 驗證 Webhook 配置
+    pub fn validate(&self) -> Result<(), DomainError> {
+        validate_http_url(&self.url)?;
+        if self.secret.as_deref().is_some_and(|secret| secret.is_empty()) {
+            return Err(DomainError::validation(format!("Webhook secret cannot be empty: {}", self.display_name())));
+        }
+        Ok(())
+    }
+}
+
+/// Slack / Discord 通知配置
+///
+/// # 欄位
+///
+/// - `name`: 顯示名稱（可選，用於日誌）
+/// - `webhook_url`: Incoming Webhook URL
+/// - `events`: 要發送的事件名稱，為空時發送默認事件
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChatWebhookConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub webhook_url: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+}
+
+impl ChatWebhookConfig {
+    /// 用於日誌的顯示名稱
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.webhook_url)
+    }
+
+    /// 驗證配置
+    pub fn validate(&self) -> Result<(), DomainError> {
+        validate_http_url(&self.webhook_url)
+    }
+}
+
+/// Telegram 通知配置
+///
+/// # 欄位
+///
+/// - `name`: 顯示名稱（可選，用於日誌）
+/// - `bot_token`: 機器人令牌
+/// - `chat_id`: 接收消息的聊天 ID
+/// - `api_url`: Bot API 地址（可選，默認為 `https://api.telegram.org`）
+/// - `events`: 要發送的事件名稱，為空時發送默認事件
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TelegramConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub bot_token: String,
+    pub chat_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+}
+
+impl TelegramConfig {
+    /// 默認的 Bot API 地址
+    pub const DEFAULT_API_URL: &'static str = "https://api.telegram.org";
+
+    /// 用於日誌的顯示名稱
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.chat_id)
+    }
+
+    /// Bot API 地址
+    pub fn api_url(&self) -> &str {
+        self.api_url.as_deref().unwrap_or(Self::DEFAULT_API_URL).trim_end_matches('/')
+    }
+
+    /// 驗證配置
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.bot_token.is_empty() {
+            return Err(DomainError::validation(format!("Telegram bot token cannot be empty: {}", self.display_name())));
+        }
+        if self.chat_id.is_empty() {
+            return Err(DomainError::validation("Telegram chat ID cannot be empty"));
+        }
+        validate_http_url(self.api_url())
+    }
+}
+
+/// SMTP 連接安全模式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// 明文連接後升級為 TLS（通常為 587 端口）
+    #[default]
+    Starttls,
+    /// 直接使用 TLS 連接（通常為 465 端口）
+    Tls,
+    /// 不加密（僅用於本地中繼）
+    None,
+}
+
+/// SMTP 郵件通知配置
+///
+/// # 欄位
+///
+/// - `name`: 顯示名稱（可選，用於日誌）
+/// - `smtp_host`: SMTP 服務器地址
+/// - `smtp_port`: SMTP 端口（可選，默認根據安全模式選擇 587、465 或 25）
+/// - `security`: 連接安全模式
+/// - `username` / `password`: SMTP 認證信息（可選）
+/// - `from`: 發件人地址
+/// - `to`: 收件人地址列表
+/// - `events`: 要發送的事件名稱，為空時發送默認事件
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EmailConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub smtp_host: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smtp_port: Option<u16>,
+    #[serde(default)]
+    pub security: SmtpSecurity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+}
+
+impl EmailConfig {
+    /// 用於日誌的顯示名稱
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.smtp_host)
+    }
+
+    /// SMTP 端口
+    pub fn port(&self) -> u16 {
+        self.smtp_port.unwrap_or(match self.security {
+            SmtpSecurity::Starttls => 587,
+            SmtpSecurity::Tls => 465,
+            SmtpSecurity::None => 25,
+        })
+    }
+
+    /// 驗證配置
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.smtp_host.is_empty() {
+            return Err(DomainError::validation("SMTP host cannot be empty"));
+        }
+        if self.to.is_empty() {
+            return Err(DomainError::validation(format!("Email recipients cannot be empty: {}", self.display_name())));
+        }
+        if self.username.is_some() != self.password.is_some() {
+            return Err(DomainError::validation(format!("SMTP username and password must be set together: {}", self.display_name())));
+        }
+        for address in std::iter::once(&self.from).chain(&self.to) {
+            if !address.contains('@') {
+                return Err(DomainError::validation(format!("Invalid email address: {}", address)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 通知限流設置
+///
+/// 每個通知目標對同一條記錄在 `window_seconds` 秒內最多發送 `max_messages` 條通知，
+/// 避免網絡抖動時刷屏
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitSettings {
+    #[serde(default = "RateLimitSettings::default_max_messages")]
+    pub max_messages: u32,
+    #[serde(default = "RateLimitSettings::default_window_seconds")]
+    pub window_seconds: u64,
+}
+
+impl RateLimitSettings {
+    fn default_max_messages() -> u32 {
+        3
+    }
+
+    fn default_window_seconds() -> u64 {
+        600
+    }
+
+    /// 是否為默認設置
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 驗證限流設置
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.max_messages == 0 || self.window_seconds == 0 {
+            return Err(DomainError::validation("Notification rate limit must be greater than zero"));
+        }
+        Ok(())
+    }
+}
+
+impl Default for RateLimitSettings {
+    fn default() -> Self {
+        Self {
+            max_messages: Self::default_max_messages(),
+            window_seconds: Self::default_window_seconds(),
+        }
+    }
+}
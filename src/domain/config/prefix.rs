@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::error::DomainError;
+use std::collections::BTreeMap;
+use std::net::Ipv6Addr;
+
+/// 默認委派前綴長度
+const DEFAULT_PREFIX_LENGTH: u8 = 64;
+
+fn default_prefix_length() -> u8 {
+    DEFAULT_PREFIX_LENGTH
+}
+
+/// IPv6 前綴委派設置
+///
+/// ISP 定期更換委派前綴時，將檢測到的 IPv6 地址的前綴與固定的主機後綴（接口 ID）組合為記錄地址，
+/// 使運行本服務的主機可以為局域網內其他主機更新 AAAA 記錄
+///
+/// # 欄位
+///
+/// - `prefix_length`: 取檢測地址的前多少位作為前綴，默認 64
+/// - `suffix`: 主機後綴，只能在前綴之外的位上有值（如 `::1234:5678:9abc:def0`）
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrefixDelegation {
+    #[serde(default = "default_prefix_length")]
+    pub prefix_length: u8,
+    pub suffix: Ipv6Addr,
+}
+
+impl PrefixDelegation {
+    /// 驗證前綴長度和主機後綴
+    pub fn validate(&self) -> Result<(), DomainError> {
+        validate_prefix_length(self.prefix_length)?;
+        if u128::from(self.suffix) & prefix_mask(self.prefix_length) != 0 {
+            return Err(DomainError::validation(format!(
+                "IPv6 suffix {} overlaps the /{} prefix",
+                self.suffix, self.prefix_length
+            )));
+        }
+        Ok(())
+    }
+
+    /// 將檢測到的地址的前綴與主機後綴組合
+    ///
+    /// # 參數
+    ///
+    /// - `detected`: 檢測到的 IPv6 地址
+    ///
+    /// # 返回
+    ///
+    /// - `Result<String, DomainError>`: 成功時返回組合後的地址，檢測結果不是 IPv6 地址時返回錯誤
+    pub fn apply(&self, detected: &str) -> Result<String, DomainError> {
+        let detected: Ipv6Addr = detected.trim().parse().map_err(|_| {
+            DomainError::ip_service(format!("Detected address {} is not an IPv6 address", detected))
+        })?;
+        let mask = prefix_mask(self.prefix_length);
+        let combined = (u128::from(detected) & mask) | (u128::from(self.suffix) & !mask);
+        Ok(Ipv6Addr::from(combined).to_string())
+    }
+}
+
+/// 記錄組的 IPv6 前綴委派設置
+///
+/// # 欄位
+///
+/// - `prefix_length`: 取檢測地址的前多少位作為前綴，默認 64
+/// - `suffixes`: 主機名到主機後綴的映射；未列出的主機名直接使用檢測到的地址
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GroupPrefixDelegation {
+    #[serde(default = "default_prefix_length")]
+    pub prefix_length: u8,
+    pub suffixes: BTreeMap<String, Ipv6Addr>,
+}
+
+impl GroupPrefixDelegation {
+    /// 驗證設置
+    ///
+    /// # 參數
+    ///
+    /// - `hostnames`: 記錄組的主機名，後綴映射中的主機名必須在其中
+    pub fn validate(&self, hostnames: &[String]) -> Result<(), DomainError> {
+        validate_prefix_length(self.prefix_length)?;
+        for hostname in self.suffixes.keys() {
+            if !hostnames.iter().any(|name| name.trim().eq_ignore_ascii_case(hostname.trim())) {
+                return Err(DomainError::validation(format!(
+                    "IPv6 suffix configured for unknown hostname: {}",
+                    hostname
+                )));
+            }
+        }
+        for hostname in hostnames {
+            if let Some(delegation) = self.for_hostname(hostname) {
+                delegation.validate()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 指定主機名的前綴委派設置（未配置後綴時為 None）
+    ///
+    /// # 參數
+    ///
+    /// - `hostname`: 主機名
+    pub fn for_hostname(&self, hostname: &str) -> Option<PrefixDelegation> {
+        self.suffixes
+            .iter()
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case(hostname.trim()))
+            .map(|(_, suffix)| PrefixDelegation {
+                prefix_length: self.prefix_length,
+                suffix: *suffix,
+            })
+    }
+}
+
+/// 驗證前綴長度
+fn validate_prefix_length(prefix_length: u8) -> Result<(), DomainError> {
+    if !(1..=127).contains(&prefix_length) {
+        return Err(DomainError::validation(format!(
+            "IPv6 prefix length must be between 1 and 127, got {}",
+            prefix_length
+        )));
+    }
+    Ok(())
+}
+
+/// 前綴部分全為 1 的掩碼
+fn prefix_mask(prefix_length: u8) -> u128 {
+    match prefix_length {
+        0 => 0,
+        length if length >= 128 => u128::MAX,
+        length => u128::MAX << (128 - u32::from(length)),
+    }
+}
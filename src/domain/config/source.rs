@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use crate::domain::config::IpType;
+use crate::domain::error::DomainError;
+use std::net::IpAddr;
+
+/// IP 檢測來源設置
+///
+/// 多出口（多 WAN）環境下，將記錄的 IP 檢測綁定到指定的網絡接口或本地地址，
+/// 使記錄反映對應出口的地址，而不是默認路由的地址
+///
+/// # 欄位
+///
+/// - `interface`: 網絡接口名稱（如 `ppp0`、`eth1`），檢測請求從該接口當前的地址發出
+/// - `address`: 本地地址，檢測請求從該地址發出
+/// - `from_interface`: 直接使用 `interface` 上的公網地址作為記錄 IP，不查詢外部服務
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SourceSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interface: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<IpAddr>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub from_interface: bool,
+}
+
+impl SourceSettings {
+    /// 是否未設置檢測來源（使用默認路由）
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 驗證檢測來源設置
+    ///
+    /// # 參數
+    ///
+    /// - `ip_type`: 記錄的 IP 類型，綁定地址必須屬於同一協議族
+    pub fn validate(&self, ip_type: &IpType) -> Result<(), DomainError> {
+        if self.interface.is_some() && self.address.is_some() {
+            return Err(DomainError::validation("Source interface and source address cannot both be set"));
+        }
+        if self.interface.as_deref().is_some_and(|name| name.trim().is_empty()) {
+            return Err(DomainError::validation("Source interface cannot be empty"));
+        }
+        if self.from_interface && self.interface.is_none() {
+            return Err(DomainError::validation("from_interface requires a source interface"));
+        }
+        if let Some(address) = self.address {
+            let matches_family = match ip_type {
+                IpType::IPv4 => address.is_ipv4(),
+                IpType::IPv6 => address.is_ipv6(),
+            };
+            if !matches_family {
+                return Err(DomainError::validation(format!(
+                    "Source address {} does not match IP type {}",
+                    address, ip_type
+                )));
+            }
+        }
+        Ok(())
+    }
+}
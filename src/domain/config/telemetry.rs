@@ -0,0 +1,93 @@
+use crate::domain::error::DomainError;
+use std::env;
+use std::fmt;
+
+/// 追蹤數據導出方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TraceExporter {
+    /// 不導出追蹤數據
+    #[default]
+    None,
+    /// 通過 OTLP/HTTP 導出到收集器
+    Otlp,
+    /// 輸出到標準輸出，用於本地調試
+    Stdout,
+}
+
+impl TryFrom<&str> for TraceExporter {
+    type Error = DomainError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "none" => Ok(TraceExporter::None),
+            "otlp" => Ok(TraceExporter::Otlp),
+            "stdout" | "console" => Ok(TraceExporter::Stdout),
+            _ => Err(DomainError::validation(format!("Invalid trace exporter: {}", value))),
+        }
+    }
+}
+
+impl fmt::Display for TraceExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceExporter::None => write!(f, "none"),
+            TraceExporter::Otlp => write!(f, "otlp"),
+            TraceExporter::Stdout => write!(f, "stdout"),
+        }
+    }
+}
+
+/// 追蹤設置
+///
+/// # 欄位
+///
+/// - `exporter`: 導出方式
+/// - `otlp_endpoint`: OTLP/HTTP 收集器地址，未設置時使用 OpenTelemetry 標準環境變量或 `http://localhost:4318`
+/// - `service_name`: 上報的服務名稱
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelemetrySettings {
+    pub exporter: TraceExporter,
+    pub otlp_endpoint: Option<String>,
+    pub service_name: String,
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        Self {
+            exporter: TraceExporter::None,
+            otlp_endpoint: None,
+            service_name: Self::DEFAULT_SERVICE_NAME.to_string(),
+        }
+    }
+}
+
+impl TelemetrySettings {
+    /// 默認服務名稱
+    pub const DEFAULT_SERVICE_NAME: &'static str = "cloudflare-ddns";
+
+    /// 從 OpenTelemetry 標準環境變量加載追蹤設置
+    ///
+    /// - `OTEL_TRACES_EXPORTER`: `otlp`、`stdout` 或 `none`（默認）
+    /// - `OTEL_EXPORTER_OTLP_ENDPOINT`: 收集器地址
+    /// - `OTEL_SERVICE_NAME`: 服務名稱
+    pub fn from_env() -> Result<Self, DomainError> {
+        let exporter = match env::var("OTEL_TRACES_EXPORTER") {
+            Ok(value) => TraceExporter::try_from(value.as_str())?,
+            Err(_) => TraceExporter::None,
+        };
+
+        Ok(Self {
+            exporter,
+            otlp_endpoint: env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok().filter(|value| !value.trim().is_empty()),
+            service_name: env::var("OTEL_SERVICE_NAME")
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+                .unwrap_or_else(|| Self::DEFAULT_SERVICE_NAME.to_string()),
+        })
+    }
+
+    /// 是否啟用追蹤
+    pub fn is_enabled(&self) -> bool {
+        self.exporter != TraceExporter::None
+    }
+}
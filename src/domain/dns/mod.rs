@@ -1,3 +1,5 @@
 mod service;
+mod template;
 
-pub use service::{DnsService, DnsRecord, DnsUpdateResult}; 
\ No newline at end of file
+pub use service::{DnsService, DnsRecord, DnsUpdateResult};
+pub use template::{render_template, template_placeholders, TemplateContext, TEMPLATE_PLACEHOLDERS}; 
\ No newline at end of file
@@ -72,6 +72,26 @@ pub trait DnsService: Send + Sync {
     /// - `Result<Vec<DnsRecord>, DomainError>`: 成功時返回 DNS 記錄列表，失敗時返回錯誤
     async fn get_records(&self, zone_id: &str) -> Result<Vec<DnsRecord>, DomainError>;
     
+    /// 按名稱和類型查找 DNS 記錄
+    /// 
+    /// 默認實現從區域內所有記錄中過濾，名稱不區分大小寫
+    /// 
+    /// # 參數
+    /// 
+    /// - `zone_id`: 區域 ID
+    /// - `name`: 記錄名稱
+    /// - `record_type`: 記錄類型（如 A、AAAA）
+    /// 
+    /// # 返回
+    /// 
+    /// - `Result<Vec<DnsRecord>, DomainError>`: 成功時返回匹配的記錄，失敗時返回錯誤
+    async fn find_records(&self, zone_id: &str, name: &str, record_type: &str) -> Result<Vec<DnsRecord>, DomainError> {
+        let records = self.get_records(zone_id).await?;
+        Ok(records.into_iter()
+            .filter(|record| record.name.eq_ignore_ascii_case(name) && record.record_type == record_type)
+            .collect())
+    }
+    
     /// 創建 DNS 記錄
     /// 
     /// # 參數
@@ -83,4 +103,16 @@ pub trait DnsService: Send + Sync {
     /// 
     /// - `Result<DnsRecord, DomainError>`: 成功時返回創建的 DNS 記錄，失敗時返回錯誤
     async fn create_record(&self, zone_id: &str, record: DnsRecord) -> Result<DnsRecord, DomainError>;
+    
+    /// 刪除 DNS 記錄
+    /// 
+    /// # 參數
+    /// 
+    /// - `zone_id`: 區域 ID
+    /// - `record_id`: 記錄 ID
+    /// 
+    /// # 返回
+    /// 
+    /// - `Result<(), DomainError>`: 成功時返回 ()，失敗時返回錯誤
+    async fn delete_record(&self, zone_id: &str, record_id: &str) -> Result<(), DomainError>;
 } 
\ No newline at end of file
@@ -0,0 +1,127 @@
+use crate::domain::error::DomainError;
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// 記錄內容模板支持的佔位符
+pub const TEMPLATE_PLACEHOLDERS: [&str; 6] = ["ip", "ipv4", "ipv6", "hostname", "timestamp", "unix_time"];
+
+/// 渲染記錄內容模板所需的值
+///
+/// # 欄位
+///
+/// - `ip`: 記錄 IP 類型對應的地址（已應用前綴委派）
+/// - `ipv4`: 檢測到的 IPv4 地址（未檢測時為 None）
+/// - `ipv6`: 檢測到的 IPv6 地址（未檢測時為 None）
+/// - `hostname`: 記錄名稱
+/// - `time`: 渲染時間
+#[derive(Debug, Clone)]
+pub struct TemplateContext {
+    pub ip: String,
+    pub ipv4: Option<String>,
+    pub ipv6: Option<String>,
+    pub hostname: String,
+    pub time: DateTime<Utc>,
+}
+
+/// 解析模板中的佔位符
+///
+/// 佔位符形如 `{ipv4}`，`{{` 和 `}}` 分別表示字面的 `{` 和 `}`
+///
+/// # 參數
+///
+/// - `template`: 記錄內容模板
+///
+/// # 返回
+///
+/// - `Result<Vec<String>, DomainError>`: 成功時按出現順序返回佔位符名稱，模板格式錯誤或包含未知佔位符時返回錯誤
+pub fn template_placeholders(template: &str) -> Result<Vec<String>, DomainError> {
+    let mut placeholders = Vec::new();
+    walk(template, |segment| {
+        if let Segment::Placeholder(name) = segment {
+            placeholders.push(name.to_string());
+        }
+        Ok(())
+    })?;
+    Ok(placeholders)
+}
+
+/// 渲染記錄內容模板
+///
+/// # 參數
+///
+/// - `template`: 記錄內容模板
+/// - `context`: 模板值
+///
+/// # 返回
+///
+/// - `Result<String, DomainError>`: 成功時返回記錄內容，引用了未檢測的地址族時返回錯誤
+pub fn render_template(template: &str, context: &TemplateContext) -> Result<String, DomainError> {
+    let mut content = String::with_capacity(template.len());
+    walk(template, |segment| {
+        match segment {
+            Segment::Text(text) => content.push_str(text),
+            Segment::Placeholder(name) => {
+                let value = match name {
+                    "ip" => context.ip.clone(),
+                    "ipv4" => context.ipv4.clone().ok_or_else(|| missing_address("ipv4"))?,
+                    "ipv6" => context.ipv6.clone().ok_or_else(|| missing_address("ipv6"))?,
+                    "hostname" => context.hostname.clone(),
+                    "timestamp" => context.time.to_rfc3339_opts(SecondsFormat::Secs, true),
+                    "unix_time" => context.time.timestamp().to_string(),
+                    _ => unreachable!("placeholders are checked while parsing"),
+                };
+                content.push_str(&value);
+            },
+        }
+        Ok(())
+    })?;
+    Ok(content)
+}
+
+/// 模板片段
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+/// 逐個解析模板片段
+fn walk<'a>(template: &'a str, mut visit: impl FnMut(Segment<'a>) -> Result<(), DomainError>) -> Result<(), DomainError> {
+    let mut rest = template;
+    while !rest.is_empty() {
+        let Some(index) = rest.find(['{', '}']) else {
+            return visit(Segment::Text(rest));
+        };
+        if index > 0 {
+            visit(Segment::Text(&rest[..index]))?;
+        }
+        rest = &rest[index..];
+
+        if let Some(escaped) = rest.strip_prefix("{{").or_else(|| rest.strip_prefix("}}")) {
+            visit(Segment::Text(&rest[..1]))?;
+            rest = escaped;
+            continue;
+        }
+        if rest.starts_with('}') {
+            return Err(DomainError::validation(format!("Unmatched '}}' in content template: {}", template)));
+        }
+
+        let Some(end) = rest.find('}') else {
+            return Err(DomainError::validation(format!("Unclosed placeholder in content template: {}", template)));
+        };
+        let name = &rest[1..end];
+        if !TEMPLATE_PLACEHOLDERS.contains(&name) {
+            return Err(DomainError::validation(format!(
+                "Unknown placeholder {{{}}} in content template, expected one of: {}",
+                name,
+                TEMPLATE_PLACEHOLDERS.join(", ")
+            )));
+        }
+        visit(Segment::Placeholder(name))?;
+        rest = &rest[end + 1..];
+    }
+    Ok(())
+}
+
+/// 引用了未檢測的地址族
+fn missing_address(family: &str) -> DomainError {
+    DomainError::ip_service(format!("Content template references {{{}}} but no {} address was detected", family, family))
+}
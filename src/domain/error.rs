@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Debug, Error, Clone)]
@@ -24,6 +25,14 @@ pub enum NetworkErrorType {
     #[error("HTTP錯誤: 狀態碼 {0}")]
     HttpError(u16),
     
+    /// 服務暫不可用（HTTP 503），附帶服務端建議的重試等待時間
+    #[error("服務暫不可用: 狀態碼 503")]
+    ServiceUnavailable(Option<Duration>),
+    
+    /// 目標主機的熔斷器已開啟，請求未發出
+    #[error("熔斷器已開啟: {0}")]
+    CircuitOpen(String),
+    
     #[error("未知網絡錯誤: {0}")]
     Unknown(String),
 }
@@ -42,8 +51,9 @@ pub enum ApiErrorType {
     #[error("API無效請求: {0}")]
     BadRequestError(String),
     
+    /// 請求頻率超限，附帶服務端建議的重試等待時間
     #[error("API請求頻率限制: {0}")]
-    RateLimitError(String),
+    RateLimitError(String, Option<Duration>),
     
     #[error("API內部錯誤: {0}")]
     ServerError(String),
@@ -84,6 +94,15 @@ pub enum DomainError {
     #[error("序列化錯誤: {0}")]
     SerializationError(String),
     
+    #[error("資源不存在: {0}")]
+    NotFound(String),
+    
+    #[error("版本衝突: {0}")]
+    Conflict(String),
+    
+    #[error("鉤子執行錯誤: {0}")]
+    Hook(String),
+    
     #[error("{0}")]
     Context(String, Box<DomainError>),
 }
@@ -121,6 +140,18 @@ impl DomainError {
         DomainError::Validation(msg.into())
     }
     
+    pub fn not_found(msg: impl Into<String>) -> Self {
+        DomainError::NotFound(msg.into())
+    }
+    
+    pub fn conflict(msg: impl Into<String>) -> Self {
+        DomainError::Conflict(msg.into())
+    }
+    
+    pub fn hook(msg: impl Into<String>) -> Self {
+        DomainError::Hook(msg.into())
+    }
+    
     // 添加上下文到錯誤
     pub fn context<C>(self, context: C) -> Self
     where
@@ -136,24 +167,59 @@ impl DomainError {
                 NetworkErrorType::ConnectionError(_) => true,
                 NetworkErrorType::TimeoutError(_) => true,
                 NetworkErrorType::HttpError(status) => *status >= 500 && *status < 600,
+                NetworkErrorType::ServiceUnavailable(_) => true,
                 _ => false,
             },
-            DomainError::Api(api_err) => match api_err {
-                ApiErrorType::RateLimitError(_) => true,
-                ApiErrorType::ServerError(_) => true,
-                _ => false,
-            },
+            DomainError::Api(api_err) => matches!(
+                api_err,
+                ApiErrorType::RateLimitError(..) | ApiErrorType::ServerError(_)
+            ),
             _ => false,
         }
     }
     
+    /// 服務端要求的重試等待時間（來自 429/503 響應的 `Retry-After` 頭）
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            DomainError::Api(ApiErrorType::RateLimitError(_, retry_after)) => *retry_after,
+            DomainError::Network(NetworkErrorType::ServiceUnavailable(retry_after)) => *retry_after,
+            DomainError::Context(_, inner) => inner.retry_after(),
+            _ => None,
+        }
+    }
+    
+    /// 獲取錯誤分類名稱（用於事件、日誌和通知中的 error_kind 字段）
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DomainError::Network(NetworkErrorType::TimeoutError(_)) => "timeout",
+            DomainError::Network(NetworkErrorType::CircuitOpen(_)) => "circuit_open",
+            DomainError::Network(_) => "network",
+            DomainError::DnsService(_) => "dns_service",
+            DomainError::IpService(_) => "ip_service",
+            DomainError::Configuration(_) => "configuration",
+            DomainError::Api(ApiErrorType::AuthenticationError(_))
+            | DomainError::Api(ApiErrorType::AuthorizationError(_)) => "auth",
+            DomainError::Api(ApiErrorType::RateLimitError(..)) => "rate_limit",
+            DomainError::Api(_) => "api",
+            DomainError::Unknown(_) => "unknown",
+            DomainError::RetryExhausted(_) => "retry_exhausted",
+            DomainError::Validation(_) => "validation",
+            DomainError::LogicError(_) => "logic",
+            DomainError::SerializationError(_) => "serialization",
+            DomainError::NotFound(_) => "not_found",
+            DomainError::Conflict(_) => "conflict",
+            DomainError::Hook(_) => "hook",
+            DomainError::Context(_, inner) => inner.kind(),
+        }
+    }
+    
     /// 將錯誤轉換為用戶友好的訊息
     pub fn user_friendly_message(&self) -> String {
         match self {
             DomainError::Validation(msg) => format!("輸入資料無效: {}", msg),
             DomainError::Network(NetworkErrorType::ConnectionError(_)) => "網絡連接問題，請檢查您的網絡連接並重試".to_string(),
             DomainError::Network(NetworkErrorType::TimeoutError(_)) => "網絡超時，請檢查您的網絡連接並重試".to_string(),
-            DomainError::Api(ApiErrorType::RateLimitError(_)) => "請求太頻繁，請稍後再試".to_string(),
+            DomainError::Api(ApiErrorType::RateLimitError(..)) => "請求太頻繁，請稍後再試".to_string(),
             DomainError::Api(ApiErrorType::ServerError(_)) => "服務暫時不可用，請稍後再試".to_string(),
             _ => "發生錯誤，請稍後重試".to_string(),
         }
@@ -252,6 +318,9 @@ impl Clone for DomainError {
             DomainError::Validation(s) => DomainError::Validation(s.clone()),
             DomainError::LogicError(s) => DomainError::LogicError(s.clone()),
             DomainError::SerializationError(s) => DomainError::SerializationError(s.clone()),
+            DomainError::NotFound(s) => DomainError::NotFound(s.clone()),
+            DomainError::Conflict(s) => DomainError::Conflict(s.clone()),
+            DomainError::Hook(s) => DomainError::Hook(s.clone()),
             DomainError::Context(s, e) => DomainError::Context(s.clone(), e.clone()),
         }
     }
@@ -0,0 +1,86 @@
+use serde::Serialize;
+use std::fmt;
+
+/// 記錄當前指向的目標
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailoverTarget {
+    /// 主目標（檢測到的地址）
+    #[default]
+    Primary,
+    /// 備用地址
+    Backup,
+}
+
+impl fmt::Display for FailoverTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailoverTarget::Primary => write!(f, "primary"),
+            FailoverTarget::Backup => write!(f, "backup"),
+        }
+    }
+}
+
+/// 故障轉移狀態
+///
+/// 記錄當前目標及連續檢查結果，只有連續失敗或連續恢復達到閾值時才切換，避免反覆切換
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FailoverState {
+    active: FailoverTarget,
+    consecutive_failures: u32,
+    consecutive_successes: u32,
+}
+
+impl FailoverState {
+    /// 創建從指定目標開始的狀態
+    ///
+    /// # 參數
+    ///
+    /// - `active`: 當前目標（如服務重建時記錄已指向備用地址）
+    pub fn new(active: FailoverTarget) -> Self {
+        Self {
+            active,
+            ..Default::default()
+        }
+    }
+
+    /// 當前目標
+    pub fn active(&self) -> FailoverTarget {
+        self.active
+    }
+
+    /// 記錄一次健康檢查結果
+    ///
+    /// # 參數
+    ///
+    /// - `healthy`: 主目標是否健康
+    /// - `failure_threshold`: 切換到備用地址所需的連續失敗次數
+    /// - `recovery_threshold`: 切回主目標所需的連續成功次數
+    ///
+    /// # 返回
+    ///
+    /// - `Option<FailoverTarget>`: 本次發生切換時返回新的目標
+    pub fn observe(&mut self, healthy: bool, failure_threshold: u32, recovery_threshold: u32) -> Option<FailoverTarget> {
+        if healthy {
+            self.consecutive_failures = 0;
+            self.consecutive_successes = self.consecutive_successes.saturating_add(1);
+            if self.active == FailoverTarget::Backup && self.consecutive_successes >= recovery_threshold {
+                return Some(self.switch_to(FailoverTarget::Primary));
+            }
+        } else {
+            self.consecutive_successes = 0;
+            self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+            if self.active == FailoverTarget::Primary && self.consecutive_failures >= failure_threshold {
+                return Some(self.switch_to(FailoverTarget::Backup));
+            }
+        }
+        None
+    }
+
+    fn switch_to(&mut self, target: FailoverTarget) -> FailoverTarget {
+        self.active = target;
+        self.consecutive_failures = 0;
+        self.consecutive_successes = 0;
+        target
+    }
+}
@@ -0,0 +1,5 @@
+mod hysteresis;
+mod probe;
+
+pub use hysteresis::{FailoverState, FailoverTarget};
+pub use probe::HealthProbe;
@@ -0,0 +1,20 @@
+use crate::domain::config::HealthCheck;
+use crate::domain::error::DomainError;
+use async_trait::async_trait;
+use std::net::IpAddr;
+
+/// 健康檢查執行接口
+#[async_trait]
+pub trait HealthProbe: Send + Sync {
+    /// 對目標地址執行一項健康檢查
+    ///
+    /// # 參數
+    ///
+    /// - `check`: 健康檢查
+    /// - `target`: 主目標地址
+    ///
+    /// # 返回
+    ///
+    /// - `Result<(), DomainError>`: 檢查通過時返回 ()，失敗或超時時返回錯誤
+    async fn probe(&self, check: &HealthCheck, target: IpAddr) -> Result<(), DomainError>;
+}
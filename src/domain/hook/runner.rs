@@ -0,0 +1,65 @@
+use crate::domain::config::HookCommand;
+use crate::domain::error::DomainError;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::fmt;
+use std::time::Duration;
+
+/// 鉤子執行階段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HookStage {
+    /// 更新 DNS 記錄前
+    Pre,
+    /// 更新 DNS 記錄後
+    Post,
+}
+
+impl fmt::Display for HookStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookStage::Pre => write!(f, "pre"),
+            HookStage::Post => write!(f, "post"),
+        }
+    }
+}
+
+/// 鉤子執行結果
+///
+/// # 欄位
+///
+/// - `exit_code`: 退出碼（超時或被信號終止時為 None）
+/// - `stdout` / `stderr`: 捕獲的輸出（過長時截斷）
+/// - `duration`: 執行時間
+/// - `timed_out`: 是否因超時被終止
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookOutput {
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration: Duration,
+    pub timed_out: bool,
+}
+
+impl HookOutput {
+    /// 命令是否執行成功
+    pub fn success(&self) -> bool {
+        !self.timed_out && self.exit_code == Some(0)
+    }
+}
+
+/// 鉤子執行接口
+#[async_trait]
+pub trait HookRunner: Send + Sync {
+    /// 執行鉤子命令
+    ///
+    /// # 參數
+    ///
+    /// - `hook`: 鉤子命令
+    /// - `env`: 傳給命令的環境變量
+    ///
+    /// # 返回
+    ///
+    /// - `Result<HookOutput, DomainError>`: 命令運行結束（包括失敗和超時）時返回結果，無法啟動時返回錯誤
+    async fn run(&self, hook: &HookCommand, env: &[(String, String)]) -> Result<HookOutput, DomainError>;
+}
@@ -1,14 +1,12 @@
 use crate::domain::error::DomainError;
 use async_trait::async_trait;
-use reqwest::header::HeaderMap;
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use std::sync::Arc;
-use std::any::Any;
 
 /// 基本 HTTP 客戶端接口（可以轉換為對象的方法）
 #[async_trait]
-pub trait HttpClient: Send + Sync + Any {
+pub trait HttpClient: Send + Sync {
     /// 發送 GET 請求
     /// 
     /// # 參數
@@ -58,12 +56,29 @@ pub trait HttpClient: Send + Sync + Any {
     /// 
     /// - `Result<String, DomainError>`: 成功時返回響應內容，失敗時返回錯誤
     async fn delete(&self, url: &str, headers: Option<HeaderMap>) -> Result<String, DomainError>;
-
-    /// 進行類型轉換
-    fn as_any(&self) -> &dyn Any;
+    
+    /// 發送不自動重試的 POST 請求
+    /// 
+    /// 用於創建資源等非冪等操作：請求超時或服務端返回 5xx 時請求可能已被處理，重試會重複創建。
+    /// 默認實現與 `post` 相同，帶重試機制的客戶端需要覆蓋此方法
+    /// 
+    /// # 參數
+    /// 
+    /// - `url`: 請求的 URL
+    /// - `body`: 請求體（可選）
+    /// - `headers`: 請求頭（可選）
+    /// 
+    /// # 返回
+    /// 
+    /// - `Result<String, DomainError>`: 成功時返回響應內容，失敗時返回錯誤
+    async fn post_once(&self, url: &str, body: Option<String>, headers: Option<HeaderMap>) -> Result<String, DomainError> {
+        self.post(url, body, headers).await
+    }
 }
 
 /// HTTP JSON 客戶端擴展特徵（包含泛型方法）
+///
+/// 基於 `HttpClient` 的字符串方法實現，所有 `HttpClient`（包括 `dyn HttpClient`）都自動獲得這些方法
 #[async_trait]
 pub trait HttpClientExt: HttpClient {
     /// 發送 GET 請求並解析 JSON 響應
@@ -91,6 +106,19 @@ pub trait HttpClientExt: HttpClient {
     /// - `Result<T, DomainError>`: 成功時返回反序列化的對象，失敗時返回錯誤
     async fn post_json<T: DeserializeOwned + Send + 'static, U: Serialize + Send + Sync>(&self, url: &str, body: Option<&U>, headers: Option<HeaderMap>) -> Result<T, DomainError>;
     
+    /// 發送不自動重試的 POST 請求並解析 JSON 響應
+    /// 
+    /// # 參數
+    /// 
+    /// - `url`: 請求的 URL
+    /// - `body`: 請求體（可選）
+    /// - `headers`: 請求頭（可選）
+    /// 
+    /// # 返回
+    /// 
+    /// - `Result<T, DomainError>`: 成功時返回反序列化的對象，失敗時返回錯誤
+    async fn post_json_once<T: DeserializeOwned + Send + 'static, U: Serialize + Send + Sync>(&self, url: &str, body: Option<&U>, headers: Option<HeaderMap>) -> Result<T, DomainError>;
+    
     /// 發送 PUT 請求並解析 JSON 響應
     /// 
     /// # 參數
@@ -117,22 +145,54 @@ pub trait HttpClientExt: HttpClient {
     async fn delete_json<T: DeserializeOwned + Send + 'static>(&self, url: &str, headers: Option<HeaderMap>) -> Result<T, DomainError>;
 }
 
-/// 擴展特徵，為 Arc<dyn HttpClient> 添加 downcast_arc 方法
-pub trait ArcHttpClientExt {
-    /// 嘗試將 Arc<dyn HttpClient> 轉換為 Arc<T>
-    fn downcast_arc<T: HttpClient + 'static>(self) -> Result<Arc<T>, Self> where Self: Sized;
+#[async_trait]
+impl<C: HttpClient + ?Sized> HttpClientExt for C {
+    async fn get_json<T: DeserializeOwned + Send + 'static>(&self, url: &str, headers: Option<HeaderMap>) -> Result<T, DomainError> {
+        let response = self.get(url, headers).await?;
+        parse_json(&response)
+    }
+    
+    async fn post_json<T: DeserializeOwned + Send + 'static, U: Serialize + Send + Sync>(&self, url: &str, body: Option<&U>, headers: Option<HeaderMap>) -> Result<T, DomainError> {
+        let body = body.map(serialize_json).transpose()?;
+        let response = self.post(url, body, Some(json_headers(headers))).await?;
+        parse_json(&response)
+    }
+    
+    async fn post_json_once<T: DeserializeOwned + Send + 'static, U: Serialize + Send + Sync>(&self, url: &str, body: Option<&U>, headers: Option<HeaderMap>) -> Result<T, DomainError> {
+        let body = body.map(serialize_json).transpose()?;
+        let response = self.post_once(url, body, Some(json_headers(headers))).await?;
+        parse_json(&response)
+    }
+    
+    async fn put_json<T: DeserializeOwned + Send + 'static, U: Serialize + Send + Sync>(&self, url: &str, body: Option<&U>, headers: Option<HeaderMap>) -> Result<T, DomainError> {
+        let body = body.map(serialize_json).transpose()?;
+        let response = self.put(url, body, Some(json_headers(headers))).await?;
+        parse_json(&response)
+    }
+    
+    async fn delete_json<T: DeserializeOwned + Send + 'static>(&self, url: &str, headers: Option<HeaderMap>) -> Result<T, DomainError> {
+        let response = self.delete(url, headers).await?;
+        parse_json(&response)
+    }
+}
+
+/// 序列化 JSON 請求體
+fn serialize_json<U: Serialize + ?Sized>(body: &U) -> Result<String, DomainError> {
+    serde_json::to_string(body).map_err(|e| {
+        DomainError::SerializationError(format!("Failed to serialize JSON request: {}", e))
+    })
 }
 
-impl ArcHttpClientExt for Arc<dyn HttpClient> {
-    fn downcast_arc<T: HttpClient + 'static>(self) -> Result<Arc<T>, Self> where Self: Sized {
-        if (*self).as_any().is::<T>() {
-            // 安全：我們已經檢查了類型
-            let ptr = Arc::into_raw(self);
-            let ptr = ptr as *const T;
-            // 安全：因爲 HttpClient 要求 Send + Sync + 'static
-            Ok(unsafe { Arc::from_raw(ptr) })
-        } else {
-            Err(self)
-        }
-    }
-} 
\ No newline at end of file
+/// 解析 JSON 響應
+fn parse_json<T: DeserializeOwned>(response: &str) -> Result<T, DomainError> {
+    serde_json::from_str(response).map_err(|e| {
+        DomainError::LogicError(format!("Failed to parse JSON response: {}", e))
+    })
+}
+
+/// 補充 JSON 請求的 `Content-Type` 頭，已設置時保留原值
+fn json_headers(headers: Option<HeaderMap>) -> HeaderMap {
+    let mut headers = headers.unwrap_or_default();
+    headers.entry(CONTENT_TYPE).or_insert(HeaderValue::from_static("application/json"));
+    headers
+}
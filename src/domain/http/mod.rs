@@ -1,3 +1,3 @@
 mod client;
 
-pub use client::{HttpClient, HttpClientExt, ArcHttpClientExt}; 
\ No newline at end of file
+pub use client::{HttpClient, HttpClientExt};
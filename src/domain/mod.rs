@@ -2,8 +2,11 @@ pub mod config;
 pub mod error;
 pub mod error_context;
 pub mod dns;
+pub mod failover;
+pub mod hook;
 pub mod http;
 pub mod ip;
+pub mod notification;
 pub mod state;
 
 pub use error::DomainError;
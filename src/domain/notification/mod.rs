@@ -0,0 +1,3 @@
+mod notifier;
+
+pub use notifier::{Notification, Notifier};
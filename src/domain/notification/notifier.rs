@@ -0,0 +1,42 @@
+use crate::domain::error::DomainError;
+use async_trait::async_trait;
+use serde_json::Value;
+
+/// 待發送的通知
+///
+/// # 欄位
+///
+/// - `event`: 事件名稱（如 `ip-changed`）
+/// - `title`: 標題
+/// - `message`: 正文
+/// - `record_key`: 涉及的記錄（用於限流分組），與記錄無關的事件為 None
+/// - `payload`: 事件原始內容
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub event: String,
+    pub title: String,
+    pub message: String,
+    pub record_key: Option<String>,
+    pub payload: Value,
+}
+
+/// 通知發送接口
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// 通知目標名稱，用於日誌和限流
+    fn name(&self) -> &str;
+
+    /// 訂閱的事件名稱，為空時使用默認事件
+    fn events(&self) -> &[String];
+
+    /// 發送通知
+    ///
+    /// # 參數
+    ///
+    /// - `notification`: 通知內容
+    ///
+    /// # 返回
+    ///
+    /// - `Result<(), DomainError>`: 發送結果
+    async fn send(&self, notification: &Notification) -> Result<(), DomainError>;
+}
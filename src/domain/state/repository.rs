@@ -1,12 +1,23 @@
 use crate::domain::error::DomainError;
+use crate::domain::config::ConfigId;
 use chrono::{DateTime, Utc};
 use async_trait::async_trait;
 
 /// 狀態條目
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct StateEntry {
     pub last_ip: Option<String>,
+    /// 最後一次寫入記錄時的地址，配置了內容模板時與記錄內容（`last_ip`）不同
+    pub last_address: Option<String>,
     pub last_update_time: Option<DateTime<Utc>>,
+    /// 最後一次檢查（無論是否更新）的時間
+    pub last_check_time: Option<DateTime<Utc>>,
+    /// 最後一次檢查失敗的錯誤信息，成功檢查後清除
+    pub last_error: Option<String>,
+    /// 最後一次錯誤發生的時間
+    pub last_error_time: Option<DateTime<Utc>>,
+    /// 最後一次成功檢查的時間
+    pub last_success_time: Option<DateTime<Utc>>,
 }
 
 /// 狀態存儲庫接口
@@ -21,7 +32,7 @@ pub trait StateRepository: Send + Sync {
     /// # 返回
     /// 
     /// - `Result<Option<String>, DomainError>`: 成功時返回 IP 地址（如果有），失敗時返回錯誤
-    async fn get_last_ip(&self, config_id: &str) -> Result<Option<String>, DomainError>;
+    async fn get_last_ip(&self, config_id: &ConfigId) -> Result<Option<String>, DomainError>;
     
     /// 設置最後的 IP 地址
     /// 
@@ -33,7 +44,7 @@ pub trait StateRepository: Send + Sync {
     /// # 返回
     /// 
     /// - `Result<(), DomainError>`: 成功時返回 ()，失敗時返回錯誤
-    async fn set_last_ip(&self, config_id: &str, ip: &str) -> Result<(), DomainError>;
+    async fn set_last_ip(&self, config_id: &ConfigId, ip: &str) -> Result<(), DomainError>;
     
     /// 獲取最後的更新時間
     /// 
@@ -44,7 +55,7 @@ pub trait StateRepository: Send + Sync {
     /// # 返回
     /// 
     /// - `Result<Option<DateTime<Utc>>, DomainError>`: 成功時返回更新時間（如果有），失敗時返回錯誤
-    async fn get_last_update_time(&self, config_id: &str) -> Result<Option<DateTime<Utc>>, DomainError>;
+    async fn get_last_update_time(&self, config_id: &ConfigId) -> Result<Option<DateTime<Utc>>, DomainError>;
     
     /// 設置最後的更新時間
     /// 
@@ -56,7 +67,20 @@ pub trait StateRepository: Send + Sync {
     /// # 返回
     /// 
     /// - `Result<(), DomainError>`: 成功時返回 ()，失敗時返回錯誤
-    async fn set_last_update_time(&self, config_id: &str, time: DateTime<Utc>) -> Result<(), DomainError>;
+    async fn set_last_update_time(&self, config_id: &ConfigId, time: DateTime<Utc>) -> Result<(), DomainError>;
+    
+    /// 記錄一次檢查結果
+    /// 
+    /// # 參數
+    /// 
+    /// - `config_id`: 配置 ID
+    /// - `time`: 檢查時間
+    /// - `error`: 檢查失敗時的錯誤信息，成功時為 None
+    /// 
+    /// # 返回
+    /// 
+    /// - `Result<(), DomainError>`: 成功時返回 ()，失敗時返回錯誤
+    async fn set_check_result(&self, config_id: &ConfigId, time: DateTime<Utc>, error: Option<String>) -> Result<(), DomainError>;
     
     /// 獲取完整的狀態條目
     /// 
@@ -67,7 +91,7 @@ pub trait StateRepository: Send + Sync {
     /// # 返回
     /// 
     /// - `Result<Option<StateEntry>, DomainError>`: 成功時返回狀態條目（如果有），失敗時返回錯誤
-    async fn get_state(&self, config_id: &str) -> Result<Option<StateEntry>, DomainError>;
+    async fn get_state(&self, config_id: &ConfigId) -> Result<Option<StateEntry>, DomainError>;
     
     /// 設置完整的狀態條目
     /// 
@@ -79,5 +103,5 @@ pub trait StateRepository: Send + Sync {
     /// # 返回
     /// 
     /// - `Result<(), DomainError>`: 成功時返回 ()，失敗時返回錯誤
-    async fn set_state(&self, config_id: &str, state: StateEntry) -> Result<(), DomainError>;
+    async fn set_state(&self, config_id: &ConfigId, state: StateEntry) -> Result<(), DomainError>;
 } 
\ No newline at end of file
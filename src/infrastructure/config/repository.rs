@@ -1,4 +1,4 @@
-use crate::domain::config::DdnsConfig;
+use crate::domain::config::{ConfigDocument, DdnsConfig, RecordGroup};
 use crate::domain::error::DomainError;
 use crate::constants::CONFIG_FILE_PATH;
 use log::{info, error, warn};
@@ -53,8 +53,13 @@ impl FileConfigRepository {
     
     /// 從文件加載 DDNS 配置
     pub async fn load_configs(&self) -> Result<Vec<DdnsConfig>, DomainError> {
+        Ok(self.load_document().await?.configs)
+    }
+    
+    /// 從文件加載完整的配置文件內容
+    pub async fn load_document(&self) -> Result<ConfigDocument, DomainError> {
         if !self.config_path.exists() {
-            return Ok(Vec::new());
+            return Ok(ConfigDocument::default());
         }
         
         // 讀取配置文件
@@ -63,48 +68,40 @@ impl FileConfigRepository {
             Err(e) => return Err(DomainError::config(format!("Failed to read config file: {}", e))),
         };
         
-        // 解析 JSON
-        let configs: Result<Vec<DdnsConfig>, _> = serde_json::from_str(&contents);
-        match configs {
-            Ok(configs) => {
-                // 更新最後修改時間
-                if let Ok(metadata) = fs::metadata(&self.config_path) {
-                    if let Ok(modified) = metadata.modified() {
-                        let mut last_modified = self.last_modified.write().await;
-                        *last_modified = Some(modified);
-                    }
-                }
-                
-                Ok(configs)
-            },
-            Err(e) => {
-                // 嘗試解析為單個配置
-                let config: Result<DdnsConfig, _> = serde_json::from_str(&contents);
-                match config {
-                    Ok(config) => {
-                        // 更新最後修改時間
-                        if let Ok(metadata) = fs::metadata(&self.config_path) {
-                            if let Ok(modified) = metadata.modified() {
-                                let mut last_modified = self.last_modified.write().await;
-                                *last_modified = Some(modified);
-                            }
-                        }
-                        
-                        Ok(vec![config])
-                    },
-                    Err(_) => Err(DomainError::config(format!("Failed to parse config file: {}", e))),
-                }
+        let document = ConfigDocument::parse(&contents)?;
+        
+        // 更新最後修改時間
+        if let Ok(metadata) = fs::metadata(&self.config_path) {
+            if let Ok(modified) = metadata.modified() {
+                let mut last_modified = self.last_modified.write().await;
+                *last_modified = Some(modified);
             }
         }
+        
+        Ok(document)
     }
     
     /// 保存 DDNS 配置到文件
+    /// 
+    /// 只替換 DDNS 配置部分，保留文件中的其他配置段
     pub async fn save_configs(&self, configs: &[DdnsConfig]) -> Result<(), DomainError> {
-        // 將配置序列化為 JSON
-        let json = match serde_json::to_string_pretty(configs) {
-            Ok(json) => json,
-            Err(e) => return Err(DomainError::config(format!("Failed to serialize configs: {}", e))),
-        };
+        let mut document = self.load_document().await?;
+        document.configs = configs.to_vec();
+        self.save_document(&document).await
+    }
+    
+    /// 保存記錄組配置到文件
+    /// 
+    /// 只替換記錄組部分，保留文件中的其他配置段
+    pub async fn save_groups(&self, groups: &[RecordGroup]) -> Result<(), DomainError> {
+        let mut document = self.load_document().await?;
+        document.groups = groups.to_vec();
+        self.save_document(&document).await
+    }
+    
+    /// 保存完整的配置文件內容
+    pub async fn save_document(&self, document: &ConfigDocument) -> Result<(), DomainError> {
+        let json = document.to_json()?;
         
         // 寫入文件
         let mut file = match File::create(&self.config_path) {
@@ -130,6 +127,13 @@ impl FileConfigRepository {
         Ok(())
     }
     
+    /// 獲取配置文件最後修改時間
+    /// 
+    /// 由加載、保存及文件監視任務維護，可作為整份配置的版本標記
+    pub async fn last_modified(&self) -> Option<std::time::SystemTime> {
+        *self.last_modified.read().await
+    }
+    
     /// 獲取配置變更接收器
     pub fn get_change_receiver(&self) -> watch::Receiver<()> {
         self.change_receiver.clone()
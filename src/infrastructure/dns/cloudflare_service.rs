@@ -1,9 +1,11 @@
-use crate::domain::error::DomainError;
+use crate::domain::error::{ApiErrorType, DomainError};
 use crate::domain::dns::{DnsService, DnsRecord, DnsUpdateResult};
-use crate::domain::http::{HttpClientExt, ArcHttpClientExt};
+use crate::domain::http::{HttpClient, HttpClientExt};
 use crate::domain::config::DdnsConfig;
-use crate::infrastructure::http::ReqwestHttpClient;
+use crate::infrastructure::metrics::observe_latency;
+use super::reachability::cloudflare_reachability;
 use async_trait::async_trait;
+use log::warn;
 use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, AUTHORIZATION};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -28,9 +30,17 @@ struct UpdateRecordRequest {
     proxied: bool,
 }
 
+/// Cloudflare 限流錯誤碼
+const RATE_LIMIT_ERROR_CODE: u64 = 971;
+
+/// Cloudflare API 延遲直方圖名稱
+const API_LATENCY_METRIC: &str = "ddns_cloudflare_api_duration_seconds";
+/// Cloudflare API 延遲直方圖說明
+const API_LATENCY_HELP: &str = "Latency of Cloudflare API requests by operation";
+
 /// Cloudflare DNS 服務實現
 pub struct CloudflareDnsService {
-    http_client: Arc<ReqwestHttpClient>,
+    http_client: Arc<dyn HttpClient>,
     config: DdnsConfig,
 }
 
@@ -41,20 +51,9 @@ impl CloudflareDnsService {
     ///
     /// - `http_client`: HTTP 客戶端
     /// - `config`: DDNS 配置
-    pub fn new(http_client: Arc<dyn crate::domain::http::HttpClient>, config: DdnsConfig) -> Self {
-        // 由於 HttpClientExt 不是對象安全的，我們需要通過類型轉換來實現
-        // 在實際應用中，可能需要修改架構以避免這種類型轉換
-        let reqwest_client = match http_client.downcast_arc::<ReqwestHttpClient>() {
-            Ok(client) => client,
-            Err(_) => {
-                // 創建一個新的 ReqwestHttpClient 作為備選
-                log::warn!("Unable to downcast HTTP client to ReqwestHttpClient, creating a new instance");
-                Arc::new(ReqwestHttpClient::new())
-            }
-        };
-        
+    pub fn new(http_client: Arc<dyn HttpClient>, config: DdnsConfig) -> Self {
         Self {
-            http_client: reqwest_client,
+            http_client,
             config,
         }
     }
@@ -76,10 +75,48 @@ impl CloudflareDnsService {
         
         Ok(headers)
     }
+    
+    /// 創建請求失敗但可能已被處理時，查找是否已創建了相同的記錄
+    ///
+    /// 找到名稱、類型和內容都相同的記錄時返回該記錄，否則返回原錯誤，由下一次檢查重新創建
+    ///
+    /// # 參數
+    ///
+    /// - `zone_id`: 區域 ID
+    /// - `record`: 請求創建的記錄
+    /// - `error`: 創建請求的錯誤
+    async fn find_created_record(&self, zone_id: &str, record: &DnsRecord, error: DomainError) -> Result<DnsRecord, DomainError> {
+        match self.find_records(zone_id, &record.name, &record.record_type).await {
+            Ok(records) => match records.into_iter().find(|existing| existing.content == record.content) {
+                Some(existing) => {
+                    warn!(event = "record-created", error_kind = error.kind();
+                          "Create request for {} failed ({}), but the record was created", record.name, error);
+                    Ok(existing)
+                }
+                None => Err(error),
+            },
+            Err(e) => {
+                warn!(event = "record-created", error_kind = e.kind();
+                      "Failed to check whether {} was created: {}", record.name, e);
+                Err(error)
+            }
+        }
+    }
+    
+    /// 要更新的記錄 ID，記錄中未帶 ID 時使用配置中的記錄 ID
+    fn record_id<'a>(&'a self, record: &'a DnsRecord) -> &'a str {
+        record.id.as_deref().filter(|id| !id.is_empty()).unwrap_or(&self.config.record_id)
+    }
 }
 
 #[async_trait]
 impl DnsService for CloudflareDnsService {
+    #[tracing::instrument(
+        name = "cloudflare.update_record",
+        skip_all,
+        fields(zone_id = %self.config.zone_id, record_id = %self.record_id(&record), record_name = %record.name),
+        err(Display),
+    )]
     async fn update_record(&self, record: DnsRecord) -> Result<DnsUpdateResult, DomainError> {
         let headers = self.create_headers()?;
         
@@ -93,21 +130,20 @@ impl DnsService for CloudflareDnsService {
         
         let url = format!(
             "https://api.cloudflare.com/client/v4/zones/{}/dns_records/{}",
-            self.config.zone_id, self.config.record_id
+            self.config.zone_id, self.record_id(&record)
         );
         
-        let response: CloudflareResponse<DnsRecord> = self.http_client
-            .put_json(&url, Some(&update_data), Some(headers))
-            .await?;
+        let result = observe_latency(
+            API_LATENCY_METRIC,
+            API_LATENCY_HELP,
+            &[("operation", "update_record")],
+            self.http_client.put_json(&url, Some(&update_data), Some(headers)),
+        ).await;
+        cloudflare_reachability().record(&result);
+        let response: CloudflareResponse<DnsRecord> = result?;
         
         if !response.success {
-            let error_msg = if !response.errors.is_empty() {
-                format!("Cloudflare API error: {:?}", response.errors)
-            } else {
-                "Unknown Cloudflare API error".to_string()
-            };
-            
-            return Err(DomainError::LogicError(error_msg));
+            return Err(api_error(&response.errors));
         }
         
         match response.result {
@@ -119,6 +155,7 @@ impl DnsService for CloudflareDnsService {
         }
     }
     
+    #[tracing::instrument(name = "cloudflare.get_record", skip(self), err(Display))]
     async fn get_record(&self, zone_id: &str, record_id: &str) -> Result<DnsRecord, DomainError> {
         let headers = self.create_headers()?;
         
@@ -127,18 +164,17 @@ impl DnsService for CloudflareDnsService {
             zone_id, record_id
         );
         
-        let response: CloudflareResponse<DnsRecord> = self.http_client
-            .get_json(&url, Some(headers))
-            .await?;
+        let result = observe_latency(
+            API_LATENCY_METRIC,
+            API_LATENCY_HELP,
+            &[("operation", "get_record")],
+            self.http_client.get_json(&url, Some(headers)),
+        ).await;
+        cloudflare_reachability().record(&result);
+        let response: CloudflareResponse<DnsRecord> = result?;
         
         if !response.success {
-            let error_msg = if !response.errors.is_empty() {
-                format!("Cloudflare API error: {:?}", response.errors)
-            } else {
-                "Unknown Cloudflare API error".to_string()
-            };
-            
-            return Err(DomainError::LogicError(error_msg));
+            return Err(api_error(&response.errors));
         }
         
         match response.result {
@@ -147,6 +183,7 @@ impl DnsService for CloudflareDnsService {
         }
     }
     
+    #[tracing::instrument(name = "cloudflare.list_records", skip(self), err(Display))]
     async fn get_records(&self, zone_id: &str) -> Result<Vec<DnsRecord>, DomainError> {
         let headers = self.create_headers()?;
         
@@ -155,18 +192,17 @@ impl DnsService for CloudflareDnsService {
             zone_id
         );
         
-        let response: CloudflareResponse<Vec<DnsRecord>> = self.http_client
-            .get_json(&url, Some(headers))
-            .await?;
+        let result = observe_latency(
+            API_LATENCY_METRIC,
+            API_LATENCY_HELP,
+            &[("operation", "list_records")],
+            self.http_client.get_json(&url, Some(headers)),
+        ).await;
+        cloudflare_reachability().record(&result);
+        let response: CloudflareResponse<Vec<DnsRecord>> = result?;
         
         if !response.success {
-            let error_msg = if !response.errors.is_empty() {
-                format!("Cloudflare API error: {:?}", response.errors)
-            } else {
-                "Unknown Cloudflare API error".to_string()
-            };
-            
-            return Err(DomainError::LogicError(error_msg));
+            return Err(api_error(&response.errors));
         }
         
         match response.result {
@@ -175,6 +211,35 @@ impl DnsService for CloudflareDnsService {
         }
     }
     
+    #[tracing::instrument(name = "cloudflare.find_records", skip(self), err(Display))]
+    async fn find_records(&self, zone_id: &str, name: &str, record_type: &str) -> Result<Vec<DnsRecord>, DomainError> {
+        let headers = self.create_headers()?;
+        
+        let mut url = url::Url::parse(&format!(
+            "https://api.cloudflare.com/client/v4/zones/{}/dns_records",
+            zone_id
+        )).map_err(|e| DomainError::validation(format!("Invalid zone ID: {}", e)))?;
+        url.query_pairs_mut()
+            .append_pair("name", name)
+            .append_pair("type", record_type);
+        
+        let result = observe_latency(
+            API_LATENCY_METRIC,
+            API_LATENCY_HELP,
+            &[("operation", "find_records")],
+            self.http_client.get_json(url.as_str(), Some(headers)),
+        ).await;
+        cloudflare_reachability().record(&result);
+        let response: CloudflareResponse<Vec<DnsRecord>> = result?;
+        
+        if !response.success {
+            return Err(api_error(&response.errors));
+        }
+        
+        Ok(response.result.unwrap_or_default())
+    }
+    
+    #[tracing::instrument(name = "cloudflare.create_record", skip(self, record), fields(record_name = %record.name), err(Display))]
     async fn create_record(&self, zone_id: &str, record: DnsRecord) -> Result<DnsRecord, DomainError> {
         let headers = self.create_headers()?;
         
@@ -191,18 +256,22 @@ impl DnsService for CloudflareDnsService {
             zone_id
         );
         
-        let response: CloudflareResponse<DnsRecord> = self.http_client
-            .post_json(&url, Some(&create_data), Some(headers))
-            .await?;
+        // 創建不是冪等操作，不自動重試，避免超時後重試創建出重複記錄
+        let result = observe_latency(
+            API_LATENCY_METRIC,
+            API_LATENCY_HELP,
+            &[("operation", "create_record")],
+            self.http_client.post_json_once(&url, Some(&create_data), Some(headers)),
+        ).await;
+        cloudflare_reachability().record(&result);
+        let response: CloudflareResponse<DnsRecord> = match result {
+            Ok(response) => response,
+            Err(e) if e.is_retryable() => return self.find_created_record(zone_id, &record, e).await,
+            Err(e) => return Err(e),
+        };
         
         if !response.success {
-            let error_msg = if !response.errors.is_empty() {
-                format!("Cloudflare API error: {:?}", response.errors)
-            } else {
-                "Unknown Cloudflare API error".to_string()
-            };
-            
-            return Err(DomainError::LogicError(error_msg));
+            return Err(api_error(&response.errors));
         }
         
         match response.result {
@@ -210,4 +279,46 @@ impl DnsService for CloudflareDnsService {
             None => Err(DomainError::LogicError("Failed to create DNS record".to_string())),
         }
     }
-} 
\ No newline at end of file
+    
+    async fn delete_record(&self, zone_id: &str, record_id: &str) -> Result<(), DomainError> {
+        let headers = self.create_headers()?;
+        
+        let url = format!(
+            "https://api.cloudflare.com/client/v4/zones/{}/dns_records/{}",
+            zone_id, record_id
+        );
+        
+        let result = observe_latency(
+            API_LATENCY_METRIC,
+            API_LATENCY_HELP,
+            &[("operation", "delete_record")],
+            self.http_client.delete_json(&url, Some(headers)),
+        ).await;
+        cloudflare_reachability().record(&result);
+        let response: CloudflareResponse<serde_json::Value> = result?;
+        
+        if !response.success {
+            return Err(api_error(&response.errors));
+        }
+        
+        Ok(())
+    }
+} 
+
+/// 將 Cloudflare 返回的錯誤列表轉換為領域錯誤
+///
+/// 錯誤碼 971 表示請求被限流，轉換為 `RateLimitError` 以便調用方退避
+fn api_error(errors: &[serde_json::Value]) -> DomainError {
+    if errors.is_empty() {
+        return DomainError::LogicError("Unknown Cloudflare API error".to_string());
+    }
+    
+    let error_msg = format!("Cloudflare API error: {:?}", errors);
+    let rate_limited = errors.iter()
+        .any(|error| error.get("code").and_then(|code| code.as_u64()) == Some(RATE_LIMIT_ERROR_CODE));
+    if rate_limited {
+        DomainError::Api(ApiErrorType::RateLimitError(error_msg, None))
+    } else {
+        DomainError::LogicError(error_msg)
+    }
+}
@@ -1,3 +1,5 @@
 mod cloudflare_service;
+mod reachability;
 
-pub use cloudflare_service::CloudflareDnsService; 
\ No newline at end of file
+pub use cloudflare_service::CloudflareDnsService;
+pub use reachability::{cloudflare_reachability, ReachabilitySnapshot, ReachabilityTracker};
@@ -0,0 +1,84 @@
+use crate::domain::error::{DomainError, NetworkErrorType};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use std::sync::RwLock;
+
+/// 最近一次 API 調用的可達性
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReachabilitySnapshot {
+    /// 最近一次調用是否可達，尚未調用過時為 None
+    pub reachable: Option<bool>,
+    /// 最近一次收到響應的時間
+    pub last_success: Option<DateTime<Utc>>,
+    /// 最近一次無法連接的時間
+    pub last_failure: Option<DateTime<Utc>>,
+    /// 最近一次無法連接的錯誤信息
+    pub last_error: Option<String>,
+}
+
+impl ReachabilitySnapshot {
+    /// 最近一次調用是否可達，尚未調用過時返回 None
+    pub fn is_reachable(&self) -> Option<bool> {
+        self.reachable
+    }
+}
+
+/// API 可達性追蹤器
+///
+/// 根據實際 API 調用的結果被動記錄，不會主動發起探測請求
+#[derive(Debug, Default)]
+pub struct ReachabilityTracker {
+    state: RwLock<ReachabilitySnapshot>,
+}
+
+impl ReachabilityTracker {
+    /// 創建新的可達性追蹤器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 記錄一次 API 調用的結果
+    ///
+    /// 收到 API 響應（包括 4xx 和業務錯誤）視為可達，連接失敗、超時和 5xx 視為不可達
+    ///
+    /// # 參數
+    ///
+    /// - `result`: API 調用結果
+    pub fn record<T>(&self, result: &Result<T, DomainError>) {
+        let now = Utc::now();
+        let mut state = self.state.write().unwrap();
+        match result {
+            Err(error) if is_unreachable(error) => {
+                state.reachable = Some(false);
+                state.last_failure = Some(now);
+                state.last_error = Some(error.to_string());
+            },
+            _ => {
+                state.reachable = Some(true);
+                state.last_success = Some(now);
+            },
+        }
+    }
+
+    /// 獲取可達性快照
+    pub fn snapshot(&self) -> ReachabilitySnapshot {
+        self.state.read().unwrap().clone()
+    }
+}
+
+/// 錯誤是否表示服務不可達
+fn is_unreachable(error: &DomainError) -> bool {
+    match error {
+        DomainError::Network(NetworkErrorType::HttpError(status)) => *status >= 500,
+        DomainError::Network(_) => true,
+        _ => false,
+    }
+}
+
+/// Cloudflare API 可達性
+static CLOUDFLARE_REACHABILITY: Lazy<ReachabilityTracker> = Lazy::new(ReachabilityTracker::new);
+
+/// 獲取 Cloudflare API 可達性追蹤器
+pub fn cloudflare_reachability() -> &'static ReachabilityTracker {
+    &CLOUDFLARE_REACHABILITY
+}
@@ -0,0 +1,3 @@
+mod network_probe;
+
+pub use network_probe::NetworkHealthProbe;
@@ -0,0 +1,70 @@
+use crate::domain::config::HealthCheck;
+use crate::domain::error::DomainError;
+use crate::domain::failover::HealthProbe;
+use async_trait::async_trait;
+use reqwest::redirect::Policy;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+/// 通過網絡連接執行健康檢查
+///
+/// HTTP 檢查直接連接主目標，不使用代理，也不跟隨重定向（重定向可能指向由本服務維護的域名）
+pub struct NetworkHealthProbe {
+    client: reqwest::Client,
+}
+
+impl Default for NetworkHealthProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NetworkHealthProbe {
+    /// 創建新的健康檢查執行器
+    pub fn new() -> Self {
+        let client = reqwest::Client::builder()
+            .no_proxy()
+            .redirect(Policy::none())
+            .build()
+            .unwrap_or_default();
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl HealthProbe for NetworkHealthProbe {
+    async fn probe(&self, check: &HealthCheck, target: IpAddr) -> Result<(), DomainError> {
+        let limit = Duration::from_secs(check.timeout_seconds());
+        match check {
+            HealthCheck::Tcp { port, .. } => {
+                let address = SocketAddr::new(target, *port);
+                match timeout(limit, TcpStream::connect(address)).await {
+                    Ok(Ok(_)) => Ok(()),
+                    Ok(Err(e)) => Err(DomainError::network(format!("TCP connect to {} failed: {}", address, e))),
+                    Err(_) => Err(DomainError::network(format!("TCP connect to {} timed out", address))),
+                }
+            },
+            HealthCheck::Http { url, expected_status, .. } => {
+                let url = HealthCheck::target_url(url, target);
+                let response = self.client
+                    .get(&url)
+                    .timeout(limit)
+                    .send()
+                    .await
+                    .map_err(|e| DomainError::network(format!("GET {} failed: {}", url, e)))?;
+                let status = response.status();
+                let passed = match expected_status {
+                    Some(expected) => status.as_u16() == *expected,
+                    None => status.is_success(),
+                };
+                if passed {
+                    Ok(())
+                } else {
+                    Err(DomainError::network(format!("GET {} returned unexpected status {}", url, status)))
+                }
+            },
+        }
+    }
+}
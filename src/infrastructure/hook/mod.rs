@@ -0,0 +1,3 @@
+mod shell_runner;
+
+pub use shell_runner::ShellHookRunner;
@@ -0,0 +1,87 @@
+use crate::domain::config::HookCommand;
+use crate::domain::error::DomainError;
+use crate::domain::hook::{HookOutput, HookRunner};
+use async_trait::async_trait;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tokio::time::timeout;
+
+/// 捕獲輸出的最大長度（字節）
+const MAX_OUTPUT_LENGTH: usize = 4096;
+
+/// 通過系統 shell 執行鉤子命令
+#[derive(Default)]
+pub struct ShellHookRunner;
+
+impl ShellHookRunner {
+    /// 創建新的鉤子執行器
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 創建 shell 命令
+    fn shell_command(command: &str) -> Command {
+        #[cfg(windows)]
+        {
+            let mut cmd = Command::new("cmd");
+            cmd.arg("/C").arg(command);
+            cmd
+        }
+        #[cfg(not(windows))]
+        {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(command);
+            cmd
+        }
+    }
+}
+
+/// 將輸出轉換為字符串，過長時截斷
+fn capture_output(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    let text = text.trim_end();
+    if text.len() <= MAX_OUTPUT_LENGTH {
+        return text.to_string();
+    }
+
+    let mut end = MAX_OUTPUT_LENGTH;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...(truncated)", &text[..end])
+}
+
+#[async_trait]
+impl HookRunner for ShellHookRunner {
+    async fn run(&self, hook: &HookCommand, env: &[(String, String)]) -> Result<HookOutput, DomainError> {
+        let started = Instant::now();
+        let child = Self::shell_command(&hook.command)
+            .envs(env.iter().map(|(key, value)| (key, value)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // 超時時丟棄子進程即終止命令
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| DomainError::hook(format!("Failed to start hook `{}`: {}", hook.command, e)))?;
+
+        match timeout(Duration::from_secs(hook.timeout_seconds), child.wait_with_output()).await {
+            Ok(Ok(output)) => Ok(HookOutput {
+                exit_code: output.status.code(),
+                stdout: capture_output(&output.stdout),
+                stderr: capture_output(&output.stderr),
+                duration: started.elapsed(),
+                timed_out: false,
+            }),
+            Ok(Err(e)) => Err(DomainError::hook(format!("Failed to wait for hook `{}`: {}", hook.command, e))),
+            Err(_) => Ok(HookOutput {
+                exit_code: None,
+                stdout: String::new(),
+                stderr: String::new(),
+                duration: started.elapsed(),
+                timed_out: true,
+            }),
+        }
+    }
+}
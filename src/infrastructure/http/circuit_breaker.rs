@@ -0,0 +1,279 @@
+use crate::domain::error::{DomainError, NetworkErrorType};
+use crate::infrastructure::metrics::metrics;
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// 熔斷器狀態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// 正常放行請求
+    Closed,
+    /// 拒絕所有請求，直到冷卻時間結束
+    Open,
+    /// 冷卻結束，放行一個探測請求
+    HalfOpen,
+}
+
+impl CircuitState {
+    /// 指標中使用的數值：0 關閉、1 半開、2 開啟
+    pub fn as_gauge(&self) -> f64 {
+        match self {
+            CircuitState::Closed => 0.0,
+            CircuitState::HalfOpen => 1.0,
+            CircuitState::Open => 2.0,
+        }
+    }
+}
+
+impl fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitState::Closed => write!(f, "closed"),
+            CircuitState::Open => write!(f, "open"),
+            CircuitState::HalfOpen => write!(f, "half_open"),
+        }
+    }
+}
+
+/// 熔斷器配置
+///
+/// # 欄位
+///
+/// - `failure_threshold`: 連續失敗多少次後開啟熔斷器
+/// - `open_duration`: 開啟後等待多久才放行探測請求
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitBreakerConfig {
+    pub failure_threshold: u32,
+    pub open_duration: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+/// 單個主機的熔斷器快照
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CircuitSnapshot {
+    pub host: String,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    /// 最近一次開啟的時間
+    pub opened_at: Option<DateTime<Utc>>,
+    /// 最近一次失敗的錯誤信息
+    pub last_error: Option<String>,
+}
+
+/// 單個主機的熔斷器
+#[derive(Debug)]
+struct HostCircuit {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    opened_at_utc: Option<DateTime<Utc>>,
+    probe_started: Option<Instant>,
+    last_error: Option<String>,
+}
+
+impl HostCircuit {
+    fn new() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            opened_at: None,
+            opened_at_utc: None,
+            probe_started: None,
+            last_error: None,
+        }
+    }
+
+    fn open(&mut self) {
+        self.state = CircuitState::Open;
+        self.opened_at = Some(Instant::now());
+        self.opened_at_utc = Some(Utc::now());
+        self.probe_started = None;
+    }
+}
+
+/// 按主機劃分的熔斷器註冊表
+///
+/// 連續失敗達到閾值後開啟熔斷器並直接拒絕該主機的請求；冷卻時間結束後放行一個探測請求，
+/// 探測成功則關閉，失敗則重新開啟
+#[derive(Debug)]
+pub struct CircuitBreakerRegistry {
+    config: CircuitBreakerConfig,
+    circuits: RwLock<BTreeMap<String, HostCircuit>>,
+}
+
+impl Default for CircuitBreakerRegistry {
+    fn default() -> Self {
+        Self::new(CircuitBreakerConfig::default())
+    }
+}
+
+impl CircuitBreakerRegistry {
+    /// 創建新的熔斷器註冊表
+    ///
+    /// # 參數
+    ///
+    /// - `config`: 熔斷器配置
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            circuits: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// 請求發出前檢查熔斷器
+    ///
+    /// # 參數
+    ///
+    /// - `host`: 目標主機
+    ///
+    /// # 返回
+    ///
+    /// - `Result<(), DomainError>`: 熔斷器開啟時返回 `CircuitOpen` 錯誤
+    pub fn acquire(&self, host: &str) -> Result<(), DomainError> {
+        let mut circuits = self.circuits.write().unwrap();
+        let Some(circuit) = circuits.get_mut(host) else {
+            return Ok(());
+        };
+
+        match circuit.state {
+            CircuitState::Closed => return Ok(()),
+            CircuitState::Open => {
+                let cooled_down = circuit.opened_at
+                    .is_some_and(|opened_at| opened_at.elapsed() >= self.config.open_duration);
+                if cooled_down {
+                    info!("{} 的熔斷器進入半開狀態，放行探測請求", host);
+                    circuit.state = CircuitState::HalfOpen;
+                    circuit.probe_started = Some(Instant::now());
+                    return Ok(());
+                }
+            },
+            CircuitState::HalfOpen => {
+                // 探測請求被取消而未記錄結果時，超過冷卻時間後放行新的探測
+                let probe_stale = circuit.probe_started
+                    .is_none_or(|started| started.elapsed() >= self.config.open_duration);
+                if probe_stale {
+                    circuit.probe_started = Some(Instant::now());
+                    return Ok(());
+                }
+            },
+        }
+
+        metrics().inc_counter(
+            "ddns_circuit_breaker_rejections_total",
+            "Requests rejected by an open circuit breaker by host",
+            &[("host", host)],
+        );
+        Err(DomainError::Network(NetworkErrorType::CircuitOpen(host.to_string())))
+    }
+
+    /// 記錄請求結果
+    ///
+    /// 連接失敗、超時和 5xx 計為失敗，其他響應（包括 4xx）視為主機可用
+    ///
+    /// # 參數
+    ///
+    /// - `host`: 目標主機
+    /// - `result`: 請求結果
+    pub fn record<T>(&self, host: &str, result: &Result<T, DomainError>) {
+        let failure = match result {
+            Err(error) if is_failure(error) => Some(error.to_string()),
+            _ => None,
+        };
+
+        let mut circuits = self.circuits.write().unwrap();
+        match failure {
+            None => {
+                if let Some(circuit) = circuits.get_mut(host) {
+                    if circuit.state != CircuitState::Closed {
+                        info!("{} 已恢復，關閉熔斷器", host);
+                    }
+                    circuit.state = CircuitState::Closed;
+                    circuit.consecutive_failures = 0;
+                    circuit.probe_started = None;
+                }
+            },
+            Some(error) => {
+                let circuit = circuits.entry(host.to_string()).or_insert_with(HostCircuit::new);
+                circuit.consecutive_failures += 1;
+                circuit.last_error = Some(error);
+
+                let should_open = match circuit.state {
+                    CircuitState::HalfOpen => true,
+                    CircuitState::Closed => circuit.consecutive_failures >= self.config.failure_threshold,
+                    CircuitState::Open => false,
+                };
+                if should_open {
+                    warn!("{} 連續失敗 {} 次，開啟熔斷器 {} 秒",
+                          host, circuit.consecutive_failures, self.config.open_duration.as_secs());
+                    circuit.open();
+                }
+            },
+        }
+    }
+
+    /// 獲取指定主機的熔斷器狀態，未記錄過失敗的主機為關閉狀態
+    pub fn state(&self, host: &str) -> CircuitState {
+        self.circuits.read().unwrap()
+            .get(host)
+            .map_or(CircuitState::Closed, |circuit| circuit.state)
+    }
+
+    /// 獲取所有記錄過失敗的主機的熔斷器快照
+    pub fn snapshot(&self) -> Vec<CircuitSnapshot> {
+        self.circuits.read().unwrap()
+            .iter()
+            .map(|(host, circuit)| CircuitSnapshot {
+                host: host.clone(),
+                state: circuit.state,
+                consecutive_failures: circuit.consecutive_failures,
+                opened_at: circuit.opened_at_utc,
+                last_error: circuit.last_error.clone(),
+            })
+            .collect()
+    }
+}
+
+/// 錯誤是否表示主機故障
+fn is_failure(error: &DomainError) -> bool {
+    match error {
+        DomainError::Network(NetworkErrorType::HttpError(status)) => *status >= 500,
+        DomainError::Network(NetworkErrorType::CircuitOpen(_)) => false,
+        DomainError::Network(_) => true,
+        _ => false,
+    }
+}
+
+/// 請求 URL 的主機（含端口），作為熔斷器的鍵
+pub fn circuit_key(url: &str) -> String {
+    match url::Url::parse(url) {
+        Ok(parsed) => match (parsed.host_str(), parsed.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            _ => url.to_string(),
+        },
+        Err(_) => url.to_string(),
+    }
+}
+
+/// 全局熔斷器註冊表
+static CIRCUIT_BREAKERS: Lazy<CircuitBreakerRegistry> = Lazy::new(CircuitBreakerRegistry::default);
+
+/// 獲取全局熔斷器註冊表
+pub fn circuit_breakers() -> &'static CircuitBreakerRegistry {
+    &CIRCUIT_BREAKERS
+}
@@ -1,5 +1,9 @@
+mod circuit_breaker;
 mod reqwest_client;
+mod retry_policy;
 mod retryable_client;
 
-pub use reqwest_client::ReqwestHttpClient;
-pub use retryable_client::RetryableHttpClient; 
\ No newline at end of file
+pub use circuit_breaker::{circuit_breakers, circuit_key, CircuitBreakerConfig, CircuitBreakerRegistry, CircuitSnapshot, CircuitState};
+pub use reqwest_client::{parse_retry_after, ReqwestHttpClient};
+pub use retry_policy::RetryPolicy;
+pub use retryable_client::RetryableHttpClient;
@@ -1,14 +1,20 @@
-use crate::domain::error::DomainError;
-use crate::domain::http::{HttpClient, HttpClientExt};
+use crate::domain::config::{HttpClientSettings, IpType};
+use crate::domain::error::{ApiErrorType, DomainError, NetworkErrorType};
+use crate::domain::http::HttpClient;
+use crate::infrastructure::http::circuit_breaker::{circuit_breakers, circuit_key, CircuitBreakerRegistry};
 use async_trait::async_trait;
-use reqwest::header::HeaderMap;
-use serde::de::DeserializeOwned;
-use serde::Serialize;
-use std::any::Any;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::StatusCode;
+use chrono::{DateTime, Utc};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
 
 /// Reqwest HTTP 客戶端實現
 pub struct ReqwestHttpClient {
     client: reqwest::Client,
+    circuit_breakers: &'static CircuitBreakerRegistry,
+    /// 綁定的本地地址，用於區分不同出口的熔斷器
+    local_address: Option<IpAddr>,
 }
 
 impl Default for ReqwestHttpClient {
@@ -22,8 +28,123 @@ impl ReqwestHttpClient {
     pub fn new() -> Self {
         Self {
             client: reqwest::Client::new(),
+            circuit_breakers: circuit_breakers(),
+            local_address: None,
         }
     }
+    
+    /// 根據 HTTP 客戶端設置創建客戶端
+    ///
+    /// # 參數
+    ///
+    /// - `settings`: HTTP 客戶端設置
+    ///
+    /// # 返回
+    ///
+    /// - `Result<Self, DomainError>`: 設置無效或 CA 證書無法讀取時返回錯誤
+    pub fn from_settings(settings: &HttpClientSettings) -> Result<Self, DomainError> {
+        Self::build(settings, settings.bind_address)
+    }
+    
+    /// 創建只使用指定協議族連接的客戶端
+    ///
+    /// 本地地址綁定到該協議族的地址，使雙棧主機上的請求只經由 IPv4 或 IPv6 發出；
+    /// 設置的 `bind_address` 屬於該協議族時使用它，否則使用未指定地址（`0.0.0.0` 或 `::`）
+    ///
+    /// # 參數
+    ///
+    /// - `settings`: HTTP 客戶端設置
+    /// - `ip_type`: 協議族
+    pub fn for_ip_type(settings: &HttpClientSettings, ip_type: IpType) -> Result<Self, DomainError> {
+        let local_address = match (ip_type, settings.bind_address) {
+            (IpType::IPv4, Some(address @ IpAddr::V4(_))) | (IpType::IPv6, Some(address @ IpAddr::V6(_))) => address,
+            (IpType::IPv4, _) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            (IpType::IPv6, _) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+        };
+        Self::build(settings, Some(local_address))
+    }
+    
+    /// 創建從指定本地地址發出請求的客戶端
+    ///
+    /// 用於多出口環境下讓請求經由指定的出口發出
+    ///
+    /// # 參數
+    ///
+    /// - `settings`: HTTP 客戶端設置
+    /// - `local_address`: 本地地址，覆蓋設置中的 `bind_address`
+    pub fn bound_to(settings: &HttpClientSettings, local_address: IpAddr) -> Result<Self, DomainError> {
+        Self::build(settings, Some(local_address))
+    }
+    
+    /// 按設置構建 reqwest 客戶端
+    fn build(settings: &HttpClientSettings, local_address: Option<IpAddr>) -> Result<Self, DomainError> {
+        settings.validate()?;
+        
+        let mut builder = reqwest::Client::builder().local_address(local_address);
+        
+        if let Some(proxy) = &settings.proxy {
+            let proxy = reqwest::Proxy::all(proxy)
+                .map_err(|e| DomainError::config(format!("無效的代理地址 {}: {}", proxy, e)))?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(seconds) = settings.connect_timeout_secs {
+            builder = builder.connect_timeout(Duration::from_secs(seconds));
+        }
+        if let Some(seconds) = settings.timeout_secs {
+            builder = builder.timeout(Duration::from_secs(seconds));
+        }
+        if let Some(path) = &settings.ca_bundle {
+            let pem = std::fs::read(path)
+                .map_err(|e| DomainError::config(format!("無法讀取 CA 證書 {}: {}", path, e)))?;
+            let certificates = reqwest::Certificate::from_pem_bundle(&pem)
+                .map_err(|e| DomainError::config(format!("無效的 CA 證書 {}: {}", path, e)))?;
+            if certificates.is_empty() {
+                return Err(DomainError::config(format!("CA 證書文件中沒有證書: {}", path)));
+            }
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        
+        let client = builder.build()
+            .map_err(|e| DomainError::config(format!("創建 HTTP 客戶端失敗: {}", e)))?;
+        
+        Ok(Self {
+            client,
+            circuit_breakers: circuit_breakers(),
+            local_address,
+        })
+    }
+    
+    /// 設置熔斷器註冊表（默認使用全局註冊表）
+    ///
+    /// # 參數
+    ///
+    /// - `circuit_breakers`: 熔斷器註冊表
+    pub fn with_circuit_breakers(mut self, circuit_breakers: &'static CircuitBreakerRegistry) -> Self {
+        self.circuit_breakers = circuit_breakers;
+        self
+    }
+    
+    /// 發送請求並將非成功狀態碼轉換為錯誤
+    ///
+    /// 目標主機的熔斷器開啟時不發出請求，直接返回 `CircuitOpen` 錯誤；
+    /// 綁定了本地地址的客戶端按「主機@本地地址」區分熔斷器，一個出口故障不影響其他出口
+    async fn send(&self, url: &str, request: reqwest::RequestBuilder) -> Result<reqwest::Response, DomainError> {
+        let host = match self.local_address {
+            Some(local_address) => format!("{}@{}", circuit_key(url), local_address),
+            None => circuit_key(url),
+        };
+        self.circuit_breakers.acquire(&host)?;
+        
+        let result = match request.send().await {
+            Ok(response) if response.status().is_success() => Ok(response),
+            Ok(response) => Err(status_error(&response)),
+            Err(e) => Err(DomainError::from(e)),
+        };
+        self.circuit_breakers.record(&host, &result);
+        result
+    }
 }
 
 #[async_trait]
@@ -35,15 +156,7 @@ impl HttpClient for ReqwestHttpClient {
             request = request.headers(headers);
         }
         
-        let response = request.send().await.map_err(|e| {
-            DomainError::LogicError(format!("HTTP request failed: {}", e))
-        })?;
-        
-        if !response.status().is_success() {
-            return Err(DomainError::LogicError(format!(
-                "HTTP request failed with status: {}", response.status()
-            )));
-        }
+        let response = self.send(url, request).await?;
         
         response.text().await.map_err(|e| {
             DomainError::LogicError(format!("Failed to read response body: {}", e))
@@ -61,15 +174,7 @@ impl HttpClient for ReqwestHttpClient {
             request = request.body(body);
         }
         
-        let response = request.send().await.map_err(|e| {
-            DomainError::LogicError(format!("HTTP request failed: {}", e))
-        })?;
-        
-        if !response.status().is_success() {
-            return Err(DomainError::LogicError(format!(
-                "HTTP request failed with status: {}", response.status()
-            )));
-        }
+        let response = self.send(url, request).await?;
         
         response.text().await.map_err(|e| {
             DomainError::LogicError(format!("Failed to read response body: {}", e))
@@ -87,15 +192,7 @@ impl HttpClient for ReqwestHttpClient {
             request = request.body(body);
         }
         
-        let response = request.send().await.map_err(|e| {
-            DomainError::LogicError(format!("HTTP request failed: {}", e))
-        })?;
-        
-        if !response.status().is_success() {
-            return Err(DomainError::LogicError(format!(
-                "HTTP request failed with status: {}", response.status()
-            )));
-        }
+        let response = self.send(url, request).await?;
         
         response.text().await.map_err(|e| {
             DomainError::LogicError(format!("Failed to read response body: {}", e))
@@ -109,122 +206,48 @@ impl HttpClient for ReqwestHttpClient {
             request = request.headers(headers);
         }
         
-        let response = request.send().await.map_err(|e| {
-            DomainError::LogicError(format!("HTTP request failed: {}", e))
-        })?;
-        
-        if !response.status().is_success() {
-            return Err(DomainError::LogicError(format!(
-                "HTTP request failed with status: {}", response.status()
-            )));
-        }
+        let response = self.send(url, request).await?;
         
         response.text().await.map_err(|e| {
             DomainError::LogicError(format!("Failed to read response body: {}", e))
         })
     }
+}
 
-    /// 實現 as_any 方法
-    fn as_any(&self) -> &dyn Any {
-        self
+
+/// 將非成功狀態碼的響應轉換為領域錯誤
+///
+/// 429 轉換為頻率限制錯誤，503 轉換為服務暫不可用錯誤，兩者都附帶 `Retry-After` 指定的等待時間
+fn status_error(response: &reqwest::Response) -> DomainError {
+    let retry_after = response.headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| parse_retry_after(value, Utc::now()));
+
+    match response.status() {
+        StatusCode::TOO_MANY_REQUESTS => DomainError::Api(ApiErrorType::RateLimitError(
+            format!("{} 返回 HTTP 429", response.url().host_str().unwrap_or_default()),
+            retry_after,
+        )),
+        StatusCode::SERVICE_UNAVAILABLE => DomainError::Network(NetworkErrorType::ServiceUnavailable(retry_after)),
+        status => DomainError::Network(NetworkErrorType::HttpError(status.as_u16())),
     }
 }
 
-#[async_trait]
-impl HttpClientExt for ReqwestHttpClient {
-    async fn get_json<T: DeserializeOwned + Send + 'static>(&self, url: &str, headers: Option<HeaderMap>) -> Result<T, DomainError> {
-        let mut request = self.client.get(url);
-        
-        if let Some(headers) = headers {
-            request = request.headers(headers);
-        }
-        
-        let response = request.send().await.map_err(|e| {
-            DomainError::LogicError(format!("HTTP request failed: {}", e))
-        })?;
-        
-        if !response.status().is_success() {
-            return Err(DomainError::LogicError(format!(
-                "HTTP request failed with status: {}", response.status()
-            )));
-        }
-        
-        response.json::<T>().await.map_err(|e| {
-            DomainError::LogicError(format!("Failed to parse JSON response: {}", e))
-        })
+/// 解析 `Retry-After` 頭
+///
+/// 支持秒數和 HTTP 日期兩種格式，日期已過時返回零
+///
+/// # 參數
+///
+/// - `value`: 頭部值
+/// - `now`: 當前時間
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
     }
-    
-    async fn post_json<T: DeserializeOwned + Send + 'static, U: Serialize + Send + Sync>(&self, url: &str, body: Option<&U>, headers: Option<HeaderMap>) -> Result<T, DomainError> {
-        let mut request = self.client.post(url);
-        
-        if let Some(headers) = headers {
-            request = request.headers(headers);
-        }
-        
-        if let Some(body) = body {
-            request = request.json(body);
-        }
-        
-        let response = request.send().await.map_err(|e| {
-            DomainError::LogicError(format!("HTTP request failed: {}", e))
-        })?;
-        
-        if !response.status().is_success() {
-            return Err(DomainError::LogicError(format!(
-                "HTTP request failed with status: {}", response.status()
-            )));
-        }
-        
-        response.json::<T>().await.map_err(|e| {
-            DomainError::LogicError(format!("Failed to parse JSON response: {}", e))
-        })
-    }
-    
-    async fn put_json<T: DeserializeOwned + Send + 'static, U: Serialize + Send + Sync>(&self, url: &str, body: Option<&U>, headers: Option<HeaderMap>) -> Result<T, DomainError> {
-        let mut request = self.client.put(url);
-        
-        if let Some(headers) = headers {
-            request = request.headers(headers);
-        }
-        
-        if let Some(body) = body {
-            request = request.json(body);
-        }
-        
-        let response = request.send().await.map_err(|e| {
-            DomainError::LogicError(format!("HTTP request failed: {}", e))
-        })?;
-        
-        if !response.status().is_success() {
-            return Err(DomainError::LogicError(format!(
-                "HTTP request failed with status: {}", response.status()
-            )));
-        }
-        
-        response.json::<T>().await.map_err(|e| {
-            DomainError::LogicError(format!("Failed to parse JSON response: {}", e))
-        })
-    }
-    
-    async fn delete_json<T: DeserializeOwned + Send + 'static>(&self, url: &str, headers: Option<HeaderMap>) -> Result<T, DomainError> {
-        let mut request = self.client.delete(url);
-        
-        if let Some(headers) = headers {
-            request = request.headers(headers);
-        }
-        
-        let response = request.send().await.map_err(|e| {
-            DomainError::LogicError(format!("HTTP request failed: {}", e))
-        })?;
-        
-        if !response.status().is_success() {
-            return Err(DomainError::LogicError(format!(
-                "HTTP request failed with status: {}", response.status()
-            )));
-        }
-        
-        response.json::<T>().await.map_err(|e| {
-            DomainError::LogicError(format!("Failed to parse JSON response: {}", e))
-        })
-    }
-} 
\ No newline at end of file
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    Some((date - now).to_std().unwrap_or(Duration::ZERO))
+}
@@ -0,0 +1,93 @@
+use crate::domain::error::DomainError;
+use backoff::backoff::Backoff;
+use backoff::{ExponentialBackoff, ExponentialBackoffBuilder};
+use std::time::Duration;
+
+/// HTTP 請求重試策略
+///
+/// 重試間隔按指數增長並加入隨機抖動；服務端通過 `Retry-After` 指定等待時間時以其為準
+///
+/// # 欄位
+///
+/// - `max_retries`: 最大重試次數（不含首次請求）
+/// - `initial_interval`: 首次重試前的等待時間
+/// - `max_interval`: 單次等待時間上限
+/// - `multiplier`: 每次重試後等待時間的增長倍數
+/// - `randomization_factor`: 抖動比例，實際等待時間在 `interval * (1 ± factor)` 之間
+/// - `max_elapsed_time`: 從首次請求起允許重試的總時長，為 None 時不限制
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_interval: Duration,
+    pub max_interval: Duration,
+    pub multiplier: f64,
+    pub randomization_factor: f64,
+    pub max_elapsed_time: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_interval: Duration::from_millis(500),
+            max_interval: Duration::from_secs(10),
+            multiplier: 2.0,
+            randomization_factor: 0.5,
+            max_elapsed_time: Some(Duration::from_secs(60)),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 創建使用默認增長倍數、抖動和總時長的重試策略
+    ///
+    /// # 參數
+    ///
+    /// - `max_retries`: 最大重試次數
+    /// - `initial_interval`: 首次重試前的等待時間
+    pub fn new(max_retries: u32, initial_interval: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_interval,
+            ..Self::default()
+        }
+    }
+
+    /// 為一次請求創建退避計時器
+    pub fn backoff(&self) -> ExponentialBackoff {
+        ExponentialBackoffBuilder::new()
+            .with_initial_interval(self.initial_interval)
+            .with_max_interval(self.max_interval.max(self.initial_interval))
+            .with_multiplier(self.multiplier)
+            .with_randomization_factor(self.randomization_factor)
+            .with_max_elapsed_time(self.max_elapsed_time)
+            .build()
+    }
+
+    /// 計算下一次重試前的等待時間
+    ///
+    /// # 參數
+    ///
+    /// - `backoff`: 本次請求的退避計時器
+    /// - `error`: 上一次嘗試的錯誤
+    ///
+    /// # 返回
+    ///
+    /// - `Option<Duration>`: 等待時間；超出總時長時返回 None，表示不再重試
+    pub fn next_delay(&self, backoff: &mut ExponentialBackoff, error: &DomainError) -> Option<Duration> {
+        let delay = backoff.next_backoff()?;
+
+        match error.retry_after() {
+            Some(retry_after) => {
+                let exceeds_budget = self.max_elapsed_time
+                    .is_some_and(|max_elapsed| backoff.get_elapsed_time() + retry_after > max_elapsed);
+                if exceeds_budget {
+                    None
+                } else {
+                    Some(retry_after.max(delay))
+                }
+            },
+            None => Some(delay),
+        }
+    }
+}
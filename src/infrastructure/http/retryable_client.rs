@@ -1,22 +1,18 @@
 use crate::domain::error::DomainError;
-use crate::domain::http::{HttpClient, HttpClientExt, ArcHttpClientExt};
-use crate::infrastructure::http::ReqwestHttpClient;
+use crate::domain::http::HttpClient;
+use crate::infrastructure::http::RetryPolicy;
 use async_trait::async_trait;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
 use reqwest::header::HeaderMap;
 use log::{error, warn, debug};
-use serde::{de::DeserializeOwned, Serialize};
-use std::any::Any;
+use tracing::Instrument;
 
 /// 帶有重試機制的 HTTP 客戶端
 pub struct RetryableHttpClient {
     inner_client: Arc<dyn HttpClient>,
-    // 用於 JSON 操作的內部客戶端
-    inner_reqwest: Arc<ReqwestHttpClient>,
-    max_retries: u32,
-    retry_delay: Duration,
+    policy: RetryPolicy,
 }
 
 impl RetryableHttpClient {
@@ -26,61 +22,113 @@ impl RetryableHttpClient {
     ///
     /// - `inner_client`: 內部 HTTP 客戶端
     /// - `max_retries`: 最大重試次數
-    /// - `retry_delay`: 重試間隔
+    /// - `retry_delay`: 首次重試前的等待時間，之後按指數增長
     pub fn new(inner_client: Arc<dyn HttpClient>, max_retries: u32, retry_delay: Duration) -> Self {
-        // 嘗試轉換內部客戶端
-        let inner_reqwest = match inner_client.clone().downcast_arc::<ReqwestHttpClient>() {
-            Ok(client) => client,
-            Err(_) => {
-                // 創建一個新的 ReqwestHttpClient 作為備選
-                warn!("Unable to downcast HTTP client to ReqwestHttpClient, creating a new instance");
-                Arc::new(ReqwestHttpClient::new())
-            }
-        };
-        
         Self {
             inner_client,
-            inner_reqwest,
-            max_retries,
-            retry_delay,
+            policy: RetryPolicy::new(max_retries, retry_delay),
         }
     }
     
+    /// 設置重試策略
+    ///
+    /// # 參數
+    ///
+    /// - `policy`: 重試策略
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+    
+    /// 獲取重試策略
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+    
     /// 使用重試機制執行操作
+    ///
+    /// 可重試的錯誤按重試策略指數退避後重試，直到超過最大重試次數或總時長；
+    /// 日誌只記錄方法和主機名，錯誤信息中的完整 URL 會被替換
     async fn with_retry<F, Fut, T>(&self, operation_name: &str, f: F) -> Result<T, DomainError>
     where
         F: Fn() -> Fut,
         Fut: std::future::Future<Output = Result<T, DomainError>>,
     {
-        let mut last_error = None;
+        self.execute(operation_name, self.policy.max_retries, f).await
+    }
+    
+    /// 執行操作，最多重試 `max_retries` 次
+    ///
+    /// # 參數
+    ///
+    /// - `operation_name`: 方法和 URL，如 `POST https://...`
+    /// - `max_retries`: 最大重試次數，為 0 時只嘗試一次
+    /// - `f`: 發送請求的操作
+    async fn execute<F, Fut, T>(&self, operation_name: &str, max_retries: u32, f: F) -> Result<T, DomainError>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T, DomainError>>,
+    {
+        let target = RequestTarget::parse(operation_name);
+        let (method, host) = (target.method.as_str(), target.host.as_str());
+        let span = tracing::info_span!(
+            "http.request",
+            http.method = method,
+            server.address = host,
+            http.attempts = tracing::field::Empty,
+            otel.status_code = tracing::field::Empty,
+        );
         
-        for attempt in 0..=self.max_retries {
-            if attempt > 0 {
-                debug!("{} 嘗試 {}/{}", operation_name, attempt, self.max_retries);
-                sleep(self.retry_delay).await;
-            }
+        async {
+            let mut backoff = self.policy.backoff();
+            let mut attempt: u32 = 0;
             
-            match f().await {
-                Ok(result) => {
-                    if attempt > 0 {
-                        debug!("{} 在第 {} 次嘗試後成功", operation_name, attempt + 1);
-                    }
-                    return Ok(result);
-                }
-                Err(e) => {
-                    if self.is_retryable(&e) {
-                        warn!("{} 失敗 (嘗試 {}/{}): {}", operation_name, attempt + 1, self.max_retries + 1, e);
-                        last_error = Some(e);
-                    } else {
-                        // 不可重試的錯誤立即返回
-                        error!("{} 發生不可重試的錯誤: {}", operation_name, e);
-                        return Err(e);
+            loop {
+                attempt += 1;
+                tracing::Span::current().record("http.attempts", attempt);
+                
+                let attempt_span = tracing::info_span!("http.attempt", attempt = attempt, error = tracing::field::Empty);
+                let e = match f().instrument(attempt_span.clone()).await {
+                    Ok(result) => {
+                        if attempt > 1 {
+                            debug!("{} 在第 {} 次嘗試後成功", target, attempt);
+                        }
+                        return Ok(result);
                     }
+                    Err(e) => e,
+                };
+                attempt_span.record("error", target.redact(&e).as_str());
+                
+                if !self.is_retryable(&e) {
+                    // 不可重試的錯誤立即返回
+                    error!(event = "http-failed", error_kind = e.kind();
+                           "{} 發生不可重試的錯誤: {}", target, target.redact(&e));
+                    tracing::Span::current().record("otel.status_code", "ERROR");
+                    return Err(e);
                 }
+                
+                let delay = if attempt > max_retries {
+                    None
+                } else {
+                    self.policy.next_delay(&mut backoff, &e)
+                };
+                let Some(delay) = delay else {
+                    warn!(event = "http-retry-exhausted", error_kind = e.kind();
+                          "{} 失敗，已停止重試 (共嘗試 {} 次): {}", target, attempt, target.redact(&e));
+                    tracing::Span::current().record("otel.status_code", "ERROR");
+                    return Err(e);
+                };
+                
+                warn!(event = "http-retry", error_kind = e.kind();
+                      "{} 失敗 (嘗試 {}/{})，{}ms 後重試: {}",
+                      target, attempt, max_retries + 1, delay.as_millis(), target.redact(&e));
+                sleep(delay)
+                    .instrument(tracing::info_span!("http.retry_delay", delay_ms = delay.as_millis() as u64))
+                    .await;
             }
         }
-        
-        Err(last_error.unwrap_or_else(|| DomainError::LogicError("未知錯誤".to_string())))
+        .instrument(span)
+        .await
     }
     
     /// 判斷錯誤是否可重試
@@ -140,90 +188,64 @@ impl HttpClient for RetryableHttpClient {
             }
         }).await
     }
-
-    /// 實現 as_any 方法
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-}
-
-#[async_trait]
-impl HttpClientExt for RetryableHttpClient {
-    async fn get_json<T: DeserializeOwned + Send + 'static>(&self, url: &str, headers: Option<HeaderMap>) -> Result<T, DomainError> {
-        self.with_retry(&format!("GET JSON {}", url), || {
-            let inner_client = self.inner_reqwest.clone();
-            let url_owned = url.to_string();
-            let headers_owned = headers.clone();
-            
-            async move {
-                inner_client.get_json::<T>(&url_owned, headers_owned).await
-            }
-        }).await
-    }
     
-    async fn post_json<T: DeserializeOwned + Send + 'static, U: Serialize + Send + Sync>(&self, url: &str, body: Option<&U>, headers: Option<HeaderMap>) -> Result<T, DomainError> {
-        // 為 body 生成 JSON 值
-        let body_json = match body {
-            Some(b) => match serde_json::to_value(b) {
-                Ok(json) => Some(json),
-                Err(e) => return Err(DomainError::SerializationError(format!("序列化失敗: {}", e))),
-            },
-            None => None,
-        };
-        
-        self.with_retry(&format!("POST JSON {}", url), || {
-            let inner_client = self.inner_reqwest.clone();
+    async fn post_once(&self, url: &str, body: Option<String>, headers: Option<HeaderMap>) -> Result<String, DomainError> {
+        self.execute(&format!("POST {}", url), 0, || {
+            let inner_client = self.inner_client.clone();
             let url_owned = url.to_string();
+            let body_owned = body.clone();
             let headers_owned = headers.clone();
-            let json_clone = body_json.clone();
             
             async move {
-                match json_clone {
-                    Some(json) => {
-                        inner_client.post_json::<T, serde_json::Value>(&url_owned, Some(&json), headers_owned).await
-                    },
-                    None => inner_client.post_json::<T, U>(&url_owned, None, headers_owned).await,
-                }
+                inner_client.post_once(&url_owned, body_owned, headers_owned).await
             }
         }).await
     }
-    
-    async fn put_json<T: DeserializeOwned + Send + 'static, U: Serialize + Send + Sync>(&self, url: &str, body: Option<&U>, headers: Option<HeaderMap>) -> Result<T, DomainError> {
-        // 為 body 生成 JSON 值
-        let body_json = match body {
-            Some(b) => match serde_json::to_value(b) {
-                Ok(json) => Some(json),
-                Err(e) => return Err(DomainError::SerializationError(format!("序列化失敗: {}", e))),
-            },
-            None => None,
+}
+
+
+/// 日誌和 span 使用的請求目標
+///
+/// 只保留方法和主機名，不記錄完整 URL，避免路徑中的令牌（如 Telegram 機器人令牌、
+/// Slack 和 Discord 的 Webhook 地址）被寫入日誌或導出
+struct RequestTarget {
+    method: String,
+    host: String,
+    /// 原始 URL 及其規範化形式（reqwest 錯誤信息中使用規範化形式）
+    urls: Vec<String>,
+}
+
+impl RequestTarget {
+    /// 從操作名稱（如 `GET JSON https://host/path`）解析
+    fn parse(operation_name: &str) -> Self {
+        let Some((method, url)) = operation_name.rsplit_once(' ') else {
+            return Self { method: operation_name.to_string(), host: String::new(), urls: Vec::new() };
         };
-        
-        self.with_retry(&format!("PUT JSON {}", url), || {
-            let inner_client = self.inner_reqwest.clone();
-            let url_owned = url.to_string();
-            let headers_owned = headers.clone();
-            let json_clone = body_json.clone();
-            
-            async move {
-                match json_clone {
-                    Some(json) => {
-                        inner_client.put_json::<T, serde_json::Value>(&url_owned, Some(&json), headers_owned).await
-                    },
-                    None => inner_client.put_json::<T, U>(&url_owned, None, headers_owned).await,
-                }
+        let parsed = url::Url::parse(url).ok();
+        let host = parsed.as_ref()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+        let mut urls = vec![url.to_string()];
+        if let Some(parsed) = parsed {
+            if parsed.as_str() != url {
+                urls.push(parsed.to_string());
             }
-        }).await
+        }
+        Self { method: method.to_string(), host, urls }
     }
-    
-    async fn delete_json<T: DeserializeOwned + Send + 'static>(&self, url: &str, headers: Option<HeaderMap>) -> Result<T, DomainError> {
-        self.with_retry(&format!("DELETE JSON {}", url), || {
-            let inner_client = self.inner_reqwest.clone();
-            let url_owned = url.to_string();
-            let headers_owned = headers.clone();
-            
-            async move {
-                inner_client.delete_json::<T>(&url_owned, headers_owned).await
-            }
-        }).await
+
+    /// 將錯誤信息中的完整 URL 替換為主機名
+    fn redact(&self, error: &DomainError) -> String {
+        let replacement = format!("<{}>", self.host);
+        // 先替換較長的形式，避免規範化 URL 只被部分替換
+        let mut urls: Vec<&String> = self.urls.iter().collect();
+        urls.sort_by_key(|url| std::cmp::Reverse(url.len()));
+        urls.into_iter().fold(error.to_string(), |message, url| message.replace(url.as_str(), &replacement))
     }
-} 
\ No newline at end of file
+}
+
+impl std::fmt::Display for RequestTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.method, self.host)
+    }
+}
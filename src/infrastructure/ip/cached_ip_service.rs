@@ -0,0 +1,80 @@
+use crate::domain::error::DomainError;
+use crate::domain::ip::IpService;
+use crate::infrastructure::metrics::metrics;
+use async_trait::async_trait;
+use log::debug;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// 帶短期緩存的 IP 查詢服務
+///
+/// 在緩存有效期內重複查詢同一協議族時直接返回上次的結果，並發的查詢合併為一次請求，
+/// 使同一時刻到期的多條記錄只觸發一次外部查詢。查詢失敗的結果不緩存
+pub struct CachedIpService {
+    inner: Arc<dyn IpService>,
+    ttl: Duration,
+    ipv4: Mutex<Option<(Instant, String)>>,
+    ipv6: Mutex<Option<(Instant, String)>>,
+}
+
+impl CachedIpService {
+    /// 默認緩存有效期
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(15);
+
+    /// 創建帶緩存的 IP 查詢服務
+    ///
+    /// # 參數
+    ///
+    /// - `inner`: 實際執行查詢的 IP 服務
+    /// - `ttl`: 緩存有效期
+    pub fn new(inner: Arc<dyn IpService>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            ipv4: Mutex::new(None),
+            ipv6: Mutex::new(None),
+        }
+    }
+
+    /// 返回緩存的結果，過期時重新查詢
+    ///
+    /// 查詢期間持有該協議族的鎖，同時到達的查詢等待並直接使用本次結果
+    async fn cached<F>(
+        &self,
+        slot: &Mutex<Option<(Instant, String)>>,
+        ip_type: &str,
+        detect: F,
+    ) -> Result<String, DomainError>
+    where
+        F: std::future::Future<Output = Result<String, DomainError>>,
+    {
+        let mut cached = slot.lock().await;
+        if let Some((detected_at, ip)) = cached.as_ref() {
+            if detected_at.elapsed() < self.ttl {
+                debug!("使用緩存的 {} 地址: {}", ip_type, ip);
+                metrics().inc_counter(
+                    "ddns_ip_detection_cache_hits_total",
+                    "Number of IP lookups served from the detection cache",
+                    &[("ip_type", ip_type)],
+                );
+                return Ok(ip.clone());
+            }
+        }
+
+        let ip = detect.await?;
+        *cached = Some((Instant::now(), ip.clone()));
+        Ok(ip)
+    }
+}
+
+#[async_trait]
+impl IpService for CachedIpService {
+    async fn get_ipv4(&self) -> Result<String, DomainError> {
+        self.cached(&self.ipv4, "ipv4", self.inner.get_ipv4()).await
+    }
+
+    async fn get_ipv6(&self) -> Result<String, DomainError> {
+        self.cached(&self.ipv6, "ipv6", self.inner.get_ipv6()).await
+    }
+}
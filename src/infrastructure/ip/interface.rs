@@ -0,0 +1,94 @@
+use crate::domain::config::IpType;
+use crate::domain::error::DomainError;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// 獲取網絡接口上指定協議族的所有地址
+///
+/// # 參數
+///
+/// - `name`: 網絡接口名稱
+/// - `ip_type`: 協議族
+///
+/// # 返回
+///
+/// - `Result<Vec<IpAddr>, DomainError>`: 接口不存在時返回錯誤；接口存在但沒有該協議族的地址時返回空列表
+pub fn interface_addresses(name: &str, ip_type: &IpType) -> Result<Vec<IpAddr>, DomainError> {
+    let interfaces = if_addrs::get_if_addrs()
+        .map_err(|e| DomainError::ip_service(format!("無法讀取網絡接口: {}", e)))?;
+
+    let mut found = false;
+    let mut addresses = Vec::new();
+    for interface in interfaces.into_iter().filter(|interface| interface.name == name) {
+        found = true;
+        let address = interface.ip();
+        if matches_family(&address, ip_type) {
+            addresses.push(address);
+        }
+    }
+
+    if !found {
+        return Err(DomainError::ip_service(format!("網絡接口不存在: {}", name)));
+    }
+    Ok(addresses)
+}
+
+/// 選擇用於綁定出站連接的地址
+///
+/// 優先使用公網地址，其次使用私有地址（如光貓後面的路由器 WAN 口）；
+/// 不使用回環地址和需要作用域的 IPv6 鏈路本地地址
+pub fn select_bind_address(addresses: &[IpAddr]) -> Option<IpAddr> {
+    select_public_address(addresses).or_else(|| {
+        addresses.iter()
+            .copied()
+            .find(|address| !address.is_loopback() && !address.is_unspecified() && !is_link_local(address))
+    })
+}
+
+/// 選擇可直接用作記錄內容的公網地址
+pub fn select_public_address(addresses: &[IpAddr]) -> Option<IpAddr> {
+    addresses.iter().copied().find(is_public)
+}
+
+/// 地址是否屬於指定協議族
+fn matches_family(address: &IpAddr, ip_type: &IpType) -> bool {
+    match ip_type {
+        IpType::IPv4 => address.is_ipv4(),
+        IpType::IPv6 => address.is_ipv6(),
+    }
+}
+
+/// 是否為鏈路本地地址
+fn is_link_local(address: &IpAddr) -> bool {
+    match address {
+        IpAddr::V4(v4) => v4.is_link_local(),
+        IpAddr::V6(v6) => (v6.segments()[0] & 0xffc0) == 0xfe80,
+    }
+}
+
+/// 是否為公網地址（排除私有、共享、回環、鏈路本地和唯一本地地址）
+fn is_public(address: &IpAddr) -> bool {
+    match address {
+        IpAddr::V4(v4) => is_public_v4(v4),
+        IpAddr::V6(v6) => is_public_v6(v6),
+    }
+}
+
+fn is_public_v4(address: &Ipv4Addr) -> bool {
+    // 100.64.0.0/10 為運營商級 NAT 共享地址
+    let shared = address.octets()[0] == 100 && (address.octets()[1] & 0xc0) == 64;
+    !(address.is_private()
+        || address.is_loopback()
+        || address.is_link_local()
+        || address.is_unspecified()
+        || address.is_broadcast()
+        || shared)
+}
+
+fn is_public_v6(address: &Ipv6Addr) -> bool {
+    // fc00::/7 為唯一本地地址
+    let unique_local = (address.segments()[0] & 0xfe00) == 0xfc00;
+    !(address.is_loopback()
+        || address.is_unspecified()
+        || is_link_local(&IpAddr::V6(*address))
+        || unique_local)
+}
@@ -1,3 +1,9 @@
+mod cached_ip_service;
+mod interface;
 mod public_ip_service;
+mod source_bound_ip_service;
 
-pub use public_ip_service::PublicIpService; 
\ No newline at end of file
+pub use cached_ip_service::CachedIpService;
+pub use interface::{interface_addresses, select_bind_address, select_public_address};
+pub use public_ip_service::PublicIpService;
+pub use source_bound_ip_service::SourceBoundIpService;
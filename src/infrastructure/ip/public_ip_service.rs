@@ -1,7 +1,7 @@
-use crate::domain::error::DomainError;
+use crate::domain::error::{DomainError, NetworkErrorType};
 use crate::domain::http::HttpClient;
 use crate::domain::ip::IpService;
-use crate::infrastructure::http::ReqwestHttpClient;
+use crate::infrastructure::metrics::observe_latency;
 use async_trait::async_trait;
 use std::sync::Arc;
 use std::net::Ipv6Addr;
@@ -9,7 +9,8 @@ use log::{info, warn, debug};
 
 /// 公共 IP 查詢服務實現
 pub struct PublicIpService {
-    http_client: Arc<ReqwestHttpClient>,
+    ipv4_client: Arc<dyn HttpClient>,
+    ipv6_client: Arc<dyn HttpClient>,
     ipv4_url: String,
     ipv6_urls: Vec<String>,
 }
@@ -22,7 +23,7 @@ impl PublicIpService {
     /// - `http_client`: HTTP 客戶端
     /// - `ipv4_url`: IPv4 查詢服務的 URL（可選，默認為 https://api4.ipify.org）
     /// - `ipv6_url`: IPv6 查詢服務的 URL（可選，默認為 https://api6.ipify.org）
-    pub fn new(http_client: Arc<ReqwestHttpClient>, ipv4_url: Option<String>, ipv6_url: Option<String>) -> Self {
+    pub fn new(http_client: Arc<dyn HttpClient>, ipv4_url: Option<String>, ipv6_url: Option<String>) -> Self {
         // 設置多個 IPv6 檢測服務
         let default_ipv6_urls = vec![
             "https://api6.ipify.org".to_string(),
@@ -36,12 +37,45 @@ impl PublicIpService {
         };
         
         Self {
-            http_client,
+            ipv4_client: http_client.clone(),
+            ipv6_client: http_client,
             ipv4_url: ipv4_url.unwrap_or_else(|| "https://api4.ipify.org".to_string()),
             ipv6_urls,
         }
     }
     
+    /// 為 IPv4 和 IPv6 查詢分別設置 HTTP 客戶端
+    ///
+    /// 用於在雙棧主機上強制 IPv4 查詢只經由 IPv4 連接、IPv6 查詢只經由 IPv6 連接
+    ///
+    /// # 參數
+    ///
+    /// - `ipv4_client`: IPv4 查詢使用的 HTTP 客戶端
+    /// - `ipv6_client`: IPv6 查詢使用的 HTTP 客戶端
+    pub fn with_family_clients(mut self, ipv4_client: Arc<dyn HttpClient>, ipv6_client: Arc<dyn HttpClient>) -> Self {
+        self.ipv4_client = ipv4_client;
+        self.ipv6_client = ipv6_client;
+        self
+    }
+    
+    /// 從查詢服務獲取 IP 並記錄延遲
+    ///
+    /// # 參數
+    ///
+    /// - `url`: 查詢服務 URL
+    /// - `ip_type`: IP 類型標籤（ipv4 或 ipv6）
+    #[tracing::instrument(name = "ip.fetch", skip(self, url), fields(provider = %provider_name(url)), err(Display))]
+    async fn fetch_ip(&self, url: &str, ip_type: &str) -> Result<String, DomainError> {
+        let provider = provider_name(url);
+        let http_client = if ip_type == "ipv6" { &self.ipv6_client } else { &self.ipv4_client };
+        observe_latency(
+            "ddns_ip_detection_duration_seconds",
+            "Latency of public IP detection requests by provider",
+            &[("provider", provider.as_str()), ("ip_type", ip_type)],
+            http_client.get(url, None),
+        ).await
+    }
+    
     /// 驗證 IPv6 地址格式
     fn validate_ipv6(&self, ip: &str) -> Result<String, DomainError> {
         match ip.parse::<Ipv6Addr>() {
@@ -53,13 +87,15 @@ impl PublicIpService {
 
 #[async_trait]
 impl IpService for PublicIpService {
+    #[tracing::instrument(name = "ip.detect", skip_all, fields(ip_type = "ipv4"), err(Display))]
     async fn get_ipv4(&self) -> Result<String, DomainError> {
         debug!("正在獲取 IPv4 地址...");
-        let ip = self.http_client.get(&self.ipv4_url, None).await?;
+        let ip = self.fetch_ip(&self.ipv4_url, "ipv4").await?;
         debug!("獲取到 IPv4 地址: {}", ip);
         Ok(ip)
     }
     
+    #[tracing::instrument(name = "ip.detect", skip_all, fields(ip_type = "ipv6"), err(Display))]
     async fn get_ipv6(&self) -> Result<String, DomainError> {
         debug!("正在獲取 IPv6 地址...");
         
@@ -67,7 +103,7 @@ impl IpService for PublicIpService {
         for (index, url) in self.ipv6_urls.iter().enumerate() {
             debug!("嘗試從 {} 獲取 IPv6 地址", url);
             
-            match self.http_client.get(url, None).await {
+            match self.fetch_ip(url, "ipv6").await {
                 Ok(ip) => {
                     match self.validate_ipv6(&ip) {
                         Ok(validated_ip) => {
@@ -75,13 +111,19 @@ impl IpService for PublicIpService {
                             return Ok(validated_ip);
                         },
                         Err(e) => {
-                            warn!("從 {} 獲取的 IPv6 地址無效: {}", url, e);
+                            warn!(ip_type = "ipv6", event = "ip-detection", error_kind = e.kind();
+                                  "從 {} 獲取的 IPv6 地址無效: {}", url, e);
                             continue;
                         }
                     }
                 },
                 Err(e) => {
-                    warn!("無法從 {} 獲取 IPv6 地址: {}", url, e);
+                    if matches!(e, DomainError::Network(NetworkErrorType::CircuitOpen(_))) {
+                        debug!("{} 的熔斷器已開啟，跳過該服務", url);
+                    } else {
+                        warn!(ip_type = "ipv6", event = "ip-detection", error_kind = e.kind();
+                              "無法從 {} 獲取 IPv6 地址: {}", url, e);
+                    }
                     if index == self.ipv6_urls.len() - 1 {
                         return Err(DomainError::network(format!("所有 IPv6 檢測服務均失敗: {}", e)));
                     }
@@ -91,4 +133,12 @@ impl IpService for PublicIpService {
         
         Err(DomainError::network("無法獲取 IPv6 地址，所有服務均失敗".to_string()))
     }
-} 
\ No newline at end of file
+}
+
+/// 查詢服務的名稱（URL 主機名），用作指標標籤
+fn provider_name(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| url.to_string())
+}
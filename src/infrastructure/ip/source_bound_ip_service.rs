@@ -0,0 +1,121 @@
+use crate::domain::config::{HttpClientSettings, IpType, SourceSettings};
+use crate::domain::error::DomainError;
+use crate::domain::ip::IpService;
+use crate::infrastructure::http::ReqwestHttpClient;
+use super::interface::{interface_addresses, select_bind_address, select_public_address};
+use super::PublicIpService;
+use async_trait::async_trait;
+use log::{debug, info};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+
+/// 綁定檢測來源的 IP 查詢服務
+///
+/// 多出口環境下，每條記錄的 IP 檢測經由配置的網絡接口或本地地址發出，
+/// 或直接讀取接口上的公網地址。接口地址在每次檢測時重新讀取，撥號重連後地址變化時自動重建客戶端
+pub struct SourceBoundIpService {
+    http_settings: HttpClientSettings,
+    source: SourceSettings,
+    ipv4_url: Option<String>,
+    ipv6_url: Option<String>,
+    /// 按本地地址緩存的查詢服務
+    services: Mutex<HashMap<IpAddr, Arc<PublicIpService>>>,
+}
+
+impl SourceBoundIpService {
+    /// 創建綁定檢測來源的 IP 查詢服務
+    ///
+    /// # 參數
+    ///
+    /// - `http_settings`: HTTP 客戶端設置
+    /// - `source`: 檢測來源設置
+    pub fn new(http_settings: HttpClientSettings, source: SourceSettings) -> Self {
+        Self {
+            http_settings,
+            source,
+            ipv4_url: None,
+            ipv6_url: None,
+            services: Mutex::new(HashMap::new()),
+        }
+    }
+    
+    /// 設置 IP 查詢服務的 URL（默認與 `PublicIpService` 相同）
+    ///
+    /// # 參數
+    ///
+    /// - `ipv4_url`: IPv4 查詢服務的 URL
+    /// - `ipv6_url`: IPv6 查詢服務的 URL
+    pub fn with_provider_urls(mut self, ipv4_url: Option<String>, ipv6_url: Option<String>) -> Self {
+        self.ipv4_url = ipv4_url;
+        self.ipv6_url = ipv6_url;
+        self
+    }
+
+    /// 檢測指定協議族的 IP
+    async fn detect(&self, ip_type: IpType) -> Result<String, DomainError> {
+        if self.source.from_interface {
+            let name = self.interface_name()?;
+            let addresses = interface_addresses(name, &ip_type)?;
+            let address = select_public_address(&addresses).ok_or_else(|| {
+                DomainError::ip_service(format!("網絡接口 {} 上沒有 {} 公網地址", name, ip_type))
+            })?;
+            debug!("從網絡接口 {} 讀取到 {} 地址: {}", name, ip_type, address);
+            return Ok(address.to_string());
+        }
+
+        let service = self.service_for(&ip_type)?;
+        match ip_type {
+            IpType::IPv4 => service.get_ipv4().await,
+            IpType::IPv6 => service.get_ipv6().await,
+        }
+    }
+
+    /// 獲取從當前本地地址發出請求的查詢服務
+    fn service_for(&self, ip_type: &IpType) -> Result<Arc<PublicIpService>, DomainError> {
+        let local_address = self.local_address(ip_type)?;
+
+        let mut services = self.services.lock().unwrap();
+        if let Some(service) = services.get(&local_address) {
+            return Ok(service.clone());
+        }
+
+        // 同一協議族的舊地址已失效，移除對應的客戶端
+        services.retain(|address, _| address.is_ipv4() != local_address.is_ipv4());
+        info!("IP 檢測綁定到本地地址 {}", local_address);
+        let http_client = Arc::new(ReqwestHttpClient::bound_to(&self.http_settings, local_address)?);
+        let service = Arc::new(PublicIpService::new(http_client, self.ipv4_url.clone(), self.ipv6_url.clone()));
+        services.insert(local_address, service.clone());
+        Ok(service)
+    }
+
+    /// 解析檢測請求使用的本地地址
+    fn local_address(&self, ip_type: &IpType) -> Result<IpAddr, DomainError> {
+        if let Some(address) = self.source.address {
+            return Ok(address);
+        }
+
+        let name = self.interface_name()?;
+        let addresses = interface_addresses(name, ip_type)?;
+        select_bind_address(&addresses).ok_or_else(|| {
+            DomainError::ip_service(format!("網絡接口 {} 上沒有可用的 {} 地址", name, ip_type))
+        })
+    }
+
+    /// 配置的網絡接口名稱
+    fn interface_name(&self) -> Result<&str, DomainError> {
+        self.source.interface.as_deref()
+            .ok_or_else(|| DomainError::config("未設置檢測來源網絡接口"))
+    }
+}
+
+#[async_trait]
+impl IpService for SourceBoundIpService {
+    async fn get_ipv4(&self) -> Result<String, DomainError> {
+        self.detect(IpType::IPv4).await
+    }
+
+    async fn get_ipv6(&self) -> Result<String, DomainError> {
+        self.detect(IpType::IPv6).await
+    }
+}
@@ -0,0 +1,75 @@
+use crate::domain::config::LogFormat;
+use chrono::{SecondsFormat, Utc};
+use log::kv::{Error as KvError, Key, Value as KvValue, VisitSource};
+use serde_json::{Map, Value};
+use std::io::Write;
+
+/// JSON 日誌中固定輸出的結構化字段
+///
+/// 日誌調用處通過 `log` 的鍵值語法附加，例如
+/// `info!(config_id = id, event = "ip-changed"; "...")`，缺少的字段輸出為 null
+pub const JSON_LOG_FIELDS: [&str; 5] = ["record_name", "config_id", "ip_type", "event", "error_kind"];
+
+/// 初始化日誌系統
+///
+/// 日誌級別由 `RUST_LOG` 控制
+///
+/// # 參數
+///
+/// - `format`: 日誌輸出格式
+pub fn init_logging(format: LogFormat) {
+    let mut builder = env_logger::builder();
+    match format {
+        LogFormat::Text => {
+            builder.format_timestamp_millis();
+        },
+        LogFormat::Json => {
+            builder.format(|buf, record| writeln!(buf, "{}", json_log_line(record)));
+        },
+    }
+    builder.init();
+}
+
+/// 將日誌記錄格式化為單行 JSON
+///
+/// # 參數
+///
+/// - `record`: 日誌記錄
+///
+/// # 返回
+///
+/// - `String`: 包含 timestamp、level、module、message 和固定結構化字段的 JSON 對象
+pub fn json_log_line(record: &log::Record) -> String {
+    let mut line = Map::new();
+    line.insert(
+        "timestamp".to_string(),
+        Value::String(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)),
+    );
+    line.insert("level".to_string(), Value::String(record.level().to_string()));
+    line.insert(
+        "module".to_string(),
+        Value::String(record.module_path().unwrap_or_else(|| record.target()).to_string()),
+    );
+    line.insert("message".to_string(), Value::String(record.args().to_string()));
+    for field in JSON_LOG_FIELDS {
+        line.insert(field.to_string(), Value::Null);
+    }
+
+    let _ = record.key_values().visit(&mut FieldCollector { line: &mut line });
+
+    Value::Object(line).to_string()
+}
+
+/// 收集日誌記錄中的固定結構化字段
+struct FieldCollector<'a> {
+    line: &'a mut Map<String, Value>,
+}
+
+impl<'kvs> VisitSource<'kvs> for FieldCollector<'_> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: KvValue<'kvs>) -> Result<(), KvError> {
+        if let Some(field) = JSON_LOG_FIELDS.iter().find(|field| **field == key.as_str()) {
+            self.line.insert(field.to_string(), Value::String(value.to_string()));
+        }
+        Ok(())
+    }
+}
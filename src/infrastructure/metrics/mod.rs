@@ -0,0 +1,45 @@
+mod registry;
+
+pub use registry::{MetricsRegistry, LATENCY_BUCKETS};
+
+use once_cell::sync::Lazy;
+use std::future::Future;
+use std::time::Instant;
+
+/// 全局指標註冊表
+static METRICS: Lazy<MetricsRegistry> = Lazy::new(MetricsRegistry::new);
+
+/// 獲取全局指標註冊表
+pub fn metrics() -> &'static MetricsRegistry {
+    &METRICS
+}
+
+/// 執行操作並記錄耗時
+///
+/// 耗時記錄到 `name` 直方圖，附加 `outcome` 標籤（`success` 或 `error`）
+///
+/// # 參數
+///
+/// - `name`: 直方圖名稱
+/// - `help`: 指標說明
+/// - `labels`: 標籤
+/// - `future`: 要執行的操作
+pub async fn observe_latency<T, E, F>(
+    name: &'static str,
+    help: &'static str,
+    labels: &[(&str, &str)],
+    future: F,
+) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    let start = Instant::now();
+    let result = future.await;
+    let outcome = if result.is_ok() { "success" } else { "error" };
+
+    let mut labels = labels.to_vec();
+    labels.push(("outcome", outcome));
+    metrics().observe(name, help, &labels, start.elapsed().as_secs_f64());
+
+    result
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::RwLock;
+
+/// 延遲直方圖的默認分桶上限（秒）
+pub const LATENCY_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// 指標類型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetricKind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl MetricKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+            MetricKind::Histogram => "histogram",
+        }
+    }
+}
+
+/// 標籤集合（按傳入順序保存）
+type Labels = Vec<(String, String)>;
+
+/// 單個時間序列的值
+#[derive(Debug, Clone)]
+enum SeriesValue {
+    Value(f64),
+    Histogram { bucket_counts: Vec<u64>, sum: f64, count: u64 },
+}
+
+/// 同名指標的所有時間序列
+#[derive(Debug)]
+struct MetricFamily {
+    help: &'static str,
+    kind: MetricKind,
+    series: BTreeMap<Labels, SeriesValue>,
+}
+
+/// 指標註冊表
+///
+/// 保存計數器、儀表和直方圖，並以 Prometheus 文本格式輸出
+#[derive(Debug, Default)]
+pub struct MetricsRegistry {
+    families: RwLock<BTreeMap<&'static str, MetricFamily>>,
+}
+
+impl MetricsRegistry {
+    /// 創建新的指標註冊表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 計數器加一
+    ///
+    /// # 參數
+    ///
+    /// - `name`: 指標名稱
+    /// - `help`: 指標說明
+    /// - `labels`: 標籤
+    pub fn inc_counter(&self, name: &'static str, help: &'static str, labels: &[(&str, &str)]) {
+        self.add_counter(name, help, labels, 1.0);
+    }
+
+    /// 計數器增加指定值
+    ///
+    /// # 參數
+    ///
+    /// - `name`: 指標名稱
+    /// - `help`: 指標說明
+    /// - `labels`: 標籤
+    /// - `value`: 增加的值
+    pub fn add_counter(&self, name: &'static str, help: &'static str, labels: &[(&str, &str)], value: f64) {
+        self.update(name, help, MetricKind::Counter, labels, |series| match series {
+            Some(SeriesValue::Value(current)) => SeriesValue::Value(current + value),
+            _ => SeriesValue::Value(value),
+        });
+    }
+
+    /// 設置儀表值
+    ///
+    /// # 參數
+    ///
+    /// - `name`: 指標名稱
+    /// - `help`: 指標說明
+    /// - `labels`: 標籤
+    /// - `value`: 當前值
+    pub fn set_gauge(&self, name: &'static str, help: &'static str, labels: &[(&str, &str)], value: f64) {
+        self.update(name, help, MetricKind::Gauge, labels, |_| SeriesValue::Value(value));
+    }
+
+    /// 記錄直方圖觀測值
+    ///
+    /// # 參數
+    ///
+    /// - `name`: 指標名稱
+    /// - `help`: 指標說明
+    /// - `labels`: 標籤
+    /// - `value`: 觀測值（延遲以秒為單位）
+    pub fn observe(&self, name: &'static str, help: &'static str, labels: &[(&str, &str)], value: f64) {
+        self.update(name, help, MetricKind::Histogram, labels, |series| {
+            let (mut bucket_counts, sum, count) = match series {
+                Some(SeriesValue::Histogram { bucket_counts, sum, count }) => (bucket_counts.clone(), *sum, *count),
+                _ => (vec![0; LATENCY_BUCKETS.len()], 0.0, 0),
+            };
+            for (bucket, upper_bound) in bucket_counts.iter_mut().zip(LATENCY_BUCKETS) {
+                if value <= upper_bound {
+                    *bucket += 1;
+                }
+            }
+            SeriesValue::Histogram { bucket_counts, sum: sum + value, count: count + 1 }
+        });
+    }
+
+    /// 更新時間序列
+    fn update(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        kind: MetricKind,
+        labels: &[(&str, &str)],
+        f: impl FnOnce(Option<&SeriesValue>) -> SeriesValue,
+    ) {
+        let labels: Labels = labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let mut families = self.families.write().unwrap();
+        let family = families.entry(name).or_insert_with(|| MetricFamily {
+            help,
+            kind,
+            series: BTreeMap::new(),
+        });
+        if family.kind != kind {
+            log::warn!("指標 {} 的類型不一致，忽略本次更新", name);
+            return;
+        }
+        let value = f(family.series.get(&labels));
+        family.series.insert(labels, value);
+    }
+
+    /// 以 Prometheus 文本格式輸出所有指標
+    pub fn render(&self) -> String {
+        let families = self.families.read().unwrap();
+        let mut output = String::new();
+
+        for (name, family) in families.iter() {
+            let _ = writeln!(output, "# HELP {} {}", name, family.help);
+            let _ = writeln!(output, "# TYPE {} {}", name, family.kind.as_str());
+
+            for (labels, value) in &family.series {
+                match value {
+                    SeriesValue::Value(value) => {
+                        let _ = writeln!(output, "{}{} {}", name, format_labels(labels, None), format_value(*value));
+                    },
+                    SeriesValue::Histogram { bucket_counts, sum, count } => {
+                        for (bucket, upper_bound) in bucket_counts.iter().zip(LATENCY_BUCKETS) {
+                            let le = format_value(upper_bound);
+                            let _ = writeln!(output, "{}_bucket{} {}", name, format_labels(labels, Some(&le)), bucket);
+                        }
+                        let _ = writeln!(output, "{}_bucket{} {}", name, format_labels(labels, Some("+Inf")), count);
+                        let _ = writeln!(output, "{}_sum{} {}", name, format_labels(labels, None), format_value(*sum));
+                        let _ = writeln!(output, "{}_count{} {}", name, format_labels(labels, None), count);
+                    },
+                }
+            }
+        }
+
+        output
+    }
+}
+
+/// 格式化標籤，直方圖分桶額外附加 `le` 標籤
+fn format_labels(labels: &Labels, le: Option<&str>) -> String {
+    let mut parts: Vec<String> = labels.iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+        .collect();
+    if let Some(le) = le {
+        parts.push(format!("le=\"{}\"", le));
+    }
+
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", parts.join(","))
+    }
+}
+
+/// 轉義標籤值中的反斜線、雙引號和換行
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// 格式化數值，整數不輸出小數部分
+fn format_value(value: f64) -> String {
+    if value.is_finite() && value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        format!("{}", value)
+    }
+}
@@ -1,6 +1,12 @@
 pub mod http;
+pub mod logging;
 pub mod dns;
 pub mod ip;
 pub mod config;
 pub mod api;
-pub mod state; 
\ No newline at end of file
+pub mod state;
+pub mod notification;
+pub mod hook;
+pub mod failover;
+pub mod metrics;
+pub mod telemetry;
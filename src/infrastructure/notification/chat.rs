@@ -0,0 +1,195 @@
+use crate::domain::config::{ChatWebhookConfig, TelegramConfig};
+use crate::domain::error::DomainError;
+use crate::domain::http::HttpClient;
+use crate::domain::notification::{Notification, Notifier};
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// 失敗類事件的提示顏色（紅色）
+const COLOR_ALERT: u32 = 0xE74C3C;
+/// 其他事件的提示顏色（綠色）
+const COLOR_INFO: u32 = 0x2ECC71;
+
+/// 是否為需要提醒的失敗類事件
+fn is_alert(notification: &Notification) -> bool {
+    matches!(notification.event.as_str(), "update-failed" | "drift-detected")
+}
+
+/// 以 JSON 格式 POST 消息
+///
+/// Webhook 地址和 Telegram 機器人令牌都是密鑰，連接錯誤的信息中包含 URL，返回前替換為主機名，
+/// 避免密鑰出現在日誌中
+async fn post_json(http_client: &Arc<dyn HttpClient>, url: &str, body: &Value) -> Result<(), DomainError> {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    http_client.post(url, Some(body.to_string()), Some(headers)).await.map_err(|e| redact_url(e, url))?;
+    Ok(())
+}
+
+/// 將錯誤信息中的 URL 替換為主機名
+fn redact_url(error: DomainError, url: &str) -> DomainError {
+    let message = error.to_string();
+    let parsed = url::Url::parse(url).ok();
+    let normalized = parsed.as_ref().map(|parsed| parsed.to_string());
+    let contains_url = message.contains(url) || normalized.as_deref().is_some_and(|normalized| message.contains(normalized));
+    match error {
+        DomainError::Network(_) if contains_url => {
+            let host = format!("<{}>", parsed.as_ref().and_then(|parsed| parsed.host_str()).unwrap_or_default());
+            let message = match &normalized {
+                Some(normalized) if normalized.len() > url.len() => message.replace(normalized.as_str(), &host).replace(url, &host),
+                Some(normalized) => message.replace(url, &host).replace(normalized.as_str(), &host),
+                None => message.replace(url, &host),
+            };
+            DomainError::network(message)
+        },
+        error => error,
+    }
+}
+
+/// Slack 通知目標（Incoming Webhook）
+pub struct SlackNotifier {
+    name: String,
+    config: ChatWebhookConfig,
+    http_client: Arc<dyn HttpClient>,
+}
+
+impl SlackNotifier {
+    /// 創建新的 Slack 通知目標
+    ///
+    /// # 參數
+    ///
+    /// - `config`: Slack Webhook 配置
+    /// - `http_client`: HTTP 客戶端
+    pub fn new(config: ChatWebhookConfig, http_client: Arc<dyn HttpClient>) -> Self {
+        Self {
+            name: format!("slack:{}", config.display_name()),
+            config,
+            http_client,
+        }
+    }
+
+    /// 構建 Slack 消息
+    pub fn build_message(notification: &Notification) -> Value {
+        let icon = if is_alert(notification) { ":warning:" } else { ":white_check_mark:" };
+        json!({
+            "text": format!("{} *{}*\n{}", icon, notification.title, notification.message),
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn events(&self) -> &[String] {
+        &self.config.events
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), DomainError> {
+        post_json(&self.http_client, &self.config.webhook_url, &Self::build_message(notification)).await
+    }
+}
+
+/// Discord 通知目標（頻道 Webhook）
+pub struct DiscordNotifier {
+    name: String,
+    config: ChatWebhookConfig,
+    http_client: Arc<dyn HttpClient>,
+}
+
+impl DiscordNotifier {
+    /// 創建新的 Discord 通知目標
+    ///
+    /// # 參數
+    ///
+    /// - `config`: Discord Webhook 配置
+    /// - `http_client`: HTTP 客戶端
+    pub fn new(config: ChatWebhookConfig, http_client: Arc<dyn HttpClient>) -> Self {
+        Self {
+            name: format!("discord:{}", config.display_name()),
+            config,
+            http_client,
+        }
+    }
+
+    /// 構建 Discord 消息
+    pub fn build_message(notification: &Notification) -> Value {
+        json!({
+            "username": "Cloudflare DDNS",
+            "embeds": [{
+                "title": notification.title,
+                "description": notification.message,
+                "color": if is_alert(notification) { COLOR_ALERT } else { COLOR_INFO },
+            }],
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn events(&self) -> &[String] {
+        &self.config.events
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), DomainError> {
+        post_json(&self.http_client, &self.config.webhook_url, &Self::build_message(notification)).await
+    }
+}
+
+/// Telegram 通知目標（Bot API sendMessage）
+pub struct TelegramNotifier {
+    name: String,
+    config: TelegramConfig,
+    http_client: Arc<dyn HttpClient>,
+}
+
+impl TelegramNotifier {
+    /// 創建新的 Telegram 通知目標
+    ///
+    /// # 參數
+    ///
+    /// - `config`: Telegram 配置
+    /// - `http_client`: HTTP 客戶端
+    pub fn new(config: TelegramConfig, http_client: Arc<dyn HttpClient>) -> Self {
+        Self {
+            name: format!("telegram:{}", config.display_name()),
+            config,
+            http_client,
+        }
+    }
+
+    /// 構建 Telegram 消息
+    ///
+    /// 使用純文本，避免記錄名稱中的特殊字符破壞 Markdown 解析
+    pub fn build_message(chat_id: &str, notification: &Notification) -> Value {
+        json!({
+            "chat_id": chat_id,
+            "text": format!("{}\n{}", notification.title, notification.message),
+            "disable_web_page_preview": true,
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn events(&self) -> &[String] {
+        &self.config.events
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), DomainError> {
+        let url = format!("{}/bot{}/sendMessage", self.config.api_url(), self.config.bot_token);
+        post_json(&self.http_client, &url, &Self::build_message(&self.config.chat_id, notification)).await
+    }
+}
@@ -0,0 +1,92 @@
+use crate::domain::config::{EmailConfig, SmtpSecurity};
+use crate::domain::error::DomainError;
+use crate::domain::notification::{Notification, Notifier};
+use async_trait::async_trait;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::time::Duration;
+
+/// SMTP 連接超時
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// SMTP 郵件通知目標
+pub struct EmailNotifier {
+    name: String,
+    config: EmailConfig,
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl EmailNotifier {
+    /// 創建新的郵件通知目標
+    ///
+    /// # 參數
+    ///
+    /// - `config`: 郵件配置
+    ///
+    /// # 返回
+    ///
+    /// - `Result<Self, DomainError>`: SMTP 地址無效時返回錯誤
+    pub fn new(config: EmailConfig) -> Result<Self, DomainError> {
+        let builder = match config.security {
+            SmtpSecurity::Starttls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host),
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_host),
+            SmtpSecurity::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.smtp_host)),
+        }
+        .map_err(|e| DomainError::config(format!("Invalid SMTP host {}: {}", config.smtp_host, e)))?;
+
+        let mut builder = builder.port(config.port()).timeout(Some(SMTP_TIMEOUT));
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self {
+            name: format!("email:{}", config.display_name()),
+            transport: builder.build(),
+            config,
+        })
+    }
+
+    /// 構建郵件
+    ///
+    /// # 參數
+    ///
+    /// - `config`: 郵件配置
+    /// - `notification`: 通知內容
+    pub fn build_message(config: &EmailConfig, notification: &Notification) -> Result<Message, DomainError> {
+        let mut builder = Message::builder()
+            .from(parse_mailbox(&config.from)?)
+            .subject(format!("[Cloudflare DDNS] {}", notification.title))
+            .header(ContentType::TEXT_PLAIN);
+        for to in &config.to {
+            builder = builder.to(parse_mailbox(to)?);
+        }
+        builder.body(notification.message.clone())
+            .map_err(|e| DomainError::validation(format!("Failed to build email: {}", e)))
+    }
+}
+
+/// 解析郵件地址
+fn parse_mailbox(address: &str) -> Result<Mailbox, DomainError> {
+    address.parse()
+        .map_err(|e| DomainError::validation(format!("Invalid email address {}: {}", address, e)))
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn events(&self) -> &[String] {
+        &self.config.events
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), DomainError> {
+        let message = Self::build_message(&self.config, notification)?;
+        self.transport.send(message).await
+            .map_err(|e| DomainError::network(format!("Failed to send email via {}: {}", self.config.smtp_host, e)))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+mod chat;
+mod email;
+mod template;
+mod webhook;
+
+pub use chat::{DiscordNotifier, SlackNotifier, TelegramNotifier};
+pub use email::EmailNotifier;
+pub use template::{render_template, template_variables};
+pub use webhook::{WebhookNotifier, WebhookSender, EVENT_HEADER, SIGNATURE_HEADER};
+
+use crate::domain::config::NotificationSettings;
+use crate::domain::http::HttpClient;
+use crate::domain::notification::Notifier;
+use log::warn;
+use std::sync::Arc;
+
+/// 根據通知設置創建所有通知目標
+///
+/// 配置無效的目標會被跳過並記錄警告
+///
+/// # 參數
+///
+/// - `settings`: 通知設置
+/// - `http_client`: 基於 HTTP 的通知目標使用的客戶端
+pub fn build_notifiers(settings: &NotificationSettings, http_client: Arc<dyn HttpClient>) -> Vec<Arc<dyn Notifier>> {
+    let mut notifiers: Vec<Arc<dyn Notifier>> = Vec::new();
+
+    for config in &settings.webhooks {
+        match config.validate() {
+            Ok(_) => notifiers.push(Arc::new(WebhookNotifier::new(config.clone(), http_client.clone()))),
+            Err(e) => warn!("跳過無效的 Webhook {}: {}", config.display_name(), e),
+        }
+    }
+    for config in &settings.slack {
+        match config.validate() {
+            Ok(_) => notifiers.push(Arc::new(SlackNotifier::new(config.clone(), http_client.clone()))),
+            Err(e) => warn!("跳過無效的 Slack 通知 {}: {}", config.display_name(), e),
+        }
+    }
+    for config in &settings.discord {
+        match config.validate() {
+            Ok(_) => notifiers.push(Arc::new(DiscordNotifier::new(config.clone(), http_client.clone()))),
+            Err(e) => warn!("跳過無效的 Discord 通知 {}: {}", config.display_name(), e),
+        }
+    }
+    for config in &settings.telegram {
+        match config.validate() {
+            Ok(_) => notifiers.push(Arc::new(TelegramNotifier::new(config.clone(), http_client.clone()))),
+            Err(e) => warn!("跳過無效的 Telegram 通知 {}: {}", config.display_name(), e),
+        }
+    }
+    for config in &settings.email {
+        match config.validate().and_then(|_| EmailNotifier::new(config.clone())) {
+            Ok(notifier) => notifiers.push(Arc::new(notifier)),
+            Err(e) => warn!("跳過無效的郵件通知 {}: {}", config.display_name(), e),
+        }
+    }
+
+    notifiers
+}
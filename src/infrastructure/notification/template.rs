@@ -0,0 +1,85 @@
+use serde_json::{Map, Value};
+
+/// 將事件內容展開為模板變量
+///
+/// 頂層字段直接作為變量，嵌套對象（如 `record`）的字段同時以自身名稱和
+/// `父字段.子字段` 兩種形式提供
+///
+/// # 參數
+///
+/// - `event_name`: 事件名稱，以 `event` 變量提供
+/// - `payload`: 事件內容
+pub fn template_variables(event_name: &str, payload: &Value) -> Map<String, Value> {
+    let mut variables = Map::new();
+    variables.insert("event".to_string(), Value::String(event_name.to_string()));
+
+    if let Value::Object(fields) = payload {
+        for (key, value) in fields {
+            if let Value::Object(nested) = value {
+                for (nested_key, nested_value) in nested {
+                    variables.insert(format!("{}.{}", key, nested_key), nested_value.clone());
+                    variables.entry(nested_key.clone()).or_insert_with(|| nested_value.clone());
+                }
+            }
+            variables.insert(key.clone(), value.clone());
+        }
+    }
+
+    variables
+}
+
+/// 渲染 JSON 模板
+///
+/// 字符串中的 `{{變量}}` 會被替換為變量值；整個字符串只有一個佔位符時，
+/// 保留變量原始的 JSON 類型（例如 null 或數字）。未知變量替換為空字符串
+///
+/// # 參數
+///
+/// - `template`: JSON 模板
+/// - `variables`: 模板變量
+pub fn render_template(template: &Value, variables: &Map<String, Value>) -> Value {
+    match template {
+        Value::String(text) => render_string(text, variables),
+        Value::Array(items) => Value::Array(items.iter().map(|item| render_template(item, variables)).collect()),
+        Value::Object(fields) => Value::Object(
+            fields.iter()
+                .map(|(key, value)| (key.clone(), render_template(value, variables)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// 渲染單個字符串
+fn render_string(text: &str, variables: &Map<String, Value>) -> Value {
+    // 整個字符串就是一個佔位符時保留原始類型
+    if let Some(name) = text.trim().strip_prefix("{{").and_then(|rest| rest.strip_suffix("}}")) {
+        if !name.contains("{{") && !name.contains("}}") {
+            return variables.get(name.trim()).cloned().unwrap_or(Value::Null);
+        }
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        let name = rest[start + 2..start + end].trim();
+        output.push_str(&variables.get(name).map(value_to_text).unwrap_or_default());
+        rest = &rest[start + end + 2..];
+    }
+    output.push_str(rest);
+
+    Value::String(output)
+}
+
+/// 將變量值轉換為插入字符串時使用的文本
+fn value_to_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,157 @@
+use crate::domain::config::WebhookConfig;
+use crate::domain::error::DomainError;
+use crate::domain::http::HttpClient;
+use crate::domain::notification::{Notification, Notifier};
+use crate::infrastructure::notification::template::{render_template, template_variables};
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use log::debug;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::sync::Arc;
+
+/// 事件名稱請求頭
+pub const EVENT_HEADER: &str = "X-DDNS-Event";
+/// 簽名請求頭，值為 `sha256=<十六進制 HMAC>`
+pub const SIGNATURE_HEADER: &str = "X-DDNS-Signature";
+
+/// Webhook 發送器
+///
+/// 將事件渲染為 JSON 請求體並 POST 到配置的 URL，重試由傳入的 HTTP 客戶端負責
+pub struct WebhookSender {
+    http_client: Arc<dyn HttpClient>,
+}
+
+impl WebhookSender {
+    /// 創建新的 Webhook 發送器
+    ///
+    /// # 參數
+    ///
+    /// - `http_client`: HTTP 客戶端（通常為帶重試的客戶端）
+    pub fn new(http_client: Arc<dyn HttpClient>) -> Self {
+        Self { http_client }
+    }
+
+    /// 構建請求體
+    ///
+    /// 配置了模板時按模板渲染，否則輸出 `{"event", "timestamp", "data"}`
+    ///
+    /// # 參數
+    ///
+    /// - `webhook`: Webhook 配置
+    /// - `event_name`: 事件名稱
+    /// - `payload`: 事件內容
+    pub fn build_body(webhook: &WebhookConfig, event_name: &str, payload: &Value) -> Value {
+        match &webhook.template {
+            Some(template) => render_template(template, &template_variables(event_name, payload)),
+            None => json!({
+                "event": event_name,
+                "timestamp": Utc::now().to_rfc3339(),
+                "data": payload,
+            }),
+        }
+    }
+
+    /// 計算請求體的 HMAC-SHA256 簽名
+    ///
+    /// # 參數
+    ///
+    /// - `secret`: 簽名密鑰
+    /// - `body`: 請求體
+    ///
+    /// # 返回
+    ///
+    /// - `String`: `sha256=<十六進制簽名>`
+    pub fn sign(secret: &str, body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .expect("HMAC 可以接受任意長度的密鑰");
+        mac.update(body.as_bytes());
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    /// 發送事件到 Webhook
+    ///
+    /// # 參數
+    ///
+    /// - `webhook`: Webhook 配置
+    /// - `event_name`: 事件名稱
+    /// - `payload`: 事件內容
+    ///
+    /// # 返回
+    ///
+    /// - `Result<(), DomainError>`: 發送結果
+    pub async fn send(&self, webhook: &WebhookConfig, event_name: &str, payload: &Value) -> Result<(), DomainError> {
+        let body = Self::build_body(webhook, event_name, payload).to_string();
+        let headers = Self::build_headers(webhook, event_name, &body)?;
+
+        debug!("發送 Webhook {} 事件: {}", webhook.display_name(), event_name);
+        self.http_client.post(&webhook.url, Some(body), Some(headers)).await?;
+        Ok(())
+    }
+
+    /// 構建請求頭
+    fn build_headers(webhook: &WebhookConfig, event_name: &str, body: &str) -> Result<HeaderMap, DomainError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        for (name, value) in &webhook.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| DomainError::validation(format!("Invalid webhook header name {}: {}", name, e)))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| DomainError::validation(format!("Invalid webhook header value for {}: {}", name, e)))?;
+            headers.insert(name, value);
+        }
+
+        headers.insert(EVENT_HEADER, Self::header_value(event_name)?);
+        if let Some(secret) = &webhook.secret {
+            headers.insert(SIGNATURE_HEADER, Self::header_value(&Self::sign(secret, body))?);
+        }
+
+        Ok(headers)
+    }
+
+    fn header_value(value: &str) -> Result<HeaderValue, DomainError> {
+        HeaderValue::from_str(value)
+            .map_err(|e| DomainError::validation(format!("Invalid header value: {}", e)))
+    }
+}
+
+/// Webhook 通知目標
+pub struct WebhookNotifier {
+    name: String,
+    config: WebhookConfig,
+    sender: WebhookSender,
+}
+
+impl WebhookNotifier {
+    /// 創建新的 Webhook 通知目標
+    ///
+    /// # 參數
+    ///
+    /// - `config`: Webhook 配置
+    /// - `http_client`: HTTP 客戶端
+    pub fn new(config: WebhookConfig, http_client: Arc<dyn HttpClient>) -> Self {
+        Self {
+            name: format!("webhook:{}", config.display_name()),
+            config,
+            sender: WebhookSender::new(http_client),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn events(&self) -> &[String] {
+        &self.config.events
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), DomainError> {
+        self.sender.send(&self.config, &notification.event, &notification.payload).await
+    }
+}
@@ -1,4 +1,5 @@
 use crate::domain::error::DomainError;
+use crate::domain::config::ConfigId;
 use crate::domain::state::{StateRepository, StateEntry};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -7,7 +8,7 @@ use std::sync::{Arc, RwLock};
 
 /// 內存狀態存儲庫
 pub struct InMemoryStateRepository {
-    state: Arc<RwLock<HashMap<String, StateEntry>>>,
+    state: Arc<RwLock<HashMap<ConfigId, StateEntry>>>,
 }
 
 impl Default for InMemoryStateRepository {
@@ -27,7 +28,7 @@ impl InMemoryStateRepository {
 
 #[async_trait]
 impl StateRepository for InMemoryStateRepository {
-    async fn get_last_ip(&self, config_id: &str) -> Result<Option<String>, DomainError> {
+    async fn get_last_ip(&self, config_id: &ConfigId) -> Result<Option<String>, DomainError> {
         match self.state.read() {
             Ok(state) => {
                 if let Some(entry) = state.get(config_id) {
@@ -40,14 +41,11 @@ impl StateRepository for InMemoryStateRepository {
         }
     }
     
-    async fn set_last_ip(&self, config_id: &str, ip: &str) -> Result<(), DomainError> {
+    async fn set_last_ip(&self, config_id: &ConfigId, ip: &str) -> Result<(), DomainError> {
         match self.state.write() {
             Ok(mut state) => {
-                let entry = state.entry(config_id.to_string())
-                    .or_insert(StateEntry {
-                        last_ip: None,
-                        last_update_time: None,
-                    });
+                let entry = state.entry(config_id.clone())
+                    .or_default();
                 
                 entry.last_ip = Some(ip.to_string());
                 Ok(())
@@ -56,7 +54,7 @@ impl StateRepository for InMemoryStateRepository {
         }
     }
     
-    async fn get_last_update_time(&self, config_id: &str) -> Result<Option<DateTime<Utc>>, DomainError> {
+    async fn get_last_update_time(&self, config_id: &ConfigId) -> Result<Option<DateTime<Utc>>, DomainError> {
         match self.state.read() {
             Ok(state) => {
                 if let Some(entry) = state.get(config_id) {
@@ -69,14 +67,11 @@ impl StateRepository for InMemoryStateRepository {
         }
     }
     
-    async fn set_last_update_time(&self, config_id: &str, time: DateTime<Utc>) -> Result<(), DomainError> {
+    async fn set_last_update_time(&self, config_id: &ConfigId, time: DateTime<Utc>) -> Result<(), DomainError> {
         match self.state.write() {
             Ok(mut state) => {
-                let entry = state.entry(config_id.to_string())
-                    .or_insert(StateEntry {
-                        last_ip: None,
-                        last_update_time: None,
-                    });
+                let entry = state.entry(config_id.clone())
+                    .or_default();
                 
                 entry.last_update_time = Some(time);
                 Ok(())
@@ -85,7 +80,25 @@ impl StateRepository for InMemoryStateRepository {
         }
     }
     
-    async fn get_state(&self, config_id: &str) -> Result<Option<StateEntry>, DomainError> {
+    async fn set_check_result(&self, config_id: &ConfigId, time: DateTime<Utc>, error: Option<String>) -> Result<(), DomainError> {
+        match self.state.write() {
+            Ok(mut state) => {
+                let entry = state.entry(config_id.clone()).or_default();
+                
+                entry.last_check_time = Some(time);
+                if error.is_some() {
+                    entry.last_error_time = Some(time);
+                } else {
+                    entry.last_success_time = Some(time);
+                }
+                entry.last_error = error;
+                Ok(())
+            },
+            Err(_) => Err(DomainError::LogicError("Failed to write state".to_string())),
+        }
+    }
+    
+    async fn get_state(&self, config_id: &ConfigId) -> Result<Option<StateEntry>, DomainError> {
         match self.state.read() {
             Ok(state) => {
                 if let Some(entry) = state.get(config_id) {
@@ -98,10 +111,10 @@ impl StateRepository for InMemoryStateRepository {
         }
     }
     
-    async fn set_state(&self, config_id: &str, state_entry: StateEntry) -> Result<(), DomainError> {
+    async fn set_state(&self, config_id: &ConfigId, state_entry: StateEntry) -> Result<(), DomainError> {
         match self.state.write() {
             Ok(mut state) => {
-                state.insert(config_id.to_string(), state_entry);
+                state.insert(config_id.clone(), state_entry);
                 Ok(())
             },
             Err(_) => Err(DomainError::LogicError("Failed to write state".to_string())),
@@ -0,0 +1,92 @@
+use crate::domain::config::{TelemetrySettings, TraceExporter};
+use crate::domain::error::DomainError;
+use log::{info, warn};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use tracing::Level;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::SubscriberExt;
+
+/// 本程序的追蹤目標前綴，只導出本程序創建的 span
+const TRACE_TARGET: &str = "cloudflare_ddns";
+
+/// OTLP/HTTP 追蹤數據路徑
+const OTLP_TRACES_PATH: &str = "/v1/traces";
+
+/// 追蹤系統句柄
+///
+/// 丟棄時刷新並關閉導出器，應在程序退出前一直持有
+pub struct TracingGuard {
+    provider: SdkTracerProvider,
+}
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            warn!("關閉追蹤導出器失敗: {}", e);
+        }
+    }
+}
+
+/// 初始化追蹤系統
+///
+/// # 參數
+///
+/// - `settings`: 追蹤設置
+///
+/// # 返回
+///
+/// - `Result<Option<TracingGuard>, DomainError>`: 未啟用時返回 None，成功時返回追蹤系統句柄，失敗時返回錯誤
+pub fn init_tracing(settings: &TelemetrySettings) -> Result<Option<TracingGuard>, DomainError> {
+    let provider = match settings.exporter {
+        TraceExporter::None => return Ok(None),
+        TraceExporter::Otlp => {
+            let mut builder = opentelemetry_otlp::SpanExporter::builder().with_http();
+            if let Some(endpoint) = &settings.otlp_endpoint {
+                builder = builder.with_endpoint(otlp_traces_endpoint(endpoint));
+            }
+            let exporter = builder
+                .build()
+                .map_err(|e| DomainError::config(format!("無法創建 OTLP 導出器: {}", e)))?;
+            tracer_provider_builder(settings).with_batch_exporter(exporter).build()
+        },
+        TraceExporter::Stdout => tracer_provider_builder(settings)
+            .with_simple_exporter(opentelemetry_stdout::SpanExporter::default())
+            .build(),
+    };
+
+    let tracer = provider.tracer(TelemetrySettings::DEFAULT_SERVICE_NAME);
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .with(Targets::new().with_target(TRACE_TARGET, Level::TRACE));
+
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(|e| DomainError::config(format!("無法設置追蹤訂閱者: {}", e)))?;
+
+    info!("已啟用追蹤導出: {}", settings.exporter);
+    Ok(Some(TracingGuard { provider }))
+}
+
+/// 創建帶服務名稱的追蹤提供者構建器
+fn tracer_provider_builder(settings: &TelemetrySettings) -> opentelemetry_sdk::trace::TracerProviderBuilder {
+    SdkTracerProvider::builder()
+        .with_resource(Resource::builder().with_service_name(settings.service_name.clone()).build())
+}
+
+/// 計算 OTLP/HTTP 追蹤數據的完整地址
+///
+/// 與 `OTEL_EXPORTER_OTLP_ENDPOINT` 的語義一致，收集器地址後追加 `/v1/traces`
+///
+/// # 參數
+///
+/// - `endpoint`: 收集器地址
+pub fn otlp_traces_endpoint(endpoint: &str) -> String {
+    let endpoint = endpoint.trim().trim_end_matches('/');
+    if endpoint.ends_with(OTLP_TRACES_PATH) {
+        endpoint.to_string()
+    } else {
+        format!("{}{}", endpoint, OTLP_TRACES_PATH)
+    }
+}
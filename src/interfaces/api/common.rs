@@ -215,6 +215,18 @@ pub fn handle_domain_error(err: DomainError, context: &str) -> HttpResponse {
             "error": "serialization_error",
             "message": format!("{}", err)
         })),
+        DomainError::NotFound(_) => HttpResponse::NotFound().json(json!({
+            "error": "not_found",
+            "message": format!("{}", err)
+        })),
+        DomainError::Conflict(_) => HttpResponse::PreconditionFailed().json(json!({
+            "error": "precondition_failed",
+            "message": format!("{}", err)
+        })),
+        DomainError::Hook(_) => HttpResponse::InternalServerError().json(json!({
+            "error": "hook_error",
+            "message": format!("{}", err)
+        })),
         DomainError::Context(_, _) => HttpResponse::InternalServerError().json(json!({
             "error": "context_error",
             "message": format!("{}", err)
@@ -1,8 +1,10 @@
-use actix_web::{web, get, post, HttpResponse, Responder};
+use actix_web::{web, get, post, put, patch, delete, HttpRequest, HttpResponse, Responder};
+use actix_web::http::header::{ETAG, IF_MATCH};
 use serde::{Deserialize, Serialize};
-use crate::domain::config::DdnsConfig;
+use crate::domain::config::{ConfigId, DdnsConfig};
 use crate::application::ServiceFactory;
 use crate::application::error::ApplicationError;
+use crate::domain::error::DomainError;
 use log::{info, error};
 use std::sync::Arc;
 use super::common::handle_application_error;
@@ -29,6 +31,14 @@ struct ConfigResponse {
     configs: Option<Vec<EnhancedDdnsConfig>>,
 }
 
+/// 單個配置響應
+#[derive(Serialize)]
+struct SingleConfigResponse {
+    success: bool,
+    message: String,
+    config: Option<EnhancedDdnsConfig>,
+}
+
 /// 配置保存請求
 #[derive(Deserialize)]
 pub struct SaveConfigRequest {
@@ -68,37 +78,18 @@ pub async fn get_configs(
             let mut enhanced_configs = Vec::new();
             
             for config in configs {
-                let config_id = format!("{}-{}", config.zone_id, config.record_id);
-                let mut enhanced = EnhancedDdnsConfig {
-                    config,
-                    current_ip: None,
-                    last_update_time: None,
-                    status: Some("ok".to_string()),
-                };
-                
-                // 尋找對應的DDNS服務獲取更多信息
-                if let Some(service) = service_factory.find_ddns_service(&config_id).await {
-                    let service_guard = service.lock().await;
-                    
-                    // 獲取當前IP
-                    if let Ok(ip) = service_guard.get_last_or_current_ip(&config_id).await {
-                        enhanced.current_ip = Some(ip);
-                    }
-                    
-                    // 獲取最後更新時間
-                    if let Ok(Some(time)) = service_guard.get_last_update_for_api(&config_id).await {
-                        enhanced.last_update_time = Some(format_datetime(time));
-                    }
-                }
-                
-                enhanced_configs.push(enhanced);
+                enhanced_configs.push(enhance_config(&service_factory, config, true).await);
             }
             
-            HttpResponse::Ok().json(ConfigResponse {
-                success: true,
-                message: format!("成功獲取 {} 個配置", enhanced_configs.len()),
-                configs: Some(enhanced_configs),
-            })
+            let revision = config_service.get_configs_revision().await;
+            
+            HttpResponse::Ok()
+                .insert_header((ETAG, format_etag(&revision)))
+                .json(ConfigResponse {
+                    success: true,
+                    message: format!("成功獲取 {} 個配置", enhanced_configs.len()),
+                    configs: Some(enhanced_configs),
+                })
         },
         Err(e) => {
             let app_error: ApplicationError = ApplicationError::DomainError(e);
@@ -123,44 +114,35 @@ pub async fn get_configs(
 #[post("")]
 pub async fn save_configs(
     service_factory: web::Data<Arc<ServiceFactory>>,
+    http_req: HttpRequest,
     req: web::Json<SaveConfigRequest>
 ) -> impl Responder {
     info!("收到保存配置請求，共 {} 個配置", req.configs.len());
     
-    let result = service_factory.save_configs_and_apply(req.configs.clone()).await;
+    let expected_revision = if_match(&http_req);
+    let result = service_factory.save_configs_and_apply(req.configs.clone(), expected_revision.as_deref()).await;
     
     match result {
         Ok(_) => {
-            // 使用相同的邏輯豐富返回的配置信息
-            let mut enhanced_configs = Vec::new();
+            // 保存後配置已分配 ID，從配置服務讀回
+            let config_service = service_factory.get_config_service();
+            let configs = config_service.get_configs().await.unwrap_or_default();
             
-            for config in &req.configs {
-                let config_id = format!("{}-{}", config.zone_id, config.record_id);
-                let mut enhanced = EnhancedDdnsConfig {
-                    config: config.clone(),
-                    current_ip: None,
-                    last_update_time: None,
-                    status: Some("ok".to_string()),
-                };
-                
-                // 嘗試查找服務獲取更多信息 (雖然剛保存可能還沒完全初始化)
-                if let Some(service) = service_factory.find_ddns_service(&config_id).await {
-                    let service_guard = service.lock().await;
-                    
-                    // 獲取當前IP
-                    if let Ok(ip) = service_guard.get_last_or_current_ip(&config_id).await {
-                        enhanced.current_ip = Some(ip);
-                    }
-                }
-                
-                enhanced_configs.push(enhanced);
+            // 使用相同的邏輯豐富返回的配置信息 (雖然剛保存可能還沒完全初始化)
+            let mut enhanced_configs = Vec::new();
+            for config in configs {
+                enhanced_configs.push(enhance_config(&service_factory, config, false).await);
             }
             
-            HttpResponse::Ok().json(ConfigResponse {
-                success: true,
-                message: format!("成功保存 {} 個配置", req.configs.len()),
-                configs: Some(enhanced_configs),
-            })
+            let revision = config_service.get_configs_revision().await;
+            
+            HttpResponse::Ok()
+                .insert_header((ETAG, format_etag(&revision)))
+                .json(ConfigResponse {
+                    success: true,
+                    message: format!("成功保存 {} 個配置", req.configs.len()),
+                    configs: Some(enhanced_configs),
+                })
         },
         Err(e) => {
             let app_error: ApplicationError = ApplicationError::DomainError(e);
@@ -169,6 +151,131 @@ pub async fn save_configs(
     }
 }
 
+/// 獲取單個配置
+/// 
+/// # 路由
+/// 
+/// - `GET /api/configs/{id}`
+/// 
+/// # 返回
+/// 
+/// - 配置內容，`ETag` 響應頭為該配置的修訂標記
+#[get("/{id}")]
+pub async fn get_config(
+    service_factory: web::Data<Arc<ServiceFactory>>,
+    path: web::Path<ConfigId>
+) -> impl Responder {
+    let id = path.into_inner();
+    info!("收到獲取配置請求: {}", id);
+    
+    match service_factory.get_config_service().get_config(&id).await {
+        Ok(Some(config)) => single_config_response(&service_factory, config, "成功獲取配置".to_string(), false).await,
+        Ok(None) => handle_application_error(
+            ApplicationError::DomainError(DomainError::not_found(format!("配置 {} 不存在", id))),
+            "獲取配置失敗"
+        ),
+        Err(e) => handle_application_error(ApplicationError::DomainError(e), "獲取配置失敗"),
+    }
+}
+
+/// 創建或替換單個配置
+/// 
+/// # 路由
+/// 
+/// - `PUT /api/configs/{id}`
+/// 
+/// # 請求頭
+/// 
+/// - `If-Match`: 可選，配置的修訂標記，不一致時返回 412
+/// 
+/// # 請求體
+/// 
+/// - 完整的配置內容
+#[put("/{id}")]
+pub async fn put_config(
+    service_factory: web::Data<Arc<ServiceFactory>>,
+    http_req: HttpRequest,
+    path: web::Path<ConfigId>,
+    req: web::Json<DdnsConfig>
+) -> impl Responder {
+    let id = path.into_inner();
+    info!("收到替換配置請求: {}", id);
+    
+    let expected_revision = if_match(&http_req);
+    let config_service = service_factory.get_config_service();
+    
+    match config_service.put_config(&id, req.into_inner(), expected_revision.as_deref()).await {
+        Ok((config, created)) => {
+            let message = if created { "配置已創建" } else { "配置已更新" };
+            single_config_response(&service_factory, config, message.to_string(), created).await
+        },
+        Err(e) => handle_application_error(ApplicationError::DomainError(e), "保存配置失敗"),
+    }
+}
+
+/// 部分更新單個配置
+/// 
+/// # 路由
+/// 
+/// - `PATCH /api/configs/{id}`
+/// 
+/// # 請求頭
+/// 
+/// - `If-Match`: 可選，配置的修訂標記，不一致時返回 412
+/// 
+/// # 請求體
+/// 
+/// - JSON Merge Patch，只包含需要修改的欄位
+#[patch("/{id}")]
+pub async fn patch_config(
+    service_factory: web::Data<Arc<ServiceFactory>>,
+    http_req: HttpRequest,
+    path: web::Path<ConfigId>,
+    req: web::Json<serde_json::Value>
+) -> impl Responder {
+    let id = path.into_inner();
+    info!("收到部分更新配置請求: {}", id);
+    
+    let expected_revision = if_match(&http_req);
+    let config_service = service_factory.get_config_service();
+    
+    match config_service.patch_config(&id, req.into_inner(), expected_revision.as_deref()).await {
+        Ok(config) => single_config_response(&service_factory, config, "配置已更新".to_string(), false).await,
+        Err(e) => handle_application_error(ApplicationError::DomainError(e), "更新配置失敗"),
+    }
+}
+
+/// 刪除單個配置
+/// 
+/// # 路由
+/// 
+/// - `DELETE /api/configs/{id}`
+/// 
+/// # 請求頭
+/// 
+/// - `If-Match`: 可選，配置的修訂標記，不一致時返回 412
+#[delete("/{id}")]
+pub async fn delete_config(
+    service_factory: web::Data<Arc<ServiceFactory>>,
+    http_req: HttpRequest,
+    path: web::Path<ConfigId>
+) -> impl Responder {
+    let id = path.into_inner();
+    info!("收到刪除配置請求: {}", id);
+    
+    let expected_revision = if_match(&http_req);
+    let config_service = service_factory.get_config_service();
+    
+    match config_service.delete_config(&id, expected_revision.as_deref()).await {
+        Ok(config) => HttpResponse::Ok().json(SingleConfigResponse {
+            success: true,
+            message: format!("配置 {} 已刪除", config.record_name),
+            config: None,
+        }),
+        Err(e) => handle_application_error(ApplicationError::DomainError(e), "刪除配置失敗"),
+    }
+}
+
 /// 驗證配置
 /// 
 /// # 路由
@@ -210,6 +317,73 @@ pub async fn validate_config(
     }
 }
 
+/// 獲取配置的擴展信息
+/// 
+/// # 參數
+/// 
+/// - `service_factory`: 服務工廠
+/// - `config`: 配置
+/// - `with_last_update`: 是否包含最後更新時間
+async fn enhance_config(service_factory: &ServiceFactory, config: DdnsConfig, with_last_update: bool) -> EnhancedDdnsConfig {
+    let config_id = config.id.clone();
+    let mut enhanced = EnhancedDdnsConfig {
+        config,
+        current_ip: None,
+        last_update_time: None,
+        status: Some("ok".to_string()),
+    };
+    
+    // 尋找對應的DDNS服務獲取更多信息
+    if let Some(service) = service_factory.find_ddns_service(&config_id).await {
+        let service_guard = service.lock().await;
+        
+        // 獲取當前IP
+        if let Ok(ip) = service_guard.get_last_or_current_ip().await {
+            enhanced.current_ip = Some(ip);
+        }
+        
+        // 獲取最後更新時間
+        if with_last_update {
+            if let Ok(Some(time)) = service_guard.get_last_update_for_api().await {
+                enhanced.last_update_time = Some(format_datetime(time));
+            }
+        }
+    }
+    
+    enhanced
+}
+
+/// 構建帶 ETag 的單個配置響應
+async fn single_config_response(service_factory: &ServiceFactory, config: DdnsConfig, message: String, created: bool) -> HttpResponse {
+    let etag = format_etag(&config.revision());
+    let enhanced = enhance_config(service_factory, config, true).await;
+    
+    let mut builder = if created { HttpResponse::Created() } else { HttpResponse::Ok() };
+    builder
+        .insert_header((ETAG, etag))
+        .json(SingleConfigResponse {
+            success: true,
+            message,
+            config: Some(enhanced),
+        })
+}
+
+/// 讀取 `If-Match` 請求頭中的修訂標記
+///
+/// 去掉弱標記前綴和引號；`*` 原樣返回，表示資源存在即可，由配置服務處理
+pub(super) fn if_match(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(IF_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().trim_start_matches("W/").trim_matches('"').to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// 將修訂標記格式化為 ETag
+pub(super) fn format_etag(revision: &str) -> String {
+    format!("\"{}\"", revision)
+}
+
 /// 格式化日期時間
 fn format_datetime(dt: DateTime<Utc>) -> String {
     dt.format("%Y-%m-%d %H:%M:%S UTC").to_string()
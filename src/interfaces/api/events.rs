@@ -0,0 +1,92 @@
+use actix_web::{web, HttpResponse, Responder, get};
+use actix_web::http::header::{CacheControl, CacheDirective, ContentEncoding};
+use actix_web::web::Bytes;
+use crate::application::ServiceFactory;
+use crate::application::events::{Event, EventType};
+use futures::stream;
+use log::{info, warn};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::timeout;
+
+/// 保持連接的心跳間隔
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// 是否通過事件流推送該類型的事件
+///
+/// 只推送對外部訂閱者有意義的事件，內部控制事件不推送
+fn is_streamed(event_type: &EventType) -> bool {
+    !matches!(event_type, EventType::ForceUpdateDns | EventType::ForceUpdateAll)
+}
+
+/// 將事件格式化為 SSE 消息
+///
+/// # 參數
+///
+/// - `event`: 事件
+///
+/// # 返回
+///
+/// - `Option<String>`: SSE 格式的消息，事件不需要推送時返回 None
+fn format_sse_event(event: &Event) -> Option<String> {
+    let event_type = event.event_type();
+    if !is_streamed(&event_type) {
+        return None;
+    }
+
+    // 沒有內容的事件輸出空對象，方便客戶端統一按 JSON 解析
+    let data = match serde_json::to_value(event) {
+        Ok(Value::Null) | Err(_) => Value::Object(Default::default()),
+        Ok(value) => value,
+    };
+
+    Some(format!("event: {}\ndata: {}\n\n", event_type.name(), data))
+}
+
+/// 事件流處理器
+///
+/// 以 Server-Sent Events 推送 IP 變更、更新結果、記錄漂移、配置變更及重啟事件
+///
+/// # 參數
+///
+/// - `service_factory`: 服務工廠
+///
+/// # 返回
+///
+/// - `impl Responder`: `text/event-stream` 響應
+#[get("/events")]
+pub async fn event_stream(service_factory: web::Data<Arc<ServiceFactory>>) -> impl Responder {
+    info!("新的事件流連接");
+
+    let receiver = service_factory.get_event_manager().subscribe_all();
+
+    let events = stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match timeout(KEEP_ALIVE_INTERVAL, receiver.recv()).await {
+                // 長時間沒有事件時發送註釋行保持連接
+                Err(_) => return Some((Ok::<_, actix_web::Error>(Bytes::from_static(b": keep-alive\n\n")), receiver)),
+                Ok(Ok(event)) => {
+                    if let Some(message) = format_sse_event(&event) {
+                        return Some((Ok(Bytes::from(message)), receiver));
+                    }
+                },
+                Ok(Err(RecvError::Lagged(skipped))) => {
+                    warn!("事件流訂閱者處理過慢，跳過了 {} 個事件", skipped);
+                },
+                Ok(Err(RecvError::Closed)) => return None,
+            }
+        }
+    });
+
+    // 先發送一條註釋行，讓客戶端立即確認連接已建立
+    let connected = stream::once(async { Ok::<_, actix_web::Error>(Bytes::from_static(b": connected\n\n")) });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+        // 避免壓縮中間件緩衝事件
+        .insert_header(ContentEncoding::Identity)
+        .streaming(futures::StreamExt::chain(connected, events))
+}
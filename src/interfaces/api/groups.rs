@@ -0,0 +1,202 @@
+use actix_web::{web, get, put, delete, HttpRequest, HttpResponse, Responder};
+use actix_web::http::header::ETAG;
+use serde::Serialize;
+use crate::domain::config::{ConfigId, RecordGroup};
+use crate::application::ServiceFactory;
+use crate::application::ddns::RecordHealth;
+use crate::application::error::ApplicationError;
+use crate::domain::error::DomainError;
+use log::{info, warn};
+use std::sync::Arc;
+use super::common::handle_application_error;
+use super::config::{format_etag, if_match};
+use super::status::RecordStatusResponse;
+
+/// 記錄組響應
+///
+/// 包含記錄組配置、整體健康狀態及展開後每條記錄的狀態
+#[derive(Serialize)]
+pub struct GroupView {
+    #[serde(flatten)]
+    group: RecordGroup,
+    /// 整體健康狀態：任一記錄失敗時為 failing，否則任一記錄未檢查時為 pending
+    health: RecordHealth,
+    members: Vec<RecordStatusResponse>,
+}
+
+/// 記錄組列表響應
+#[derive(Serialize)]
+struct GroupsResponse {
+    success: bool,
+    message: String,
+    groups: Vec<GroupView>,
+}
+
+/// 單個記錄組響應
+#[derive(Serialize)]
+struct SingleGroupResponse {
+    success: bool,
+    message: String,
+    group: Option<GroupView>,
+}
+
+/// 獲取所有記錄組
+///
+/// # 路由
+///
+/// - `GET /api/groups`
+#[get("")]
+pub async fn get_groups(
+    service_factory: web::Data<Arc<ServiceFactory>>
+) -> impl Responder {
+    info!("收到獲取記錄組請求");
+
+    let mut groups = Vec::new();
+    for group in service_factory.get_config_service().get_groups().await {
+        groups.push(group_view(&service_factory, group).await);
+    }
+
+    HttpResponse::Ok().json(GroupsResponse {
+        success: true,
+        message: format!("成功獲取 {} 個記錄組", groups.len()),
+        groups,
+    })
+}
+
+/// 獲取單個記錄組
+///
+/// # 路由
+///
+/// - `GET /api/groups/{id}`
+///
+/// # 返回
+///
+/// - 記錄組內容及每條記錄的狀態，`ETag` 響應頭為該記錄組的修訂標記
+#[get("/{id}")]
+pub async fn get_group(
+    service_factory: web::Data<Arc<ServiceFactory>>,
+    path: web::Path<ConfigId>
+) -> impl Responder {
+    let id = path.into_inner();
+    info!("收到獲取記錄組請求: {}", id);
+
+    match service_factory.get_config_service().get_group(&id).await {
+        Some(group) => single_group_response(&service_factory, group, "成功獲取記錄組".to_string(), false).await,
+        None => handle_application_error(
+            ApplicationError::DomainError(DomainError::not_found(format!("記錄組 {} 不存在", id))),
+            "獲取記錄組失敗"
+        ),
+    }
+}
+
+/// 創建或替換單個記錄組
+///
+/// # 路由
+///
+/// - `PUT /api/groups/{id}`
+///
+/// # 請求頭
+///
+/// - `If-Match`: 可選，記錄組的修訂標記，不一致時返回 412
+#[put("/{id}")]
+pub async fn put_group(
+    service_factory: web::Data<Arc<ServiceFactory>>,
+    http_req: HttpRequest,
+    path: web::Path<ConfigId>,
+    req: web::Json<RecordGroup>
+) -> impl Responder {
+    let id = path.into_inner();
+    info!("收到替換記錄組請求: {}", id);
+
+    let expected_revision = if_match(&http_req);
+    let config_service = service_factory.get_config_service();
+
+    match config_service.put_group(&id, req.into_inner(), expected_revision.as_deref()).await {
+        Ok((group, created)) => {
+            let message = if created { "記錄組已創建" } else { "記錄組已更新" };
+            single_group_response(&service_factory, group, message.to_string(), created).await
+        },
+        Err(e) => handle_application_error(ApplicationError::DomainError(e), "保存記錄組失敗"),
+    }
+}
+
+/// 刪除單個記錄組
+///
+/// # 路由
+///
+/// - `DELETE /api/groups/{id}`
+///
+/// # 請求頭
+///
+/// - `If-Match`: 可選，記錄組的修訂標記，不一致時返回 412
+#[delete("/{id}")]
+pub async fn delete_group(
+    service_factory: web::Data<Arc<ServiceFactory>>,
+    http_req: HttpRequest,
+    path: web::Path<ConfigId>
+) -> impl Responder {
+    let id = path.into_inner();
+    info!("收到刪除記錄組請求: {}", id);
+
+    let expected_revision = if_match(&http_req);
+    let config_service = service_factory.get_config_service();
+
+    match config_service.delete_group(&id, expected_revision.as_deref()).await {
+        Ok(group) => HttpResponse::Ok().json(SingleGroupResponse {
+            success: true,
+            message: format!("記錄組 {} 已刪除", group.name.as_deref().unwrap_or(group.id.as_str())),
+            group: None,
+        }),
+        Err(e) => handle_application_error(ApplicationError::DomainError(e), "刪除記錄組失敗"),
+    }
+}
+
+/// 組裝記錄組響應，附帶展開後每條記錄的狀態
+///
+/// # 參數
+///
+/// - `service_factory`: 服務工廠
+/// - `group`: 記錄組
+async fn group_view(service_factory: &ServiceFactory, group: RecordGroup) -> GroupView {
+    let mut members = Vec::new();
+    for config in group.expand() {
+        // 剛保存的記錄組可能尚未創建服務
+        let Some(service) = service_factory.find_ddns_service(&config.id).await else {
+            continue;
+        };
+        let service_guard = service.lock().await;
+        match service_guard.status_snapshot().await {
+            Ok(status) => members.push(status),
+            Err(e) => warn!("獲取 {} 的狀態失敗: {}", config.record_name, e),
+        }
+    }
+
+    let health = if members.iter().any(|status| status.health == RecordHealth::Failing) {
+        RecordHealth::Failing
+    } else if members.is_empty() || members.iter().any(|status| status.health == RecordHealth::Pending) {
+        RecordHealth::Pending
+    } else {
+        RecordHealth::Healthy
+    };
+
+    GroupView {
+        group,
+        health,
+        members: members.into_iter().map(RecordStatusResponse::from).collect(),
+    }
+}
+
+/// 構建帶 ETag 的單個記錄組響應
+async fn single_group_response(service_factory: &ServiceFactory, group: RecordGroup, message: String, created: bool) -> HttpResponse {
+    let etag = format_etag(&group.revision());
+    let view = group_view(service_factory, group).await;
+
+    let mut builder = if created { HttpResponse::Created() } else { HttpResponse::Ok() };
+    builder
+        .insert_header((ETAG, etag))
+        .json(SingleGroupResponse {
+            success: true,
+            message,
+            group: Some(view),
+        })
+}
@@ -1,5 +1,6 @@
 use actix_web::{get, web, HttpResponse};
 use crate::ServiceFactory;
+use crate::application::health::{ReadinessReport, DEFAULT_MAX_MISSED_INTERVALS};
 use serde::{Serialize, Deserialize};
 use std::time::Instant;
 use super::common::{ApiResponse, ApiMetricsMiddleware, get_api_metrics, get_connection_count};
@@ -45,6 +46,22 @@ struct ApiStatus {
     avg_response_time_ms: f64,
 }
 
+/// 存活檢查響應結構
+#[derive(Serialize)]
+struct LivenessResponse {
+    /// 存活狀態
+    status: &'static str,
+    /// 啟動時間（秒）
+    uptime: u64,
+}
+
+/// 就緒檢查查詢參數
+#[derive(Deserialize, Default)]
+pub struct ReadinessQuery {
+    /// 記錄允許錯過的更新間隔數
+    max_missed_intervals: Option<u32>,
+}
+
 /// 服務啟動時間
 static mut START_TIME: Option<Instant> = None;
 
@@ -55,16 +72,9 @@ pub fn init_start_time() {
     }
 }
 
-/// 健康檢查端點
-/// 
-/// 返回服務健康狀態信息
-#[get("/health")]
-pub async fn health_check(service_factory: web::Data<std::sync::Arc<ServiceFactory>>) -> HttpResponse {
-    // 記錄API調用開始
-    let (endpoint, start_time) = ApiMetricsMiddleware::begin("health_check");
-    
-    // 獲取啟動時間
-    let uptime = unsafe {
+/// 獲取服務運行時間（秒）
+fn uptime_seconds() -> u64 {
+    unsafe {
         match START_TIME {
             Some(start_time) => start_time.elapsed().as_secs(),
             None => {
@@ -73,7 +83,20 @@ pub async fn health_check(service_factory: web::Data<std::sync::Arc<ServiceFacto
                 0
             }
         }
-    };
+    }
+}
+
+/// 健康檢查端點
+/// 
+/// 返回服務健康狀態信息，`status` 根據就緒檢查結果為 `operational` 或 `degraded`
+#[get("/health")]
+pub async fn health_check(service_factory: web::Data<std::sync::Arc<ServiceFactory>>) -> HttpResponse {
+    // 記錄API調用開始
+    let (endpoint, start_time) = ApiMetricsMiddleware::begin("health_check");
+    
+    // 獲取啟動時間
+    let uptime = uptime_seconds();
+    let readiness = service_factory.readiness(DEFAULT_MAX_MISSED_INTERVALS).await;
     
     // 收集API統計數據
     let metrics = get_api_metrics();
@@ -96,7 +119,7 @@ pub async fn health_check(service_factory: web::Data<std::sync::Arc<ServiceFacto
     // 構建響應
     let response = HealthResponse {
         service: "Cloudflare DDNS".to_string(),
-        status: "operational".to_string(),
+        status: if readiness.ready { "operational" } else { "degraded" }.to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
         uptime,
         system_info: SystemInfo {
@@ -119,4 +142,41 @@ pub async fn health_check(service_factory: web::Data<std::sync::Arc<ServiceFacto
     
     // 返回響應
     ApiResponse::success(response, Some(start_time)).into_response()
-} 
\ No newline at end of file
+} 
+
+/// 存活檢查端點
+///
+/// 進程能處理請求即返回 200，供編排系統判斷是否需要重啟
+#[get("/health/live")]
+pub async fn liveness_check() -> HttpResponse {
+    HttpResponse::Ok().json(LivenessResponse {
+        status: "alive",
+        uptime: uptime_seconds(),
+    })
+}
+
+/// 就緒檢查端點
+///
+/// 檢查配置加載、更新任務、記錄最近成功時間和 Cloudflare 可達性，
+/// 任一項未通過時返回 503
+#[get("/health/ready")]
+pub async fn readiness_check(
+    service_factory: web::Data<std::sync::Arc<ServiceFactory>>,
+    query: web::Query<ReadinessQuery>,
+) -> HttpResponse {
+    let (endpoint, start_time) = ApiMetricsMiddleware::begin("readiness_check");
+    
+    let max_missed_intervals = query.max_missed_intervals.unwrap_or(DEFAULT_MAX_MISSED_INTERVALS);
+    let report = service_factory.readiness(max_missed_intervals).await;
+    let ready = report.ready;
+    
+    ApiMetricsMiddleware::end(endpoint, start_time, ready);
+    
+    let mut response = ApiResponse::<ReadinessReport>::success(report, Some(start_time));
+    if !ready {
+        response.success = false;
+        response.code = 503;
+        response.error = Some("服務未就緒".to_string());
+    }
+    response.into_response()
+}
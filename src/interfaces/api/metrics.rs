@@ -0,0 +1,125 @@
+use actix_web::{get, web, HttpResponse};
+use crate::application::ServiceFactory;
+use crate::infrastructure::http::circuit_breakers;
+use crate::infrastructure::metrics::{metrics, MetricsRegistry};
+use super::common::{get_api_metrics, get_connection_count};
+use chrono::Utc;
+use log::warn;
+use std::sync::Arc;
+
+/// Prometheus 文本格式的內容類型
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Prometheus 指標端點
+///
+/// 返回累計的更新、失敗和延遲指標，以及抓取時計算的記錄狀態和 API 請求指標
+#[get("/metrics")]
+pub async fn prometheus_metrics(service_factory: web::Data<Arc<ServiceFactory>>) -> HttpResponse {
+    let snapshot = MetricsRegistry::new();
+    collect_record_metrics(&service_factory, &snapshot).await;
+    collect_api_metrics(&snapshot);
+    collect_circuit_metrics(&snapshot);
+
+    let mut body = metrics().render();
+    body.push_str(&snapshot.render());
+
+    HttpResponse::Ok()
+        .content_type(PROMETHEUS_CONTENT_TYPE)
+        .body(body)
+}
+
+/// 收集每條記錄距離上次成功更新的秒數
+///
+/// # 參數
+///
+/// - `service_factory`: 服務工廠
+/// - `registry`: 寫入的指標註冊表
+async fn collect_record_metrics(service_factory: &ServiceFactory, registry: &MetricsRegistry) {
+    let now = Utc::now();
+
+    for service in service_factory.list_ddns_services().await {
+        let status = match service.lock().await.status_snapshot().await {
+            Ok(status) => status,
+            Err(e) => {
+                warn!("收集記錄指標失敗: {}", e);
+                continue;
+            }
+        };
+
+        let Some(last_update) = status.last_update else {
+            continue;
+        };
+
+        let ip_type = status.ip_type.to_string();
+        let seconds = (now - last_update).num_milliseconds().max(0) as f64 / 1000.0;
+        registry.set_gauge(
+            "ddns_seconds_since_last_update",
+            "Seconds since the last successful DNS record update",
+            &[
+                ("config_id", status.config_id.as_str()),
+                ("record_name", status.record_name.as_str()),
+                ("ip_type", ip_type.as_str()),
+            ],
+            seconds,
+        );
+    }
+}
+
+/// 收集 API 請求指標
+///
+/// # 參數
+///
+/// - `registry`: 寫入的指標註冊表
+fn collect_api_metrics(registry: &MetricsRegistry) {
+    for (endpoint, metric) in get_api_metrics() {
+        let labels = [("endpoint", endpoint.as_str())];
+        registry.add_counter(
+            "ddns_http_requests_total",
+            "HTTP API requests by endpoint",
+            &labels,
+            metric.request_count as f64,
+        );
+        registry.add_counter(
+            "ddns_http_request_errors_total",
+            "Failed HTTP API requests by endpoint",
+            &labels,
+            metric.error_count as f64,
+        );
+        registry.add_counter(
+            "ddns_http_request_duration_seconds_total",
+            "Total time spent handling HTTP API requests by endpoint",
+            &labels,
+            metric.total_response_time_ms as f64 / 1000.0,
+        );
+    }
+
+    registry.set_gauge(
+        "ddns_http_active_connections",
+        "HTTP API requests currently in flight",
+        &[],
+        get_connection_count() as f64,
+    );
+}
+
+/// 收集上游主機的熔斷器狀態
+///
+/// # 參數
+///
+/// - `registry`: 寫入的指標註冊表
+fn collect_circuit_metrics(registry: &MetricsRegistry) {
+    for circuit in circuit_breakers().snapshot() {
+        let labels = [("host", circuit.host.as_str())];
+        registry.set_gauge(
+            "ddns_circuit_breaker_state",
+            "Circuit breaker state by upstream host (0 closed, 1 half-open, 2 open)",
+            &labels,
+            circuit.state.as_gauge(),
+        );
+        registry.set_gauge(
+            "ddns_circuit_breaker_consecutive_failures",
+            "Consecutive failed requests by upstream host",
+            &labels,
+            circuit.consecutive_failures as f64,
+        );
+    }
+}
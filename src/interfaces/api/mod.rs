@@ -4,8 +4,11 @@ pub mod common;
 
 // API 處理器模塊
 mod config;
+mod events;
+mod groups;
 pub mod health;
 mod ip;
+mod metrics;
 mod status;
 mod update;
 mod wizard;
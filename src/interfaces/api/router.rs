@@ -1,9 +1,12 @@
 use actix_web::web;
 use super::ip::{get_ipv4, get_ipv6};
-use super::health::health_check;
+use super::health::{health_check, liveness_check, readiness_check};
 use super::status::get_status;
+use super::events::event_stream;
+use super::metrics::prometheus_metrics;
 use super::update::{force_update, restart_service, update_all_records};
-use super::config::{get_configs, save_configs, validate_config};
+use super::config::{get_configs, save_configs, validate_config, get_config, put_config, patch_config, delete_config};
+use super::groups::{get_groups, get_group, put_group, delete_group};
 use super::wizard::{validate_token, get_dns_records};
 use log::info;
 
@@ -20,9 +23,12 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     // 使用一個靜態變數確保只輸出一次日誌
     static LOGGED: std::sync::Once = std::sync::Once::new();
     LOGGED.call_once(|| {
-        info!("註冊API路由: /api/ip, /api/health, /api/status, /api/update, /api/update-all, /api/configs, /api/wizard");
+        info!("註冊API路由: /api/ip, /api/health, /api/health/live, /api/health/ready, /api/status, /api/events, /api/update, /api/update-all, /api/configs, /api/configs/{{id}}, /api/groups, /api/groups/{{id}}, /api/wizard, /metrics");
     });
     
+    // Prometheus 抓取端點位於 /api 之外
+    cfg.service(prometheus_metrics);
+    
     cfg.service(
         web::scope("/api")
             .service(
@@ -31,7 +37,10 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
                     .service(get_ipv6)
             )
             .service(health_check)
+            .service(liveness_check)
+            .service(readiness_check)
             .service(get_status)
+            .service(event_stream)
             .service(force_update)
             .service(update_all_records)
             .service(restart_service)
@@ -40,6 +49,17 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
                     .service(get_configs)
                     .service(save_configs)
                     .service(validate_config)
+                    .service(get_config)
+                    .service(put_config)
+                    .service(patch_config)
+                    .service(delete_config)
+            )
+            .service(
+                web::scope("/groups")
+                    .service(get_groups)
+                    .service(get_group)
+                    .service(put_group)
+                    .service(delete_group)
             )
             .service(
                 web::scope("/wizard")
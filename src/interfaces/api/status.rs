@@ -1,6 +1,9 @@
 use actix_web::{web, HttpResponse, Responder, get};
 use crate::application::ServiceFactory;
-use serde::Serialize;
+use crate::application::ddns::{DdnsRecordStatus, RecordHealth};
+use crate::domain::config::{ConfigId, IpType};
+use crate::interfaces::api::common::handle_domain_error;
+use serde::{Deserialize, Serialize};
 use log::{info, warn};
 use chrono::{DateTime, Utc};
 use std::sync::Arc;
@@ -10,71 +13,172 @@ use std::sync::Arc;
 pub struct StatusResponse {
     status: String,
     version: String,
+    /// 所有記錄中最近一次的更新時間
     last_update: Option<String>,
-    ip_address: Option<String>,
-    domain: Option<String>,
+    summary: StatusSummary,
+    /// 記錄狀態列表，`view=summary` 時省略
+    #[serde(skip_serializing_if = "Option::is_none")]
+    records: Option<Vec<RecordStatusResponse>>,
+}
+
+/// 狀態摘要（供儀表板使用）
+#[derive(Serialize, Default)]
+pub struct StatusSummary {
+    total: usize,
+    healthy: usize,
+    failing: usize,
+    pending: usize,
+}
+
+/// 單條記錄狀態響應
+#[derive(Serialize)]
+pub struct RecordStatusResponse {
+    config_id: ConfigId,
+    record_name: String,
+    ip_type: IpType,
+    last_ip: Option<String>,
+    last_update: Option<String>,
+    last_check: Option<String>,
+    last_error: Option<String>,
+    last_error_time: Option<String>,
+    next_check: Option<String>,
+    health: RecordHealth,
+}
+
+impl From<DdnsRecordStatus> for RecordStatusResponse {
+    fn from(status: DdnsRecordStatus) -> Self {
+        Self {
+            config_id: status.config_id,
+            record_name: status.record_name,
+            ip_type: status.ip_type,
+            last_ip: status.last_ip,
+            last_update: status.last_update.map(format_datetime),
+            last_check: status.last_check.map(format_datetime),
+            last_error: status.last_error,
+            last_error_time: status.last_error_time.map(format_datetime),
+            next_check: status.next_check.map(format_datetime),
+            health: status.health,
+        }
+    }
+}
+
+/// 狀態查詢參數
+#[derive(Deserialize, Default)]
+pub struct StatusQuery {
+    /// 按配置 ID 過濾
+    config_id: Option<ConfigId>,
+    /// 按記錄名稱過濾（包含匹配，不區分大小寫）
+    record_name: Option<String>,
+    /// 按 IP 類型過濾（ipv4 / ipv6）
+    ip_type: Option<String>,
+    /// 按健康狀態過濾（healthy / failing / pending）
+    health: Option<String>,
+    /// 顯示模式，`summary` 時只返回摘要
+    view: Option<String>,
+}
+
+/// 已解析的狀態過濾條件
+struct StatusFilter {
+    config_id: Option<ConfigId>,
+    record_name: Option<String>,
+    ip_type: Option<IpType>,
+    health: Option<RecordHealth>,
+}
+
+impl StatusFilter {
+    /// 從查詢參數解析過濾條件
+    fn from_query(query: &StatusQuery) -> Result<Self, crate::domain::error::DomainError> {
+        Ok(Self {
+            config_id: query.config_id.clone(),
+            record_name: query.record_name.as_ref().map(|name| name.to_lowercase()),
+            ip_type: query.ip_type.as_deref().map(IpType::try_from).transpose()?,
+            health: query.health.as_deref().map(RecordHealth::try_from).transpose()?,
+        })
+    }
+
+    /// 檢查記錄狀態是否符合過濾條件
+    fn matches(&self, status: &DdnsRecordStatus) -> bool {
+        self.config_id.as_ref().is_none_or(|id| *id == status.config_id)
+            && self.record_name.as_ref().is_none_or(|name| status.record_name.to_lowercase().contains(name))
+            && self.ip_type.as_ref().is_none_or(|ip_type| *ip_type == status.ip_type)
+            && self.health.is_none_or(|health| health == status.health)
+    }
 }
 
 /// 服務狀態處理器
-/// 
+///
 /// # 參數
-/// 
+///
 /// - `service_factory`: 服務工廠
-/// 
+/// - `query`: 過濾條件（config_id、record_name、ip_type、health）及顯示模式（view=summary）
+///
 /// # 返回
-/// 
-/// - `impl Responder`: 返回服務狀態信息
+///
+/// - `impl Responder`: 返回所有 DDNS 記錄的狀態信息
 #[get("/status")]
-pub async fn get_status(service_factory: web::Data<Arc<ServiceFactory>>) -> impl Responder {
+pub async fn get_status(
+    service_factory: web::Data<Arc<ServiceFactory>>,
+    query: web::Query<StatusQuery>,
+) -> impl Responder {
     info!("接收到狀態查詢請求");
-    
-    // 嘗試從服務工廠獲取運行中的DDNS服務實例
-    let mut response = StatusResponse {
-        status: "running".to_string(),
-        version: env!("CARGO_PKG_VERSION").to_string(),
-        last_update: None,
-        ip_address: None,
-        domain: None,
+
+    let filter = match StatusFilter::from_query(&query) {
+        Ok(filter) => filter,
+        Err(e) => return handle_domain_error(e, "解析狀態查詢參數"),
     };
-    
-    // 如果找到服務實例，從中獲取狀態信息
-    if let Some(service) = service_factory.get_first_ddns_service().await {
+
+    // 收集所有服務實例的狀態快照
+    let mut statuses = Vec::new();
+    for service in service_factory.list_ddns_services().await {
         let service_guard = service.lock().await;
-        
-        // 獲取配置信息
-        let config = service_guard.config();
-        
-        // 設置域名信息
-        response.domain = Some(config.record_name.clone());
-        
-        // 嘗試從狀態存儲獲取更多信息
-        let config_id = format!("{}-{}", config.zone_id, config.record_id);
-        
-        // 使用服務獲取當前IP
-        match service_guard.get_last_or_current_ip(&config_id).await {
-            Ok(ip) => {
-                response.ip_address = Some(ip);
-            },
-            Err(e) => {
-                warn!("獲取當前IP失敗: {}", e);
-            }
-        }
-        
-        // 獲取最後更新時間
-        if let Ok(last_update) = service_guard.get_last_update_for_api(&config_id).await {
-            if let Some(time) = last_update {
-                response.last_update = Some(format_datetime(time));
-            }
+        match service_guard.status_snapshot().await {
+            Ok(status) => statuses.push(status),
+            Err(e) => warn!("獲取 {} 的狀態失敗: {}", service_guard.config().record_name, e),
         }
-    } else {
+    }
+
+    if statuses.is_empty() {
         warn!("找不到運行中的DDNS服務實例");
     }
-    
-    info!("返回服務狀態: {}, 版本: {}", response.status, response.version);
+
+    statuses.retain(|status| filter.matches(status));
+
+    let mut summary = StatusSummary {
+        total: statuses.len(),
+        ..Default::default()
+    };
+    for status in &statuses {
+        match status.health {
+            RecordHealth::Healthy => summary.healthy += 1,
+            RecordHealth::Failing => summary.failing += 1,
+            RecordHealth::Pending => summary.pending += 1,
+        }
+    }
+
+    let last_update = statuses.iter()
+        .filter_map(|status| status.last_update)
+        .max()
+        .map(format_datetime);
+
+    let records = if query.view.as_deref() == Some("summary") {
+        None
+    } else {
+        Some(statuses.into_iter().map(RecordStatusResponse::from).collect())
+    };
+
+    let response = StatusResponse {
+        status: "running".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        last_update,
+        summary,
+        records,
+    };
+
+    info!("返回服務狀態: {}, 版本: {}, 記錄數: {}", response.status, response.version, response.summary.total);
     HttpResponse::Ok().json(response)
 }
 
 /// 格式化日期時間
 fn format_datetime(dt: DateTime<Utc>) -> String {
     dt.format("%Y-%m-%d %H:%M:%S UTC").to_string()
-} 
\ No newline at end of file
+}
@@ -1,5 +1,7 @@
 use actix_web::{web, HttpResponse, Responder, post};
 use crate::application::ServiceFactory;
+use crate::application::ddns::{PlanAction, PlanEntry};
+use crate::domain::config::ConfigId;
 use serde::{Deserialize, Serialize};
 use log::{info, error};
 use std::sync::Arc;
@@ -14,13 +16,26 @@ pub struct UpdateResponse {
     updated: bool,
 }
 
+/// 試運行響應結構
+#[derive(Serialize)]
+pub struct PlanResponse {
+    success: bool,
+    message: String,
+    plan: Vec<PlanEntry>,
+}
+
 /// 特定記錄更新請求
 #[derive(Deserialize)]
 pub struct UpdateSpecificRequest {
+    /// 配置 ID（優先於 domain 和 record_id）
+    config_id: Option<ConfigId>,
     domain: Option<String>,
     record_id: Option<String>,
     /// 是否等待結果
     wait_for_result: Option<bool>,
+    /// 是否只返回更新計劃而不寫入 Cloudflare
+    #[serde(default)]
+    dry_run: bool,
 }
 
 /// 強制更新 DNS 記錄處理器
@@ -39,6 +54,10 @@ pub async fn force_update(
 ) -> impl Responder {
     info!("收到強制更新DNS記錄請求");
     
+    if let Some(req) = req.as_ref().filter(|r| r.dry_run) {
+        return plan_update(&service_factory, req).await;
+    }
+    
     let wait_for_result = req.as_ref()
         .map(|r| r.wait_for_result.unwrap_or(false))
         .unwrap_or(false);
@@ -48,7 +67,56 @@ pub async fn force_update(
     
     // 檢查是否有特定的域名或記錄ID
     if let Some(req) = req {
-        if let Some(domain) = &req.domain {
+        if let Some(config_id) = &req.config_id {
+            info!("強制更新特定配置: {}", config_id);
+            
+            let Some(service) = service_factory.find_ddns_service(config_id).await else {
+                return HttpResponse::NotFound().json(UpdateResponse {
+                    success: false,
+                    message: format!("未找到ID為 {} 的配置", config_id),
+                    ip_address: None,
+                    domain: None,
+                    updated: false,
+                });
+            };
+            
+            let service_guard = service.lock().await;
+            let domain = service_guard.config().record_name.clone();
+            
+            if wait_for_result {
+                return match service_guard.force_update().await {
+                    Ok((_, current_ip)) => HttpResponse::Ok().json(UpdateResponse {
+                        success: true,
+                        message: format!("DNS記錄 {} 已成功更新", domain),
+                        ip_address: Some(current_ip),
+                        domain: Some(domain),
+                        updated: true,
+                    }),
+                    Err(e) => {
+                        error!("更新配置 {} 失敗: {}", config_id, e);
+                        HttpResponse::InternalServerError().json(UpdateResponse {
+                            success: false,
+                            message: format!("更新配置 {} 失敗: {}", config_id, e),
+                            ip_address: None,
+                            domain: Some(domain),
+                            updated: false,
+                        })
+                    }
+                };
+            }
+            
+            let ip_address = service_guard.get_last_or_current_ip().await.ok();
+            drop(service_guard);
+            event_manager.force_update_dns(Some(config_id)).await;
+            
+            return HttpResponse::Ok().json(UpdateResponse {
+                success: true,
+                message: format!("DNS記錄 {} 更新請求已發送", domain),
+                ip_address,
+                domain: Some(domain),
+                updated: false,
+            });
+        } else if let Some(domain) = &req.domain {
             info!("強制更新特定域名: {}", domain);
             
             // 查找相關的配置和服務以獲取當前IP
@@ -58,13 +126,11 @@ pub async fn force_update(
             if let Ok(configs) = config_service.get_configs().await {
                 for config in configs {
                     if config.record_name == *domain {
-                        // 構造配置ID
-                        let config_id = format!("{}-{}", config.zone_id, config.record_id);
-                        if let Some(service) = service_factory.find_ddns_service(&config_id).await {
+                        if let Some(service) = service_factory.find_ddns_service(&config.id).await {
                             let service_guard = service.lock().await;
                             
                             // 獲取當前IP (無論是否需要更新)
-                            if let Ok(current_ip) = service_guard.get_last_or_current_ip(&config_id).await {
+                            if let Ok(current_ip) = service_guard.get_last_or_current_ip().await {
                                 ip_address = Some(current_ip);
                                 break;
                             }
@@ -79,9 +145,7 @@ pub async fn force_update(
                 if let Ok(configs) = config_service.get_configs().await {
                     for config in configs {
                         if config.record_name == *domain {
-                            // 構造配置ID
-                            let config_id = format!("{}-{}", config.zone_id, config.record_id);
-                            if let Some(service) = service_factory.find_ddns_service(&config_id).await {
+                            if let Some(service) = service_factory.find_ddns_service(&config.id).await {
                                 let service_guard = service.lock().await;
                                 
                                 // 直接調用服務的強制更新方法並等待結果
@@ -135,8 +199,12 @@ pub async fn force_update(
                     });
                 }
             } else {
-                // 不等待結果，立即返回但仍提供當前IP
-                event_manager.force_update_dns(Some(domain.clone())).await;
+                // 不等待結果，為每個匹配的配置發送更新事件
+                if let Ok(configs) = config_service.get_configs().await {
+                    for config in configs.iter().filter(|c| c.record_name == *domain) {
+                        event_manager.force_update_dns(Some(&config.id)).await;
+                    }
+                }
                 
                 return HttpResponse::Ok().json(UpdateResponse {
                     success: true,
@@ -155,19 +223,18 @@ pub async fn force_update(
                 for config in configs {
                     if config.record_id == *record_id {
                         let domain = config.record_name.clone();
-                        let config_id = format!("{}-{}", config.zone_id, config.record_id);
                         let mut ip_address = None;
                         
                         // 獲取當前IP (無論是否需要更新)
-                        if let Some(service) = service_factory.find_ddns_service(&config_id).await {
+                        if let Some(service) = service_factory.find_ddns_service(&config.id).await {
                             let service_guard = service.lock().await;
-                            if let Ok(current_ip) = service_guard.get_last_or_current_ip(&config_id).await {
+                            if let Ok(current_ip) = service_guard.get_last_or_current_ip().await {
                                 ip_address = Some(current_ip);
                             }
                         }
                         
                         if wait_for_result {
-                            if let Some(service) = service_factory.find_ddns_service(&config_id).await {
+                            if let Some(service) = service_factory.find_ddns_service(&config.id).await {
                                 let service_guard = service.lock().await;
                                 
                                 // 直接調用服務的強制更新方法並等待結果
@@ -195,7 +262,7 @@ pub async fn force_update(
                             }
                         } else {
                             // 不等待結果，立即返回但仍提供當前IP
-                            event_manager.force_update_dns(Some(domain.clone())).await;
+                            event_manager.force_update_dns(Some(&config.id)).await;
                             
                             return HttpResponse::Ok().json(UpdateResponse {
                                 success: true,
@@ -227,13 +294,12 @@ pub async fn force_update(
     
     if let Ok(configs) = config_service.get_configs().await {
         for config in configs {
-            let config_id = format!("{}-{}", config.zone_id, config.record_id);
             let domain = config.record_name.clone();
             
-            if let Some(service) = service_factory.find_ddns_service(&config_id).await {
+            if let Some(service) = service_factory.find_ddns_service(&config.id).await {
                 let service_guard = service.lock().await;
                 
-                if let Ok(current_ip) = service_guard.get_last_or_current_ip(&config_id).await {
+                if let Ok(current_ip) = service_guard.get_last_or_current_ip().await {
                     ips_info.push(format!("{}: {}", domain, current_ip));
                 }
             }
@@ -252,10 +318,9 @@ pub async fn force_update(
         
         if let Ok(configs) = config_service.get_configs().await {
             for config in configs {
-                let config_id = format!("{}-{}", config.zone_id, config.record_id);
                 let domain = config.record_name.clone();
                 
-                if let Some(service) = service_factory.find_ddns_service(&config_id).await {
+                if let Some(service) = service_factory.find_ddns_service(&config.id).await {
                     let service_guard = service.lock().await;
                     
                     match service_guard.force_update().await {
@@ -327,13 +392,12 @@ pub async fn update_all_records(
     
     if let Ok(configs) = config_service.get_configs().await {
         for config in configs {
-            let config_id = format!("{}-{}", config.zone_id, config.record_id);
             let domain = config.record_name.clone();
             
-            if let Some(service) = service_factory.find_ddns_service(&config_id).await {
+            if let Some(service) = service_factory.find_ddns_service(&config.id).await {
                 let service_guard = service.lock().await;
                 
-                if let Ok(current_ip) = service_guard.get_last_or_current_ip(&config_id).await {
+                if let Ok(current_ip) = service_guard.get_last_or_current_ip().await {
                     ips_info.push(format!("{}: {}", domain, current_ip));
                 }
             }
@@ -352,10 +416,9 @@ pub async fn update_all_records(
         
         if let Ok(configs) = config_service.get_configs().await {
             for config in configs {
-                let config_id = format!("{}-{}", config.zone_id, config.record_id);
                 let domain = config.record_name.clone();
                 
-                if let Some(service) = service_factory.find_ddns_service(&config_id).await {
+                if let Some(service) = service_factory.find_ddns_service(&config.id).await {
                     let service_guard = service.lock().await;
                     
                     match service_guard.force_update().await {
@@ -427,4 +490,54 @@ pub async fn restart_service(service_factory: web::Data<Arc<ServiceFactory>>) ->
     };
     
     HttpResponse::Ok().json(response)
-} 
\ No newline at end of file
+} 
+
+/// 試運行更新，返回選中記錄的計劃
+///
+/// 選擇記錄的方式與實際更新相同：`config_id` 優先，其次 `domain`、`record_id`，都未設置時為所有記錄
+///
+/// # 參數
+///
+/// - `service_factory`: 服務工廠
+/// - `req`: 更新請求
+async fn plan_update(service_factory: &ServiceFactory, req: &UpdateSpecificRequest) -> HttpResponse {
+    let services = if let Some(config_id) = &req.config_id {
+        service_factory.find_ddns_service(config_id).await.into_iter().collect()
+    } else if let Some(domain) = &req.domain {
+        service_factory.find_ddns_services_by_name(domain).await
+    } else if let Some(record_id) = &req.record_id {
+        let mut found = Vec::new();
+        for service in service_factory.list_ddns_services().await {
+            if service.lock().await.config().record_id == *record_id {
+                found.push(service);
+            }
+        }
+        found
+    } else {
+        service_factory.list_ddns_services().await
+    };
+    
+    if services.is_empty() && (req.config_id.is_some() || req.domain.is_some() || req.record_id.is_some()) {
+        return HttpResponse::NotFound().json(PlanResponse {
+            success: false,
+            message: "未找到匹配的DNS記錄配置".to_string(),
+            plan: Vec::new(),
+        });
+    }
+    
+    let mut plan = Vec::new();
+    for service in services {
+        let service_guard = service.lock().await;
+        plan.push(service_guard.plan(None).await);
+    }
+    
+    let failed = plan.iter().filter(|entry| entry.action == PlanAction::Error).count();
+    let changes = plan.iter().filter(|entry| entry.has_changes()).count();
+    info!("試運行完成: {} 條記錄，{} 條將變更，{} 條失敗", plan.len(), changes, failed);
+    
+    HttpResponse::Ok().json(PlanResponse {
+        success: failed == 0,
+        message: format!("試運行: {} 條記錄將變更，{} 條無法確定", changes, failed),
+        plan,
+    })
+}
@@ -6,10 +6,10 @@ pub mod constants;
 
 // 重新導出常用類型和結構
 pub use application::ServiceFactory;
-pub use domain::config::{DdnsConfig, IpType, Settings};
+pub use domain::config::{ConfigId, DdnsConfig, IpType, Settings};
 pub use domain::error::DomainError;
 pub use application::error::ApplicationError;
-pub use application::events::{EventManager, EventType, EventData};
+pub use application::events::{Event, EventManager, EventType};
 pub use constants::*;
 
 // 重新導出服務啟動函數
@@ -1,20 +1,30 @@
 use cloudflare_ddns::{
     run_server, 
     ServiceFactory, 
+    ConfigId,
     DdnsConfig,
     Settings,
     IpType
 };
+use cloudflare_ddns::application::background::background_tasks;
+use cloudflare_ddns::domain::config::{HttpClientSettings, LogFormat, TelemetrySettings, TraceExporter};
+use cloudflare_ddns::infrastructure::config::FileConfigRepository;
+use cloudflare_ddns::infrastructure::logging::init_logging;
+use cloudflare_ddns::infrastructure::telemetry::init_tracing;
 use log::{info, error, warn};
 use std::env;
-use std::process;
-use std::sync::mpsc;
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+use std::time::Duration;
 use actix_web::middleware::Compress;
 
-// 用於控制 DDNS 服務的全局變量
-static DDNS_CONTROL: once_cell::sync::Lazy<Arc<Mutex<Option<mpsc::Sender<()>>>>> = 
-    once_cell::sync::Lazy::new(|| Arc::new(Mutex::new(None)));
+/// 單次運行和試運行成功
+const EXIT_SUCCESS: i32 = 0;
+/// 任一記錄更新或試運行失敗
+const EXIT_FAILED: i32 = 1;
+/// 沒有可用或匹配的記錄
+const EXIT_NO_RECORDS: i32 = 2;
+/// 單次運行退出前等待通知發送的最長時間
+const BACKGROUND_TASKS_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// 顯示使用方法說明
 fn help() {
@@ -27,8 +37,14 @@ fn help() {
     println!("  --ddns               只運行 DDNS 更新服務");
     println!("  --web                只運行 Web 伺服器");
     println!("  --log=<level>        設置日誌級別 (debug, info, warn, error)");
+    println!("  --log-format=<fmt>   設置日誌格式 (text, json)");
     println!("  --port=<port>        設置 Web 伺服器端口");
     println!("  --host=<host>        設置 Web 伺服器主機地址");
+    println!("  --trace=<exporter>   設置追蹤導出方式 (otlp, stdout, none)");
+    println!("  --otlp-endpoint=<url> 設置 OTLP/HTTP 收集器地址");
+    println!("  --dry-run            檢測 IP 並列出每條記錄將執行的操作，不寫入 Cloudflare");
+    println!("  --once               檢測並更新一次所有記錄後退出（任一記錄更新失敗時退出碼為 1）");
+    println!("  --only=<a,b>         與 --once 或 --dry-run 一起使用，只處理指定的配置 ID、記錄名稱或記錄組");
     println!("  無參數                同時運行 DDNS 服務和 Web 伺服器");
 }
 
@@ -40,46 +56,6 @@ fn version() {
     println!("授權: MIT");
 }
 
-// 重啟 DDNS 服務的公共函數
-pub fn restart_ddns_service() {
-    if let Ok(mut ctrl) = DDNS_CONTROL.lock() {
-        if let Some(sender) = ctrl.take() {
-            // 發送停止信號給當前服務
-            let _ = sender.send(());
-        }
-        
-        // 啟動新的 DDNS 服務
-        start_ddns_service();
-    }
-}
-
-/// 啟動 DDNS 服務（作為獨立進程）
-fn start_ddns_service() {
-    // 使用獨立進程啟動 DDNS 服務
-    let mut command = process::Command::new(env::current_exe().unwrap());
-    
-    // 添加必要的環境變數和參數
-    command.env("RUN_MODE", "ddns");
-    
-    // 傳遞日誌級別
-    if let Ok(log_level) = env::var("RUST_LOG") {
-        command.env("RUST_LOG", log_level);
-    }
-    
-    // 添加 --ddns 參數
-    command.arg("--ddns");
-    
-    // 啟動進程
-    match command.spawn() {
-        Ok(child) => {
-            info!("DDNS service started in a separate process (PID: {})", child.id());
-        },
-        Err(e) => {
-            error!("Failed to start DDNS service process: {}", e);
-        }
-    }
-}
-
 /// 應用程式入口點
 /// 
 /// # 功能
@@ -93,6 +69,7 @@ fn start_ddns_service() {
 /// # 環境變數
 /// 
 /// - `RUST_LOG`: 日誌級別（默認：info）
+/// - `DDNS_LOG_FORMAT`: 日誌格式（text 或 json，默認：text）
 /// - `CLOUDFLARE_API_TOKEN`: Cloudflare API 令牌
 /// - `CLOUDFLARE_ZONE_ID`: Cloudflare 區域 ID
 /// - `CLOUDFLARE_RECORD_ID`: IPv4 DNS 記錄 ID
@@ -100,6 +77,9 @@ fn start_ddns_service() {
 /// - `CLOUDFLARE_RECORD_ID_V6`: IPv6 DNS 記錄 ID（可選）
 /// - `CLOUDFLARE_RECORD_NAME_V6`: IPv6 DNS 記錄名稱（可選）
 /// - `DDNS_UPDATE_INTERVAL`: 更新間隔（秒，默認：300）
+/// - `OTEL_TRACES_EXPORTER`: 追蹤導出方式（otlp、stdout 或 none，默認：none）
+/// - `OTEL_EXPORTER_OTLP_ENDPOINT`: OTLP/HTTP 收集器地址（默認：http://localhost:4318）
+/// - `OTEL_SERVICE_NAME`: 追蹤上報的服務名稱（默認：cloudflare-ddns）
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     // 載入 .env 檔案
@@ -115,8 +95,14 @@ async fn main() -> std::io::Result<()> {
     let mut run_ddns = false;
     let mut run_web = false;
     let mut log_level = None;
+    let mut log_format = None;
     let mut port = None;
     let mut host = None;
+    let mut trace_exporter = None;
+    let mut otlp_endpoint = None;
+    let mut dry_run = false;
+    let mut once = false;
+    let mut selectors = Vec::new();
     
     // 檢查是否有任何參數
     if args.len() > 1 {
@@ -132,6 +118,27 @@ async fn main() -> std::io::Result<()> {
                 run_ddns = true;
             } else if arg == "--web" {
                 run_web = true;
+            } else if arg == "--dry-run" {
+                dry_run = true;
+            } else if arg == "--once" {
+                once = true;
+            } else if arg.starts_with("--only=") {
+                selectors.extend(
+                    arg.trim_start_matches("--only=")
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|selector| !selector.is_empty())
+                        .map(str::to_string),
+                );
+            } else if arg.starts_with("--log-format=") {
+                match LogFormat::try_from(arg.trim_start_matches("--log-format=")) {
+                    Ok(format) => log_format = Some(format),
+                    Err(_) => {
+                        println!("錯誤: 無效的日誌格式");
+                        help();
+                        return Ok(());
+                    }
+                }
             } else if arg.starts_with("--log=") {
                 log_level = Some(arg.trim_start_matches("--log=").to_string());
             } else if arg.starts_with("--port=") {
@@ -144,6 +151,17 @@ async fn main() -> std::io::Result<()> {
                 }
             } else if arg.starts_with("--host=") {
                 host = Some(arg.trim_start_matches("--host=").to_string());
+            } else if arg.starts_with("--trace=") {
+                match TraceExporter::try_from(arg.trim_start_matches("--trace=")) {
+                    Ok(exporter) => trace_exporter = Some(exporter),
+                    Err(_) => {
+                        println!("錯誤: 無效的追蹤導出方式");
+                        help();
+                        return Ok(());
+                    }
+                }
+            } else if arg.starts_with("--otlp-endpoint=") {
+                otlp_endpoint = Some(arg.trim_start_matches("--otlp-endpoint=").to_string());
             } else {
                 println!("錯誤: 未知選項: {}", arg);
                 help();
@@ -160,14 +178,54 @@ async fn main() -> std::io::Result<()> {
         env::set_var("RUST_LOG", "info");
     }
     
-    // 初始化日誌系統
-    env_logger::builder().format_timestamp_millis().init();
+    // 初始化日誌系統，命令行參數優先於環境變數
+    let log_format = match log_format {
+        Some(format) => format,
+        None => LogFormat::from_env().unwrap_or_else(|e| {
+            println!("警告: {}，使用文本日誌格式", e);
+            LogFormat::Text
+        }),
+    };
+    init_logging(log_format);
+    
+    // 初始化追蹤系統，命令行參數優先於環境變數
+    let mut telemetry_settings = TelemetrySettings::from_env().unwrap_or_else(|e| {
+        warn!("追蹤設置無效，已停用追蹤: {}", e);
+        TelemetrySettings::default()
+    });
+    if let Some(exporter) = trace_exporter {
+        telemetry_settings.exporter = exporter;
+    }
+    if let Some(endpoint) = otlp_endpoint {
+        telemetry_settings.otlp_endpoint = Some(endpoint);
+    }
+    let _tracing_guard = match init_tracing(&telemetry_settings) {
+        Ok(guard) => guard,
+        Err(e) => {
+            error!("初始化追蹤系統失敗: {}", e);
+            None
+        }
+    };
+    
+    // 試運行和單次運行完成後直接退出，不啟動常駐服務
+    if dry_run || once {
+        let exit_code = if dry_run { run_dry_run(&selectors).await } else { run_once(&selectors).await };
+        // 退出前關閉追蹤系統以導出剩餘的 span
+        drop(_tracing_guard);
+        std::process::exit(exit_code);
+    }
+    if !selectors.is_empty() {
+        println!("錯誤: --only 只能與 --once 或 --dry-run 一起使用");
+        help();
+        return Ok(());
+    }
     
     // 如果同時指定了 --ddns 和 --web，或都沒有指定，則運行兩個服務
     if (run_ddns && run_web) || (!run_ddns && !run_web) {
-        // 在 Web 模式下運行
-        // 先啟動 DDNS 服務作為獨立進程
-        start_ddns_service();
+        // 在同一進程中運行 DDNS 更新任務和 Web 伺服器，
+        // 使更新事件和狀態可以直接通過 API 查詢
+        let service_factory = create_service_factory(true).await;
+        start_ddns_updates(&service_factory).await;
         
         // 載入設置
         let mut settings = Settings::new().expect("Failed to load settings");
@@ -183,7 +241,7 @@ async fn main() -> std::io::Result<()> {
         // 運行 Web 伺服器
         info!("Starting Web server at {}:{}", settings.server.host, settings.server.port);
         // 自定義優化的 Web 伺服器配置
-        run_optimized_web_server(&settings.server.host, settings.server.port).await?;
+        run_optimized_web_server(service_factory, &settings.server.host, settings.server.port).await?;
     } else if run_ddns {
         // 只運行 DDNS 服務
         return run_ddns_service().await;
@@ -202,82 +260,182 @@ async fn main() -> std::io::Result<()> {
         
         info!("Starting Web server at {}:{}", settings.server.host, settings.server.port);
         // 使用優化的 Web 伺服器配置
-        return run_optimized_web_server(&settings.server.host, settings.server.port).await;
+        let service_factory = create_service_factory(true).await;
+        return run_optimized_web_server(service_factory, &settings.server.host, settings.server.port).await;
     }
     
     Ok(())
 }
 
-/// 運行 DDNS 服務
-async fn run_ddns_service() -> std::io::Result<()> {
-    info!("Starting DDNS service...");
-    
-    // 建立服務工廠
-    let service_factory = Arc::new(ServiceFactory::new());
-    
-    // 初始化事件監聽系統
-    service_factory.init_event_listeners().await;
-    
-    // 從環境變數和配置文件載入配置
-    let mut configs = Vec::new();
-    
-    // 從環境變數載入配置
-    match load_ddns_configs_from_env() {
-        Ok(env_configs) => configs.extend(env_configs),
+/// 創建服務工廠並初始化事件監聽系統
+///
+/// 配置文件中的 `http` 段在創建時應用，設置無效時使用默認 HTTP 客戶端；
+/// 環境變數中的 DDNS 配置交給服務工廠，每次重新加載配置時保留
+///
+/// # 參數
+///
+/// - `watch_config`: 是否監視配置文件變更並在沒有配置時創建示例配置（單次運行和試運行時關閉）
+async fn create_service_factory(watch_config: bool) -> Arc<ServiceFactory> {
+    let http_settings = match FileConfigRepository::new().load_document().await {
+        Ok(document) => document.http,
         Err(e) => {
-            warn!("Failed to load DDNS configuration from environment: {}", e);
+            warn!("讀取 HTTP 客戶端設置失敗，使用默認設置: {}", e);
+            HttpClientSettings::default()
         }
     };
-
-    // 從配置文件載入配置
-    match service_factory.get_config_service().get_configs().await {
-        Ok(file_configs) => configs.extend(file_configs),
+    let service_factory = match ServiceFactory::with_http_settings(&http_settings) {
+        Ok(factory) => factory,
         Err(e) => {
-            warn!("Failed to load DDNS configuration from file: {}", e);
+            error!("HTTP 客戶端設置無效，使用默認設置: {}", e);
+            ServiceFactory::new()
         }
     };
+    let env_configs = load_ddns_configs_from_env().unwrap_or_else(|e| {
+        warn!("Failed to load DDNS configuration from environment: {}", e);
+        Vec::new()
+    });
+    let service_factory = Arc::new(
+        service_factory
+            .with_config_watching(watch_config)
+            .with_env_configs(env_configs)
+    );
+    service_factory.init_event_listeners().await;
+    info!("事件系統已初始化");
+    service_factory
+}
 
-    if configs.is_empty() {
-        error!("No available DDNS configurations, service exiting");
-        return Ok(());
+/// 獲取可用的 DDNS 配置數量（配置文件和環境變數中的配置已由服務工廠載入）
+///
+/// # 返回
+///
+/// - `usize`: 可用的 DDNS 配置數量
+async fn load_ddns_services(service_factory: &Arc<ServiceFactory>) -> usize {
+    service_factory.list_ddns_services().await.len()
+}
+
+/// 載入環境變數配置並啟動所有 DDNS 自動更新任務
+///
+/// # 返回
+///
+/// - `bool`: 是否有可用的 DDNS 配置
+async fn start_ddns_updates(service_factory: &Arc<ServiceFactory>) -> bool {
+    let service_count = load_ddns_services(service_factory).await;
+    if service_count == 0 {
+        warn!("No available DDNS configurations");
+        return false;
     }
     
-    info!("Successfully loaded {} DDNS configurations", configs.len());
+    info!("Successfully loaded {} DDNS configurations", service_count);
+    service_factory.start_auto_update().await;
+    true
+}
+
+/// 試運行：輸出每條記錄的當前內容、期望內容和將執行的操作
+///
+/// # 參數
+///
+/// - `selectors`: 記錄選擇器，為空時試運行所有記錄
+///
+/// # 返回
+///
+/// - `i32`: 進程退出碼
+async fn run_dry_run(selectors: &[String]) -> i32 {
+    let service_factory = create_service_factory(false).await;
+    if load_ddns_services(&service_factory).await == 0 {
+        error!("No available DDNS configurations");
+        return EXIT_NO_RECORDS;
+    }
     
-    // 創建信號通道用於等待終止信號
-    let (tx, rx) = mpsc::channel();
+    let plan = service_factory.plan_dns_records(selectors).await;
+    if plan.is_empty() {
+        error!("沒有匹配的 DDNS 記錄");
+        return EXIT_NO_RECORDS;
+    }
     
-    // 更新全局控制變量
-    if let Ok(mut ctrl) = DDNS_CONTROL.lock() {
-        *ctrl = Some(tx);
+    println!("{:<6}  {:<5}  {:<30}  {:<24}  DESIRED", "ACTION", "TYPE", "RECORD", "CURRENT");
+    for entry in &plan {
+        let desired = match (&entry.desired_content, &entry.error) {
+            (_, Some(error)) => error.clone(),
+            (Some(desired), None) => desired.clone(),
+            (None, None) => "-".to_string(),
+        };
+        println!(
+            "{:<6}  {:<5}  {:<30}  {:<24}  {}",
+            entry.action,
+            entry.record_type,
+            entry.record_name,
+            entry.current_content.as_deref().unwrap_or("-"),
+            desired,
+        );
     }
     
-    // 啟動所有配置的 DDNS 服務
-    let mut service_handles = Vec::new();
+    let changes = plan.iter().filter(|entry| entry.has_changes()).count();
+    let failed = plan.iter().filter(|entry| entry.error.is_some()).count();
+    println!();
+    println!("{} 條記錄，{} 條將變更，{} 條無法確定（未寫入任何記錄）", plan.len(), changes, failed);
+    if failed == 0 { EXIT_SUCCESS } else { EXIT_FAILED }
+}
+
+/// 單次運行：檢測並更新一次選中的記錄，輸出摘要
+///
+/// # 參數
+///
+/// - `selectors`: 記錄選擇器，為空時更新所有記錄
+///
+/// # 返回
+///
+/// - `i32`: 進程退出碼
+async fn run_once(selectors: &[String]) -> i32 {
+    let service_factory = create_service_factory(false).await;
+    if load_ddns_services(&service_factory).await == 0 {
+        error!("No available DDNS configurations");
+        return EXIT_NO_RECORDS;
+    }
     
-    for ddns_config in configs {
-        let ip_type = ddns_config.ip_type.clone();
-        info!("Starting {} DDNS update service", ip_type);
-        
-        // 建立 DDNS 應用服務
-        let ddns_service = service_factory.create_ddns_service(ddns_config).await;
-        
-        // 啟動自動更新任務
-        let handle = tokio::spawn(async move {
-            ddns_service.start_auto_update().await;
-        });
-        
-        service_handles.push(handle);
+    let outcomes = service_factory.update_dns_records_once(selectors).await;
+    if outcomes.is_empty() {
+        error!("沒有匹配的 DDNS 記錄");
+        return EXIT_NO_RECORDS;
+    }
+    
+    println!("{:<9}  {:<5}  {:<30}  CONTENT", "RESULT", "TYPE", "RECORD");
+    for outcome in &outcomes {
+        let (result, detail) = match (&outcome.error, &outcome.content) {
+            (Some(error), _) => ("failed", error.as_str()),
+            (None, content) if outcome.updated => ("updated", content.as_deref().unwrap_or("-")),
+            (None, content) => ("unchanged", content.as_deref().unwrap_or("-")),
+        };
+        println!("{:<9}  {:<5}  {:<30}  {}", result, outcome.record_type, outcome.record_name, detail);
+    }
+    
+    let updated = outcomes.iter().filter(|outcome| outcome.updated).count();
+    let failed = outcomes.iter().filter(|outcome| outcome.failed()).count();
+    println!();
+    println!("{} 條記錄，{} 條已更新，{} 條未變化，{} 條失敗", outcomes.len(), updated, outcomes.len() - updated - failed, failed);
+    
+    // 等待事件監聽器和通知發送完成後再退出
+    if tokio::time::timeout(BACKGROUND_TASKS_TIMEOUT, background_tasks().wait_idle()).await.is_err() {
+        warn!("等待 {} 個通知任務超時，直接退出", background_tasks().pending());
+    }
+    if failed == 0 { EXIT_SUCCESS } else { EXIT_FAILED }
+}
+
+/// 運行 DDNS 服務
+async fn run_ddns_service() -> std::io::Result<()> {
+    info!("Starting DDNS service...");
+    
+    let service_factory = create_service_factory(true).await;
+    
+    if !start_ddns_updates(&service_factory).await {
+        error!("No available DDNS configurations, service exiting");
+        return Ok(());
     }
     
     // 等待終止信號
-    let _ = rx.recv();
+    tokio::signal::ctrl_c().await?;
     info!("接收到終止信號，DDNS服務正在關閉");
     
-    // 等待所有任務完成
-    for handle in service_handles {
-        let _ = handle.await;
-    }
+    service_factory.stop_auto_update().await;
     
     info!("DDNS服務已關閉");
     Ok(())
@@ -299,12 +457,21 @@ fn load_ddns_configs_from_env() -> Result<Vec<DdnsConfig>, String> {
             .unwrap_or(300);
         
         let ipv4_config = DdnsConfig {
+            id: ConfigId::new("env-ipv4"),
             api_token,
             zone_id,
             record_id,
             record_name,
             update_interval,
             ip_type: IpType::IPv4,
+            hooks: Default::default(),
+            source: Default::default(),
+            ipv6_loss: Default::default(),
+            prefix_delegation: None,
+            record_type: None,
+            content: None,
+            failover: None,
+            drift_check_interval: None,
         };
         
         configs.push(ipv4_config);
@@ -322,12 +489,21 @@ fn load_ddns_configs_from_env() -> Result<Vec<DdnsConfig>, String> {
             .unwrap_or(300);
         
         let ipv6_config = DdnsConfig {
+            id: ConfigId::new("env-ipv6"),
             api_token,
             zone_id,
             record_id,
             record_name,
             update_interval,
             ip_type: IpType::IPv6,
+            hooks: Default::default(),
+            source: Default::default(),
+            ipv6_loss: Default::default(),
+            prefix_delegation: None,
+            record_type: None,
+            content: None,
+            failover: None,
+            drift_check_interval: None,
         };
         
         configs.push(ipv6_config);
@@ -339,11 +515,10 @@ fn load_ddns_configs_from_env() -> Result<Vec<DdnsConfig>, String> {
 /// 優化的 Web 伺服器啟動函數
 /// 
 /// 添加更多性能優化如壓縮支持和連接保持活
-async fn run_optimized_web_server(host: &str, port: u16) -> std::io::Result<()> {
+async fn run_optimized_web_server(service_factory: Arc<ServiceFactory>, host: &str, port: u16) -> std::io::Result<()> {
     use cloudflare_ddns::{web, App, HttpServer, Cors};
     use cloudflare_ddns::constants::*;
     use std::path::Path;
-    use std::time::Duration;
     
     let address = format!("{}:{}", host, port);
     info!("準備在 {} 上啟動優化的 Web 伺服器", address);
@@ -363,16 +538,8 @@ async fn run_optimized_web_server(host: &str, port: u16) -> std::io::Result<()>
         info!("Found static files, web UI should be available");
     }
     
-    // 創建服務工廠
-    let service_factory = ServiceFactory::new();
-    let service_factory_arc = Arc::new(service_factory);
-    
-    // 初始化事件監聽系統
-    service_factory_arc.init_event_listeners().await;
-    info!("事件系統已初始化");
-    
     // 包裝為web::Data
-    let service_factory_data = web::Data::new(service_factory_arc);
+    let service_factory_data = web::Data::new(service_factory);
     
     HttpServer::new(move || {
         // 啟用 CORS
@@ -64,6 +64,27 @@
       });
     },
     
+    // 獲取單個配置
+    async getConfig(id) {
+      return this.request(`/configs/${encodeURIComponent(id)}`);
+    },
+    
+    // 替換單個配置
+    async updateConfig(id, config) {
+      return this.request(`/configs/${encodeURIComponent(id)}`, {
+        method: 'PUT',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify(config)
+      });
+    },
+    
+    // 刪除單個配置
+    async deleteConfig(id) {
+      return this.request(`/configs/${encodeURIComponent(id)}`, {
+        method: 'DELETE'
+      });
+    },
+    
     // 立即更新DDNS記錄
     async updateDdns() {
       return this.request('/update', {
@@ -76,6 +97,21 @@
       return this.request('/status');
     },
     
+    // 訂閱服務器事件流，返回 EventSource 以便調用方關閉
+    subscribeEvents(handlers) {
+      const source = new EventSource(`${API_BASE}/events`);
+      Object.entries(handlers).forEach(([eventName, handler]) => {
+        source.addEventListener(eventName, event => {
+          try {
+            handler(JSON.parse(event.data));
+          } catch (error) {
+            console.error(`處理事件 ${eventName} 失敗:`, error);
+          }
+        });
+      });
+      return source;
+    },
+    
     // 驗證配置
     async validateConfig(config) {
       return this.request('/configs/validate', {
@@ -101,6 +137,7 @@
     // 轉換API配置為前端顯示格式
     adaptConfig(config) {
       return {
+        id: config.id || '',
         record_id: config.record_id || '',
         zone_id: config.zone_id || '',
         api_token: config.api_token || '',
@@ -109,6 +146,7 @@
         ip_type: config.ip_type || 'ipv4',
         current_ip: config.current_ip || '未知',
         update_interval: config.update_interval || 300,
+        hooks: config.hooks,
         last_update_time: config.last_update_time || null,
         status: 'active' // 默認狀態
       };
@@ -117,12 +155,15 @@
     // 轉換前端表單數據為API格式
     prepareConfigForSave(formData) {
       return {
+        id: formData.id || undefined,
         record_id: formData.record_id,
         zone_id: formData.zone_id,
         api_token: formData.api_token,
         record_name: formData.record_name,
         ip_type: formData.record_type === 'A' ? 'ipv4' : 'ipv6',
-        update_interval: parseInt(formData.update_interval) || 300
+        update_interval: parseInt(formData.update_interval) || 300,
+        // 鉤子只能在配置文件中編輯，保存時原樣保留
+        hooks: formData.hooks || undefined
       };
     },
     
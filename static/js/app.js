@@ -116,6 +116,10 @@ function initApp() {
         console.log("初始化連接監測...");
         initConnectionMonitor();
         
+        // 訂閱服務器事件流
+        console.log("初始化事件流...");
+        initEventStream();
+        
         // 延遲加載初始數據，確保UI已準備好
         console.log("準備加載初始數據...");
         setTimeout(loadInitialData, 1000);
@@ -253,6 +257,36 @@ function initConnectionMonitor() {
     console.log("連接監視器初始化完成");
 }
 
+// 事件流訂閱，收到更新事件時即時刷新數據
+function initEventStream() {
+    if (!window.EventSource) {
+        console.log("瀏覽器不支持 EventSource，使用輪詢刷新");
+        return;
+    }
+    
+    ApiClient.subscribeEvents({
+        'ip-changed': data => {
+            NotificationManager.showSuccess(`${data.record.record_name} IP 已變更為 ${data.new_ip}`);
+            loadDashboardData();
+        },
+        'update-failed': data => {
+            NotificationManager.showError(`${data.record.record_name} 更新失敗: ${data.error}`);
+            loadDashboardData();
+        },
+        'drift-detected': data => {
+            NotificationManager.showError(`${data.record.record_name} 記錄被修改為 ${data.actual_ip}，正在恢復`);
+        },
+        'hook-executed': data => {
+            if (!data.success) {
+                NotificationManager.showError(`${data.record.record_name} ${data.stage}-update 鉤子執行失敗`);
+            }
+        },
+        'update-succeeded': () => loadDashboardData(),
+        'config-changed': () => loadInitialData(),
+        'restart': () => loadInitialData()
+    });
+}
+
 // 更新連接狀態UI
 function updateConnectionStatus(isConnected) {
     const statusIndicator = document.getElementById('connection-status');
@@ -739,13 +773,14 @@ async function deleteRecord(recordId) {
     try {
         LoadingManager.show('正在刪除記錄...');
         
-        // 過濾掉要刪除的記錄
-        const newConfigs = currentConfigs.filter(c => c.record_id !== recordId);
+        // 只刪除指定的記錄，不影響其他記錄
+        const config = currentConfigs.find(c => c.record_id === recordId);
+        if (!config) {
+            throw new Error('找不到指定的記錄');
+        }
+        const newConfigs = currentConfigs.filter(c => c !== config);
         
-        // 保存新的配置列表
-        const result = await ApiClient.saveConfigs(
-            newConfigs.map(c => DataAdapter.prepareConfigForSave(c))
-        );
+        const result = await ApiClient.deleteConfig(config.id);
         
         if (result.success) {
             NotificationManager.showSuccess('記錄已成功刪除');
@@ -851,21 +886,21 @@ async function saveRecord() {
         
         // 更新或添加配置
         let newConfigs;
-        
-        if (recordId) {
-            // 更新現有配置
-            newConfigs = currentConfigs.map(c => 
-                c.record_id === recordId ? {...c, ...formData} : c
-            );
+        let result;
+        const existing = recordId ? currentConfigs.find(c => c.record_id === recordId) : null;
+        
+        if (existing && existing.id) {
+            // 更新現有配置，只替換這一條記錄
+            const merged = {...existing, ...formData};
+            newConfigs = currentConfigs.map(c => c === existing ? merged : c);
+            result = await ApiClient.updateConfig(existing.id, DataAdapter.prepareConfigForSave(merged));
         } else {
             // 添加新配置
             newConfigs = [...currentConfigs, formData];
+            const apiConfigs = newConfigs.map(c => DataAdapter.prepareConfigForSave(c));
+            result = await ApiClient.saveConfigs(apiConfigs);
         }
         
-        // 保存配置
-        const apiConfigs = newConfigs.map(c => DataAdapter.prepareConfigForSave(c));
-        const result = await ApiClient.saveConfigs(apiConfigs);
-        
         if (result.success) {
             NotificationManager.showSuccess(recordId ? '記錄更新成功' : '記錄創建成功');
             
@@ -0,0 +1,35 @@
+use cloudflare_ddns::application::background::background_tasks;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(test)]
+mod background_task_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_wait_idle_waits_for_nested_tasks() {
+        let finished = Arc::new(AtomicBool::new(false));
+        let flag = finished.clone();
+        background_tasks().spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            // 任務中再啟動的任務同樣需要等待
+            background_tasks().spawn(async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                flag.store(true, Ordering::SeqCst);
+            });
+        });
+
+        tokio::time::timeout(Duration::from_secs(5), background_tasks().wait_idle()).await.unwrap();
+        assert!(finished.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_panicking_task_is_not_counted() {
+        background_tasks().spawn(async {
+            panic!("task failed");
+        });
+
+        tokio::time::timeout(Duration::from_secs(5), background_tasks().wait_idle()).await.unwrap();
+    }
+}
@@ -1,5 +1,5 @@
 use cloudflare_ddns::application::ddns::DdnsApplicationService;
-use cloudflare_ddns::domain::config::{DdnsConfig, IpType};
+use cloudflare_ddns::domain::config::{ConfigId, DdnsConfig, IpType};
 use cloudflare_ddns::domain::dns::{DnsRecord, DnsService, DnsUpdateResult};
 use cloudflare_ddns::domain::ip::IpService;
 use cloudflare_ddns::domain::state::{StateRepository, StateEntry};
@@ -18,6 +18,8 @@ mock! {
         async fn get_record(&self, zone_id: &str, record_id: &str) -> Result<DnsRecord, DomainError>;
         async fn get_records(&self, zone_id: &str) -> Result<Vec<DnsRecord>, DomainError>;
         async fn create_record(&self, zone_id: &str, record: DnsRecord) -> Result<DnsRecord, DomainError>;
+        async fn find_records(&self, zone_id: &str, name: &str, record_type: &str) -> Result<Vec<DnsRecord>, DomainError>;
+        async fn delete_record(&self, zone_id: &str, record_id: &str) -> Result<(), DomainError>;
     }
 }
 
@@ -34,12 +36,44 @@ mock! {
     pub StateMock {}
     #[async_trait::async_trait]
     impl StateRepository for StateMock {
-        async fn get_last_ip(&self, config_id: &str) -> Result<Option<String>, DomainError>;
-        async fn set_last_ip(&self, config_id: &str, ip: &str) -> Result<(), DomainError>;
-        async fn get_last_update_time(&self, config_id: &str) -> Result<Option<DateTime<Utc>>, DomainError>;
-        async fn set_last_update_time(&self, config_id: &str, time: DateTime<Utc>) -> Result<(), DomainError>;
-        async fn get_state(&self, config_id: &str) -> Result<Option<StateEntry>, DomainError>;
-        async fn set_state(&self, config_id: &str, state: StateEntry) -> Result<(), DomainError>;
+        async fn get_last_ip(&self, config_id: &ConfigId) -> Result<Option<String>, DomainError>;
+        async fn set_last_ip(&self, config_id: &ConfigId, ip: &str) -> Result<(), DomainError>;
+        async fn get_last_update_time(&self, config_id: &ConfigId) -> Result<Option<DateTime<Utc>>, DomainError>;
+        async fn set_last_update_time(&self, config_id: &ConfigId, time: DateTime<Utc>) -> Result<(), DomainError>;
+        async fn set_check_result(&self, config_id: &ConfigId, time: DateTime<Utc>, error: Option<String>) -> Result<(), DomainError>;
+        async fn get_state(&self, config_id: &ConfigId) -> Result<Option<StateEntry>, DomainError>;
+        async fn set_state(&self, config_id: &ConfigId, state: StateEntry) -> Result<(), DomainError>;
+    }
+}
+
+// 基礎測試配置，其他測試模塊用結構體更新語法覆蓋需要的欄位
+fn create_test_config() -> DdnsConfig {
+    DdnsConfig {
+        id: ConfigId::new("test_config"),
+        api_token: "test_token".to_string(),
+        zone_id: "test_zone".to_string(),
+        record_id: "test_record".to_string(),
+        record_name: "test.example.com".to_string(),
+        update_interval: 300,
+        ip_type: IpType::IPv4,
+        hooks: Default::default(),
+        source: Default::default(),
+        ipv6_loss: Default::default(),
+        prefix_delegation: None,
+        record_type: None,
+        content: None,
+        failover: None,
+        drift_check_interval: None,
+    }
+}
+
+// 主機名為 home.example.com 的測試配置
+fn home_config() -> DdnsConfig {
+    DdnsConfig {
+        id: ConfigId::new("home"),
+        record_id: "home_record".to_string(),
+        record_name: "home.example.com".to_string(),
+        ..create_test_config()
     }
 }
 
@@ -48,17 +82,6 @@ mod ddns_application_service_tests {
     use super::*;
     
 
-    fn create_test_config() -> DdnsConfig {
-        DdnsConfig {
-            api_token: "test_token".to_string(),
-            zone_id: "test_zone".to_string(),
-            record_id: "test_record".to_string(),
-            record_name: "test.example.com".to_string(),
-            update_interval: 300,
-            ip_type: IpType::IPv4,
-        }
-    }
-
     #[tokio::test]
     async fn test_get_current_ip_ipv4() {
         let mut ip_mock = MockIpMock::new();
@@ -126,7 +149,7 @@ mod ddns_application_service_tests {
             });
         
         let mut state_mock = MockStateMock::new();
-        let config_id = "test_zone-test_record".to_string();
+        let config_id = ConfigId::new("test_config");
         
         state_mock.expect_get_last_ip()
             .with(eq(config_id.clone()))
@@ -142,6 +165,11 @@ mod ddns_application_service_tests {
             .times(1)
             .returning(|_, _| Ok(()));
         
+        state_mock.expect_set_check_result()
+            .with(eq(config_id.clone()), always(), eq(None))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        
         let config = create_test_config();
         
         let service = DdnsApplicationService::new(
@@ -167,16 +195,32 @@ mod ddns_application_service_tests {
             .times(1)
             .returning(move || Ok(current_ip.to_string()));
         
-        let dns_mock = MockDnsMock::new();
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_get_record()
+            .with(eq("test_zone"), eq("test_record"))
+            .times(1)
+            .returning(move |_, _| Ok(DnsRecord {
+                id: Some("test_record".to_string()),
+                name: "test.example.com".to_string(),
+                record_type: "A".to_string(),
+                content: current_ip.to_string(),
+                ttl: 120,
+                proxied: false,
+            }));
         
         let mut state_mock = MockStateMock::new();
-        let config_id = "test_zone-test_record".to_string();
+        let config_id = ConfigId::new("test_config");
         
         state_mock.expect_get_last_ip()
             .with(eq(config_id.clone()))
             .times(1)
             .returning(move |_| Ok(Some(current_ip.to_string())));
         
+        state_mock.expect_set_check_result()
+            .with(eq(config_id.clone()), always(), eq(None))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        
         let config = create_test_config();
         
         let service = DdnsApplicationService::new(
@@ -213,7 +257,7 @@ mod ddns_application_service_tests {
             });
         
         let mut state_mock = MockStateMock::new();
-        let config_id = "test_zone-test_record".to_string();
+        let config_id = ConfigId::new("test_config");
         
         state_mock.expect_get_last_ip()
             .with(eq(config_id.clone()))
@@ -229,6 +273,11 @@ mod ddns_application_service_tests {
             .times(1)
             .returning(|_, _| Ok(()));
         
+        state_mock.expect_set_check_result()
+            .with(eq(config_id.clone()), always(), eq(None))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        
         let config = create_test_config();
         
         let service = DdnsApplicationService::new(
@@ -265,7 +314,7 @@ mod ddns_application_service_tests {
             });
         
         let mut state_mock = MockStateMock::new();
-        let config_id = "test_zone-test_record".to_string();
+        let config_id = ConfigId::new("test_config");
         
         state_mock.expect_get_last_ip()
             .with(eq(config_id.clone()))
@@ -280,6 +329,11 @@ mod ddns_application_service_tests {
             .times(1)
             .returning(|_, _| Ok(()));
         
+        state_mock.expect_set_check_result()
+            .with(eq(config_id.clone()), always(), eq(None))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        
         let config = create_test_config();
         
         let service = DdnsApplicationService::new(
@@ -295,4 +349,1200 @@ mod ddns_application_service_tests {
         assert_eq!(domain, "test.example.com");
         assert_eq!(ip, current_ip);
     }
-} 
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_update_failure_is_recorded() {
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4()
+            .times(1)
+            .returning(|| Err(DomainError::network("connection refused")));
+        
+        let dns_mock = MockDnsMock::new();
+        
+        let mut state_mock = MockStateMock::new();
+        state_mock.expect_set_check_result()
+            .withf(|config_id, _, error| {
+                config_id.as_str() == "test_config"
+                    && error.as_deref().is_some_and(|e| e.contains("connection refused"))
+            })
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        
+        let service = DdnsApplicationService::new(
+            Arc::new(dns_mock),
+            Arc::new(ip_mock),
+            Arc::new(state_mock),
+            create_test_config(),
+        );
+        
+        assert!(service.update_dns_record().await.is_err());
+    }
+}
+
+#[cfg(test)]
+mod ddns_event_tests {
+    use super::*;
+    use cloudflare_ddns::application::events::{Event, EventManager};
+    use cloudflare_ddns::infrastructure::state::InMemoryStateRepository;
+
+    #[tokio::test]
+    async fn test_drift_is_detected_and_restored() {
+        let current_ip = "192.168.1.1";
+        
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4()
+            .returning(move || Ok(current_ip.to_string()));
+        
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_get_record()
+            .times(1)
+            .returning(|_, _| Ok(DnsRecord {
+                id: Some("test_record".to_string()),
+                name: "test.example.com".to_string(),
+                record_type: "A".to_string(),
+                content: "10.0.0.1".to_string(),
+                ttl: 120,
+                proxied: false,
+            }));
+        dns_mock.expect_update_record()
+            .times(1)
+            .returning(|record| Ok(DnsUpdateResult { record, updated: true }));
+        
+        let state = Arc::new(InMemoryStateRepository::new());
+        let config = DdnsConfig {
+            id: ConfigId::new("drift_config"),
+            api_token: "test_token".to_string(),
+            zone_id: "test_zone".to_string(),
+            record_id: "test_record".to_string(),
+            record_name: "test.example.com".to_string(),
+            update_interval: 300,
+            ip_type: IpType::IPv4,
+            hooks: Default::default(),
+            source: Default::default(),
+            ipv6_loss: Default::default(),
+            prefix_delegation: None,
+            record_type: None,
+            content: None,
+            failover: None,
+            drift_check_interval: None,
+        };
+        state.set_last_ip(&config.id, current_ip).await.unwrap();
+        
+        let event_manager = Arc::new(EventManager::new());
+        let mut receiver = event_manager.subscribe_all();
+        
+        let service = DdnsApplicationService::new(
+            Arc::new(dns_mock),
+            Arc::new(ip_mock),
+            state,
+            config,
+        ).with_event_manager(event_manager);
+        
+        let result = service.update_dns_record().await.unwrap();
+        assert!(result.updated);
+        
+        match receiver.recv().await.unwrap() {
+            Event::DriftDetected(event) => {
+                assert_eq!(event.expected_ip, current_ip);
+                assert_eq!(event.actual_ip, "10.0.0.1");
+            },
+            other => panic!("應該收到 DriftDetected，實際為 {:?}", other),
+        }
+        
+        // IP 本身未變更，只發布更新成功事件
+        match receiver.recv().await.unwrap() {
+            Event::UpdateSucceeded(event) => assert_eq!(event.ip, current_ip),
+            other => panic!("應該收到 UpdateSucceeded，實際為 {:?}", other),
+        }
+        assert!(receiver.try_recv().is_err());
+    }
+
+    async fn unchanged_service(dns_mock: MockDnsMock, drift_check_interval: Option<u64>) -> DdnsApplicationService {
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().returning(|| Ok("192.168.1.1".to_string()));
+
+        let state = Arc::new(InMemoryStateRepository::new());
+        let config = DdnsConfig {
+            id: ConfigId::new("drift_interval_config"),
+            api_token: "test_token".to_string(),
+            zone_id: "test_zone".to_string(),
+            record_id: "test_record".to_string(),
+            record_name: "test.example.com".to_string(),
+            update_interval: 300,
+            ip_type: IpType::IPv4,
+            hooks: Default::default(),
+            source: Default::default(),
+            ipv6_loss: Default::default(),
+            prefix_delegation: None,
+            record_type: None,
+            content: None,
+            failover: None,
+            drift_check_interval,
+        };
+        state.set_last_ip(&config.id, "192.168.1.1").await.unwrap();
+        DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(ip_mock), state, config)
+    }
+
+    fn unchanged_record() -> DnsRecord {
+        DnsRecord {
+            id: Some("test_record".to_string()),
+            name: "test.example.com".to_string(),
+            record_type: "A".to_string(),
+            content: "192.168.1.1".to_string(),
+            ttl: 120,
+            proxied: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drift_check_runs_on_its_own_interval() {
+        // 默認間隔為一小時，連續多次檢查只查詢一次記錄
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_get_record().times(1).returning(|_, _| Ok(unchanged_record()));
+        let service = unchanged_service(dns_mock, None).await;
+
+        for _ in 0..3 {
+            assert!(!service.update_dns_record().await.unwrap().updated);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drift_check_can_be_disabled() {
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_get_record().never();
+        let service = unchanged_service(dns_mock, Some(0)).await;
+
+        assert!(!service.update_dns_record().await.unwrap().updated);
+    }
+}
+
+#[cfg(test)]
+mod ddns_status_tests {
+    use super::*;
+    use cloudflare_ddns::application::ddns::RecordHealth;
+    use cloudflare_ddns::infrastructure::state::InMemoryStateRepository;
+
+    fn create_service(state: Arc<InMemoryStateRepository>, ip_result: Result<String, DomainError>) -> DdnsApplicationService {
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().returning(move || ip_result.clone());
+        
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_update_record()
+            .returning(|record| Ok(DnsUpdateResult { record, updated: true }));
+        
+        DdnsApplicationService::new(
+            Arc::new(dns_mock),
+            Arc::new(ip_mock),
+            state,
+            DdnsConfig {
+                id: ConfigId::new("status_config"),
+                api_token: "test_token".to_string(),
+                zone_id: "test_zone".to_string(),
+                record_id: "test_record".to_string(),
+                record_name: "status.example.com".to_string(),
+                update_interval: 300,
+                ip_type: IpType::IPv4,
+                hooks: Default::default(),
+                source: Default::default(),
+                ipv6_loss: Default::default(),
+                prefix_delegation: None,
+                record_type: None,
+                content: None,
+                failover: None,
+                drift_check_interval: None,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_status_pending_before_first_check() {
+        let service = create_service(Arc::new(InMemoryStateRepository::new()), Ok("192.168.1.1".to_string()));
+        
+        let status = service.status_snapshot().await.unwrap();
+        assert_eq!(status.health, RecordHealth::Pending);
+        assert_eq!(status.record_name, "status.example.com");
+        assert!(status.last_ip.is_none());
+        assert!(status.next_check.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_status_healthy_after_successful_check() {
+        let service = create_service(Arc::new(InMemoryStateRepository::new()), Ok("192.168.1.1".to_string()));
+        service.update_dns_record().await.unwrap();
+        
+        let status = service.status_snapshot().await.unwrap();
+        assert_eq!(status.health, RecordHealth::Healthy);
+        assert_eq!(status.last_ip.as_deref(), Some("192.168.1.1"));
+        assert!(status.last_update.is_some());
+        assert_eq!(
+            status.next_check.unwrap() - status.last_check.unwrap(),
+            chrono::Duration::seconds(300)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_status_failing_after_error_and_recovers() {
+        let state = Arc::new(InMemoryStateRepository::new());
+        
+        let failing = create_service(state.clone(), Err(DomainError::network("timeout")));
+        assert!(failing.update_dns_record().await.is_err());
+        
+        let status = failing.status_snapshot().await.unwrap();
+        assert_eq!(status.health, RecordHealth::Failing);
+        assert!(status.last_error.unwrap().contains("timeout"));
+        assert!(status.last_error_time.is_some());
+        
+        let recovered = create_service(state, Ok("192.168.1.1".to_string()));
+        recovered.update_dns_record().await.unwrap();
+        
+        let status = recovered.status_snapshot().await.unwrap();
+        assert_eq!(status.health, RecordHealth::Healthy);
+        assert!(status.last_error.is_none());
+        assert!(status.last_error_time.is_some());
+    }
+}
+
+#[cfg(test)]
+mod ddns_hook_tests {
+    use super::*;
+    use cloudflare_ddns::application::events::{Event, EventManager};
+    use cloudflare_ddns::domain::config::{HookCommand, HookSettings};
+    use cloudflare_ddns::domain::hook::{HookOutput, HookRunner, HookStage};
+    use cloudflare_ddns::infrastructure::state::InMemoryStateRepository;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// 鉤子命令及其環境變量
+    type HookCall = (String, Vec<(String, String)>);
+
+    // 記錄調用並返回固定退出碼的鉤子執行器
+    struct RecordingHookRunner {
+        exit_code: i32,
+        calls: Mutex<Vec<HookCall>>,
+    }
+
+    impl RecordingHookRunner {
+        fn new(exit_code: i32) -> Arc<Self> {
+            Arc::new(Self { exit_code, calls: Mutex::new(Vec::new()) })
+        }
+
+        fn env_of(&self, index: usize, key: &str) -> Option<String> {
+            let calls = self.calls.lock().unwrap();
+            calls[index].1.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl HookRunner for RecordingHookRunner {
+        async fn run(&self, hook: &HookCommand, env: &[(String, String)]) -> Result<HookOutput, DomainError> {
+            self.calls.lock().unwrap().push((hook.command.clone(), env.to_vec()));
+            Ok(HookOutput {
+                exit_code: Some(self.exit_code),
+                stdout: "done".to_string(),
+                stderr: String::new(),
+                duration: Duration::from_millis(5),
+                timed_out: false,
+            })
+        }
+    }
+
+    fn create_config(hooks: HookSettings) -> DdnsConfig {
+        DdnsConfig {
+            id: ConfigId::new("hook_config"),
+            api_token: "test_token".to_string(),
+            zone_id: "test_zone".to_string(),
+            record_id: "test_record".to_string(),
+            record_name: "vpn.example.com".to_string(),
+            update_interval: 300,
+            ip_type: IpType::IPv4,
+            hooks,
+            source: Default::default(),
+            ipv6_loss: Default::default(),
+            prefix_delegation: None,
+            record_type: None,
+            content: None,
+            failover: None,
+            drift_check_interval: None,
+        }
+    }
+
+    fn create_hooks(abort_on_pre_hook_failure: bool) -> HookSettings {
+        HookSettings {
+            pre_update: Some(HookCommand::new("pre.sh")),
+            post_update: Some(HookCommand::new("post.sh")),
+            abort_on_pre_hook_failure,
+        }
+    }
+
+    fn create_service(config: DdnsConfig, update_times: usize, runner: Arc<RecordingHookRunner>) -> (DdnsApplicationService, Arc<InMemoryStateRepository>) {
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().returning(|| Ok("203.0.113.7".to_string()));
+
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_update_record()
+            .times(update_times)
+            .returning(|record| Ok(DnsUpdateResult { record, updated: true }));
+
+        let state = Arc::new(InMemoryStateRepository::new());
+        let service = DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(ip_mock), state.clone(), config)
+            .with_hook_runner(runner);
+        (service, state)
+    }
+
+    #[tokio::test]
+    async fn test_hooks_receive_ip_environment() {
+        let runner = RecordingHookRunner::new(0);
+        let (service, state) = create_service(create_config(create_hooks(true)), 1, runner.clone());
+        state.set_last_ip(&ConfigId::new("hook_config"), "198.51.100.1").await.unwrap();
+
+        service.update_dns_record().await.unwrap();
+
+        assert_eq!(runner.calls.lock().unwrap().len(), 2);
+        assert_eq!(runner.env_of(0, "DDNS_HOOK_STAGE").as_deref(), Some("pre"));
+        assert_eq!(runner.env_of(0, "DDNS_OLD_IP").as_deref(), Some("198.51.100.1"));
+        assert_eq!(runner.env_of(0, "DDNS_NEW_IP").as_deref(), Some("203.0.113.7"));
+        assert_eq!(runner.env_of(0, "DDNS_RECORD_NAME").as_deref(), Some("vpn.example.com"));
+        assert_eq!(runner.env_of(0, "DDNS_RECORD_TYPE").as_deref(), Some("A"));
+        assert_eq!(runner.env_of(0, "DDNS_UPDATE_STATUS"), None);
+        assert_eq!(runner.env_of(1, "DDNS_HOOK_STAGE").as_deref(), Some("post"));
+        assert_eq!(runner.env_of(1, "DDNS_UPDATE_STATUS").as_deref(), Some("success"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_pre_hook_aborts_update() {
+        let runner = RecordingHookRunner::new(1);
+        let (service, state) = create_service(create_config(create_hooks(true)), 0, runner.clone());
+
+        let error = service.update_dns_record().await.unwrap_err();
+        assert_eq!(error.kind(), "hook");
+        // 放棄更新時不執行後置鉤子，也不記錄新 IP
+        assert_eq!(runner.calls.lock().unwrap().len(), 1);
+        assert_eq!(state.get_last_ip(&ConfigId::new("hook_config")).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_failed_pre_hook_continues_without_abort() {
+        let runner = RecordingHookRunner::new(1);
+        let (service, _) = create_service(create_config(create_hooks(false)), 1, runner.clone());
+
+        assert!(service.update_dns_record().await.unwrap().updated);
+        assert_eq!(runner.calls.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_hooks_not_run_when_ip_unchanged() {
+        let runner = RecordingHookRunner::new(0);
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().returning(|| Ok("203.0.113.7".to_string()));
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_get_record().returning(|_, _| Ok(DnsRecord {
+            id: Some("test_record".to_string()),
+            name: "vpn.example.com".to_string(),
+            record_type: "A".to_string(),
+            content: "203.0.113.7".to_string(),
+            ttl: 120,
+            proxied: false,
+        }));
+
+        let state = Arc::new(InMemoryStateRepository::new());
+        state.set_last_ip(&ConfigId::new("hook_config"), "203.0.113.7").await.unwrap();
+        let service = DdnsApplicationService::new(
+            Arc::new(dns_mock),
+            Arc::new(ip_mock),
+            state,
+            create_config(create_hooks(true)),
+        ).with_hook_runner(runner.clone());
+
+        assert!(!service.update_dns_record().await.unwrap().updated);
+        assert!(runner.calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_hook_results_are_published() {
+        let runner = RecordingHookRunner::new(0);
+        let mut hooks = create_hooks(false);
+        hooks.pre_update = None;
+        let (service, _) = create_service(create_config(hooks), 1, runner);
+
+        let event_manager = Arc::new(EventManager::new());
+        let mut receiver = event_manager.subscribe_all();
+        let service = service.with_event_manager(event_manager);
+
+        service.update_dns_record().await.unwrap();
+
+        match receiver.recv().await.unwrap() {
+            Event::HookExecuted(event) => {
+                assert_eq!(event.stage, HookStage::Post);
+                assert_eq!(event.command, "post.sh");
+                assert!(event.success);
+                assert_eq!(event.stdout, "done");
+            },
+            other => panic!("應該收到 HookExecuted，實際為 {:?}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod record_lookup_tests {
+    use super::*;
+    use cloudflare_ddns::domain::config::RecordGroup;
+    use cloudflare_ddns::infrastructure::state::InMemoryStateRepository;
+
+    fn group_member(ip_type: &str) -> DdnsConfig {
+        let group: RecordGroup = serde_json::from_value(serde_json::json!({
+            "id": "home",
+            "api_token": "test_token",
+            "zone_id": "test_zone",
+            "hostnames": ["www.example.com"],
+            "ip_type": ip_type,
+            "update_interval": 300
+        })).unwrap();
+        group.expand().remove(0)
+    }
+
+    fn existing_record(id: &str, content: &str) -> DnsRecord {
+        DnsRecord {
+            id: Some(id.to_string()),
+            name: "www.example.com".to_string(),
+            record_type: "A".to_string(),
+            content: content.to_string(),
+            ttl: 120,
+            proxied: false,
+        }
+    }
+
+    fn ip_mock(ip: &'static str) -> MockIpMock {
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().returning(move || Ok(ip.to_string()));
+        ip_mock
+    }
+
+    #[tokio::test]
+    async fn test_record_id_resolved_by_name_once() {
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_find_records()
+            .with(eq("test_zone"), eq("www.example.com"), eq("A"))
+            .times(1)
+            .returning(|_, _, _| Ok(vec![existing_record("www_a", "192.0.2.1")]));
+        dns_mock.expect_update_record()
+            .times(2)
+            .withf(|record| record.id.as_deref() == Some("www_a"))
+            .returning(|record| Ok(DnsUpdateResult { record, updated: true }));
+
+        let mut ip_mock = MockIpMock::new();
+        let mut sequence = vec!["198.51.100.2", "198.51.100.1"];
+        ip_mock.expect_get_ipv4().returning(move || Ok(sequence.pop().unwrap().to_string()));
+
+        let service = DdnsApplicationService::new(
+            Arc::new(dns_mock),
+            Arc::new(ip_mock),
+            Arc::new(InMemoryStateRepository::new()),
+            group_member("ipv4"),
+        );
+
+        assert!(service.update_dns_record().await.unwrap().updated);
+        assert!(service.update_dns_record().await.unwrap().updated);
+    }
+
+    #[tokio::test]
+    async fn test_missing_record_is_created() {
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_find_records().times(1).returning(|_, _, _| Ok(Vec::new()));
+        dns_mock.expect_create_record()
+            .with(eq("test_zone"), always())
+            .times(1)
+            .returning(|_, record| Ok(DnsRecord { id: Some("created".to_string()), ..record }));
+        dns_mock.expect_update_record().never();
+
+        let service = DdnsApplicationService::new(
+            Arc::new(dns_mock),
+            Arc::new(ip_mock("198.51.100.1")),
+            Arc::new(InMemoryStateRepository::new()),
+            group_member("ipv4"),
+        );
+
+        let result = service.update_dns_record().await.unwrap();
+        assert!(result.updated);
+        assert_eq!(result.record.id.as_deref(), Some("created"));
+        assert_eq!(result.record.content, "198.51.100.1");
+    }
+
+    #[tokio::test]
+    async fn test_deleted_record_is_recreated() {
+        let state = Arc::new(InMemoryStateRepository::new());
+        let member = group_member("ipv4");
+        state.set_last_ip(&member.id, "198.51.100.1").await.unwrap();
+
+        let mut dns_mock = MockDnsMock::new();
+        // 記錄已在 Cloudflare 上被刪除，按漂移處理並重新創建
+        dns_mock.expect_find_records().times(2).returning(|_, _, _| Ok(Vec::new()));
+        dns_mock.expect_create_record()
+            .times(1)
+            .returning(|_, record| Ok(DnsRecord { id: Some("recreated".to_string()), ..record }));
+
+        let service = DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(ip_mock("198.51.100.1")), state, member);
+
+        let result = service.update_dns_record().await.unwrap();
+        assert!(result.updated);
+        assert_eq!(result.record.id.as_deref(), Some("recreated"));
+    }
+}
+
+#[cfg(test)]
+mod ipv6_withdrawal_tests {
+    use super::*;
+    use cloudflare_ddns::domain::config::{ConfigDocument, RecordGroup};
+    use cloudflare_ddns::infrastructure::state::InMemoryStateRepository;
+
+    fn aaaa_member(ipv6_loss: serde_json::Value) -> DdnsConfig {
+        let group: RecordGroup = serde_json::from_value(serde_json::json!({
+            "id": "home",
+            "api_token": "test_token",
+            "zone_id": "test_zone",
+            "hostnames": ["www.example.com"],
+            "ip_type": "ipv6",
+            "update_interval": 300,
+            "ipv6_loss": ipv6_loss
+        })).unwrap();
+        group.expand().remove(0)
+    }
+
+    /// `ip_type` 為 `both` 的單條配置展開後的 AAAA 記錄
+    fn dual_stack_aaaa_member(ipv6_loss: serde_json::Value) -> DdnsConfig {
+        let contents = serde_json::json!([{
+            "id": "www",
+            "api_token": "test_token",
+            "zone_id": "test_zone",
+            "record_name": "www.example.com",
+            "ip_type": "both",
+            "update_interval": 300,
+            "ipv6_loss": ipv6_loss
+        }]).to_string();
+        let document = ConfigDocument::parse(&contents).unwrap();
+        document.groups[0].expand().remove(1)
+    }
+
+    fn aaaa_record(content: &str) -> DnsRecord {
+        DnsRecord {
+            id: Some("www_aaaa".to_string()),
+            name: "www.example.com".to_string(),
+            record_type: "AAAA".to_string(),
+            content: content.to_string(),
+            ttl: 120,
+            proxied: false,
+        }
+    }
+
+    fn lost() -> Result<String, DomainError> {
+        Err(DomainError::network("no IPv6 route"))
+    }
+
+    #[tokio::test]
+    async fn test_record_deleted_after_threshold_and_recreated() {
+        let state = Arc::new(InMemoryStateRepository::new());
+        let member = aaaa_member(serde_json::json!({ "action": "delete", "after_failures": 2 }));
+        state.set_last_ip(&member.id, "2001:db8::1").await.unwrap();
+
+        let mut dns_mock = MockDnsMock::new();
+        let mut lookups = 0;
+        dns_mock.expect_find_records()
+            .with(eq("test_zone"), eq("www.example.com"), eq("AAAA"))
+            .times(2)
+            .returning(move |_, _, _| {
+                lookups += 1;
+                Ok(if lookups == 1 { vec![aaaa_record("2001:db8::1")] } else { Vec::new() })
+            });
+        dns_mock.expect_delete_record()
+            .with(eq("test_zone"), eq("www_aaaa"))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        dns_mock.expect_create_record()
+            .times(1)
+            .returning(|_, record| Ok(DnsRecord { id: Some("recreated".to_string()), ..record }));
+
+        let service = DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(MockIpMock::new()), state.clone(), member.clone());
+
+        assert!(service.apply_detected_ip(lost()).await.is_err());
+        assert_eq!(state.get_last_ip(&member.id).await.unwrap().as_deref(), Some("2001:db8::1"));
+
+        // 達到閾值後刪除記錄，之後的失敗不再重複刪除
+        assert!(service.apply_detected_ip(lost()).await.is_err());
+        assert!(service.apply_detected_ip(lost()).await.is_err());
+        assert_eq!(state.get_last_ip(&member.id).await.unwrap(), None);
+
+        let result = service.apply_detected_ip(Ok("2001:db8::2".to_string())).await.unwrap();
+        assert!(result.updated);
+        assert_eq!(result.record.id.as_deref(), Some("recreated"));
+        assert_eq!(result.record.content, "2001:db8::2");
+    }
+
+    #[tokio::test]
+    async fn test_single_dual_stack_config_withdraws_aaaa_record() {
+        let state = Arc::new(InMemoryStateRepository::new());
+        let member = dual_stack_aaaa_member(serde_json::json!({ "action": "delete", "after_failures": 1 }));
+        assert_eq!(member.ip_type, IpType::IPv6);
+        state.set_last_ip(&member.id, "2001:db8::1").await.unwrap();
+
+        let mut dns_mock = MockDnsMock::new();
+        let mut lookups = 0;
+        dns_mock.expect_find_records()
+            .with(eq("test_zone"), eq("www.example.com"), eq("AAAA"))
+            .times(2)
+            .returning(move |_, _, _| {
+                lookups += 1;
+                Ok(if lookups == 1 { vec![aaaa_record("2001:db8::1")] } else { Vec::new() })
+            });
+        dns_mock.expect_delete_record()
+            .with(eq("test_zone"), eq("www_aaaa"))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        dns_mock.expect_create_record()
+            .times(1)
+            .returning(|_, record| Ok(DnsRecord { id: Some("recreated".to_string()), ..record }));
+
+        let service = DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(MockIpMock::new()), state.clone(), member.clone());
+
+        assert!(service.apply_detected_ip(lost()).await.is_err());
+        assert_eq!(state.get_last_ip(&member.id).await.unwrap(), None);
+
+        let result = service.apply_detected_ip(Ok("2001:db8::2".to_string())).await.unwrap();
+        assert!(result.updated);
+        assert_eq!(result.record.record_type, "AAAA");
+        assert_eq!(result.record.content, "2001:db8::2");
+    }
+
+    #[tokio::test]
+    async fn test_record_parked_and_restored() {
+        let state = Arc::new(InMemoryStateRepository::new());
+        let member = aaaa_member(serde_json::json!({ "action": "park", "after_failures": 1 }));
+        state.set_last_ip(&member.id, "2001:db8::1").await.unwrap();
+
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_find_records()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![aaaa_record("2001:db8::1")]));
+        dns_mock.expect_update_record()
+            .withf(|record| record.content == "100::" && record.id.as_deref() == Some("www_aaaa"))
+            .times(1)
+            .returning(|record| Ok(DnsUpdateResult { record, updated: true }));
+        dns_mock.expect_update_record()
+            .withf(|record| record.content == "2001:db8::1")
+            .times(1)
+            .returning(|record| Ok(DnsUpdateResult { record, updated: true }));
+        dns_mock.expect_delete_record().never();
+
+        let service = DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(MockIpMock::new()), state.clone(), member.clone());
+
+        assert!(service.apply_detected_ip(lost()).await.is_err());
+        assert_eq!(state.get_last_ip(&member.id).await.unwrap().as_deref(), Some("100::"));
+
+        let result = service.apply_detected_ip(Ok("2001:db8::1".to_string())).await.unwrap();
+        assert!(result.updated);
+        assert_eq!(state.get_last_ip(&member.id).await.unwrap().as_deref(), Some("2001:db8::1"));
+    }
+
+    #[tokio::test]
+    async fn test_record_kept_by_default() {
+        let state = Arc::new(InMemoryStateRepository::new());
+        let member = aaaa_member(serde_json::json!({}));
+        state.set_last_ip(&member.id, "2001:db8::1").await.unwrap();
+
+        // 未配置撤回時不訪問 DNS 服務
+        let service = DdnsApplicationService::new(Arc::new(MockDnsMock::new()), Arc::new(MockIpMock::new()), state.clone(), member.clone());
+        for _ in 0..5 {
+            assert!(service.apply_detected_ip(lost()).await.is_err());
+        }
+        assert_eq!(state.get_last_ip(&member.id).await.unwrap().as_deref(), Some("2001:db8::1"));
+    }
+}
+
+#[cfg(test)]
+mod record_content_tests {
+    use super::*;
+    use cloudflare_ddns::application::events::{Event, EventManager};
+    use cloudflare_ddns::infrastructure::state::InMemoryStateRepository;
+
+    fn txt_config(content: &str) -> DdnsConfig {
+        DdnsConfig {
+            id: ConfigId::new("txt_config"),
+            record_id: "txt_record".to_string(),
+            record_name: "_ddns.example.com".to_string(),
+            record_type: Some("TXT".to_string()),
+            content: Some(content.to_string()),
+            ..create_test_config()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_txt_record_rendered_from_template() {
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().times(1).returning(|| Ok("203.0.113.10".to_string()));
+        ip_mock.expect_get_ipv6().times(1).returning(|| Ok("2001:db8::10".to_string()));
+
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_update_record()
+            .withf(|record| record.record_type == "TXT" && record.content == "host=_ddns.example.com v4=203.0.113.10 v6=2001:db8::10")
+            .times(1)
+            .returning(|record| Ok(DnsUpdateResult { record, updated: true }));
+
+        let state = Arc::new(InMemoryStateRepository::new());
+        let config = txt_config("host={hostname} v4={ip} v6={ipv6}");
+        let service = DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(ip_mock), state.clone(), config.clone());
+
+        let result = service.update_dns_record().await.unwrap();
+        assert!(result.updated);
+        assert_eq!(
+            state.get_last_ip(&config.id).await.unwrap().as_deref(),
+            Some("host=_ddns.example.com v4=203.0.113.10 v6=2001:db8::10")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_other_family_not_detected_when_unused() {
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().times(1).returning(|| Ok("203.0.113.10".to_string()));
+        ip_mock.expect_get_ipv6().never();
+
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_update_record()
+            .withf(|record| record.content == "203.0.113.10")
+            .times(1)
+            .returning(|record| Ok(DnsUpdateResult { record, updated: true }));
+
+        let service = DdnsApplicationService::new(
+            Arc::new(dns_mock),
+            Arc::new(ip_mock),
+            Arc::new(InMemoryStateRepository::new()),
+            txt_config("{ip}"),
+        );
+
+        assert!(service.update_dns_record().await.unwrap().updated);
+    }
+
+    #[tokio::test]
+    async fn test_time_only_rewrite_is_not_an_ip_change() {
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().times(1).returning(|| Ok("203.0.113.10".to_string()));
+
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_update_record()
+            .withf(|record| record.content.starts_with("v4=203.0.113.10 t=") && record.content != "v4=203.0.113.10 t=0")
+            .times(1)
+            .returning(|record| Ok(DnsUpdateResult { record, updated: true }));
+
+        let state = Arc::new(InMemoryStateRepository::new());
+        let config = txt_config("v4={ip} t={unix_time}");
+        state.set_state(&config.id, StateEntry {
+            last_ip: Some("v4=203.0.113.10 t=0".to_string()),
+            last_address: Some("203.0.113.10".to_string()),
+            ..Default::default()
+        }).await.unwrap();
+
+        let event_manager = Arc::new(EventManager::new());
+        let mut receiver = event_manager.subscribe_all();
+        let service = DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(ip_mock), state.clone(), config.clone())
+            .with_event_manager(event_manager);
+
+        assert!(service.update_dns_record().await.unwrap().updated);
+
+        // 只有時間變化，重寫記錄但不發布 IP 變更事件
+        match receiver.recv().await.unwrap() {
+            Event::UpdateSucceeded(event) => assert!(event.ip.starts_with("v4=203.0.113.10 t=")),
+            other => panic!("應該收到 UpdateSucceeded，實際為 {:?}", other),
+        }
+        assert!(receiver.try_recv().is_err());
+        let entry = state.get_state(&config.id).await.unwrap().unwrap();
+        assert_eq!(entry.last_address.as_deref(), Some("203.0.113.10"));
+    }
+
+    #[tokio::test]
+    async fn test_ip_change_event_carries_addresses() {
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().times(1).returning(|| Ok("203.0.113.20".to_string()));
+
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_update_record()
+            .times(1)
+            .returning(|record| Ok(DnsUpdateResult { record, updated: true }));
+
+        let state = Arc::new(InMemoryStateRepository::new());
+        let config = txt_config("v=1 ip={ip}");
+        state.set_state(&config.id, StateEntry {
+            last_ip: Some("v=1 ip=203.0.113.10".to_string()),
+            last_address: Some("203.0.113.10".to_string()),
+            ..Default::default()
+        }).await.unwrap();
+
+        let event_manager = Arc::new(EventManager::new());
+        let mut receiver = event_manager.subscribe_all();
+        let service = DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(ip_mock), state, config)
+            .with_event_manager(event_manager);
+
+        assert!(service.update_dns_record().await.unwrap().updated);
+
+        match receiver.recv().await.unwrap() {
+            Event::IpChanged(event) => {
+                assert_eq!(event.old_ip.as_deref(), Some("203.0.113.10"));
+                assert_eq!(event.new_ip, "203.0.113.20");
+            },
+            other => panic!("應該收到 IpChanged，實際為 {:?}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod failover_tests {
+    use super::*;
+    use cloudflare_ddns::domain::config::{FailoverSettings, HealthCheck};
+    use cloudflare_ddns::domain::failover::HealthProbe;
+    use cloudflare_ddns::infrastructure::state::InMemoryStateRepository;
+    use std::collections::VecDeque;
+    use std::net::IpAddr;
+    use std::sync::Mutex;
+
+    /// 按順序返回預設結果的健康檢查
+    struct ScriptedProbe {
+        results: Mutex<VecDeque<bool>>,
+    }
+
+    impl ScriptedProbe {
+        fn new(results: &[bool]) -> Arc<Self> {
+            Arc::new(Self { results: Mutex::new(results.iter().copied().collect()) })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl HealthProbe for ScriptedProbe {
+        async fn probe(&self, _check: &HealthCheck, _target: IpAddr) -> Result<(), DomainError> {
+            match self.results.lock().unwrap().pop_front().expect("沒有預設的檢查結果") {
+                true => Ok(()),
+                false => Err(DomainError::network("connection refused")),
+            }
+        }
+    }
+
+    fn failover_config() -> DdnsConfig {
+        DdnsConfig {
+            failover: Some(FailoverSettings {
+                backup: "198.51.100.20".parse().unwrap(),
+                checks: vec![HealthCheck::Tcp { port: 443, timeout_seconds: 1 }],
+                failure_threshold: 2,
+                recovery_threshold: 2,
+            }),
+            ..home_config()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_switches_to_backup_and_back_with_hysteresis() {
+        let state = Arc::new(InMemoryStateRepository::new());
+        let config = failover_config();
+        state.set_last_ip(&config.id, "203.0.113.10").await.unwrap();
+
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().returning(|| Ok("203.0.113.10".to_string()));
+
+        // DNS 上的記錄內容隨更新變化，避免被當作漂移
+        let live = Arc::new(Mutex::new("203.0.113.10".to_string()));
+        let mut dns_mock = MockDnsMock::new();
+        let current = live.clone();
+        dns_mock.expect_get_record().returning(move |_, _| Ok(DnsRecord {
+            id: Some("home_record".to_string()),
+            name: "home.example.com".to_string(),
+            record_type: "A".to_string(),
+            content: current.lock().unwrap().clone(),
+            ttl: 120,
+            proxied: false,
+        }));
+        let written = live.clone();
+        dns_mock.expect_update_record()
+            .withf(|record| record.content == "198.51.100.20")
+            .times(1)
+            .returning(move |record| {
+                *written.lock().unwrap() = record.content.clone();
+                Ok(DnsUpdateResult { record, updated: true })
+            });
+        let written = live.clone();
+        dns_mock.expect_update_record()
+            .withf(|record| record.content == "203.0.113.10")
+            .times(1)
+            .returning(move |record| {
+                *written.lock().unwrap() = record.content.clone();
+                Ok(DnsUpdateResult { record, updated: true })
+            });
+
+        let probe = ScriptedProbe::new(&[false, false, true, false, true, true]);
+        let service = DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(ip_mock), state.clone(), config.clone())
+            .with_health_probe(probe);
+
+        // 第一次失敗保持主目標
+        assert!(!service.update_dns_record().await.unwrap().updated);
+        // 連續兩次失敗切換到備用地址
+        assert_eq!(service.update_dns_record().await.unwrap().record.content, "198.51.100.20");
+        // 恢復一次後又失敗，不切回
+        assert!(!service.update_dns_record().await.unwrap().updated);
+        assert!(!service.update_dns_record().await.unwrap().updated);
+        assert_eq!(state.get_last_ip(&config.id).await.unwrap().as_deref(), Some("198.51.100.20"));
+        // 連續兩次恢復切回主目標
+        assert!(!service.update_dns_record().await.unwrap().updated);
+        assert_eq!(service.update_dns_record().await.unwrap().record.content, "203.0.113.10");
+    }
+
+    #[tokio::test]
+    async fn test_detection_failure_counts_as_unhealthy() {
+        let state = Arc::new(InMemoryStateRepository::new());
+        let config = failover_config();
+
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().returning(|| Err(DomainError::network("link down")));
+
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_update_record()
+            .withf(|record| record.content == "198.51.100.20")
+            .times(1)
+            .returning(|record| Ok(DnsUpdateResult { record, updated: true }));
+
+        let service = DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(ip_mock), state.clone(), config.clone())
+            .with_health_probe(ScriptedProbe::new(&[]));
+
+        assert!(service.update_dns_record().await.is_err());
+        assert!(service.update_dns_record().await.unwrap().updated);
+        assert_eq!(state.get_last_ip(&config.id).await.unwrap().as_deref(), Some("198.51.100.20"));
+    }
+
+    #[tokio::test]
+    async fn test_restarts_on_backup_when_record_already_switched() {
+        let state = Arc::new(InMemoryStateRepository::new());
+        let config = failover_config();
+        state.set_last_ip(&config.id, "198.51.100.20").await.unwrap();
+
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().returning(|| Ok("203.0.113.10".to_string()));
+
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_get_record().returning(|_, _| Ok(DnsRecord {
+            id: Some("home_record".to_string()),
+            name: "home.example.com".to_string(),
+            record_type: "A".to_string(),
+            content: "198.51.100.20".to_string(),
+            ttl: 120,
+            proxied: false,
+        }));
+        dns_mock.expect_update_record().never();
+
+        // 重建的服務需要同樣連續恢復才切回
+        let service = DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(ip_mock), state, config)
+            .with_health_probe(ScriptedProbe::new(&[true]));
+        assert_eq!(service.update_dns_record().await.unwrap().record.content, "198.51.100.20");
+    }
+
+    #[tokio::test]
+    async fn test_plan_does_not_advance_hysteresis() {
+        let state = Arc::new(InMemoryStateRepository::new());
+        let config = failover_config();
+        state.set_last_ip(&config.id, "203.0.113.10").await.unwrap();
+
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().returning(|| Ok("203.0.113.10".to_string()));
+
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_get_record().returning(|_, _| Ok(DnsRecord {
+            id: Some("home_record".to_string()),
+            name: "home.example.com".to_string(),
+            record_type: "A".to_string(),
+            content: "203.0.113.10".to_string(),
+            ttl: 120,
+            proxied: false,
+        }));
+        dns_mock.expect_update_record().never();
+
+        let service = DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(ip_mock), state, config)
+            .with_health_probe(ScriptedProbe::new(&[false, false, false]));
+
+        // 兩次試運行都只看到一次失敗，實際更新時同樣未達到閾值
+        assert_eq!(service.plan(None).await.desired_content.as_deref(), Some("203.0.113.10"));
+        assert_eq!(service.plan(None).await.desired_content.as_deref(), Some("203.0.113.10"));
+        assert!(!service.update_dns_record().await.unwrap().updated);
+    }
+}
+
+#[cfg(test)]
+mod dry_run_plan_tests {
+    use super::*;
+    use cloudflare_ddns::application::ddns::PlanAction;
+    use cloudflare_ddns::domain::config::RecordGroup;
+    use cloudflare_ddns::infrastructure::state::InMemoryStateRepository;
+
+    fn live_record(content: &'static str) -> MockDnsMock {
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_get_record()
+            .with(eq("test_zone"), eq("home_record"))
+            .times(1)
+            .returning(move |_, _| Ok(DnsRecord {
+                id: Some("home_record".to_string()),
+                name: "home.example.com".to_string(),
+                record_type: "A".to_string(),
+                content: content.to_string(),
+                ttl: 120,
+                proxied: false,
+            }));
+        dns_mock.expect_update_record().never();
+        dns_mock.expect_create_record().never();
+        dns_mock
+    }
+
+    fn ip_mock(result: Result<&'static str, DomainError>) -> MockIpMock {
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().return_once(move || result.map(str::to_string));
+        ip_mock
+    }
+
+    #[tokio::test]
+    async fn test_changed_address_is_planned_as_update_without_writing() {
+        let state = Arc::new(InMemoryStateRepository::new());
+        let service = DdnsApplicationService::new(
+            Arc::new(live_record("192.0.2.1")),
+            Arc::new(ip_mock(Ok("198.51.100.1"))),
+            state.clone(),
+            home_config(),
+        );
+
+        let entry = service.plan(None).await;
+        assert_eq!(entry.action, PlanAction::Update);
+        assert_eq!(entry.record_type, "A");
+        assert_eq!(entry.current_content.as_deref(), Some("192.0.2.1"));
+        assert_eq!(entry.desired_content.as_deref(), Some("198.51.100.1"));
+        assert!(entry.has_changes());
+        // 試運行不記錄狀態
+        assert!(state.get_state(&home_config().id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_matching_record_is_noop() {
+        let service = DdnsApplicationService::new(
+            Arc::new(live_record("198.51.100.1")),
+            Arc::new(ip_mock(Ok("198.51.100.1"))),
+            Arc::new(InMemoryStateRepository::new()),
+            home_config(),
+        );
+
+        let entry = service.plan(None).await;
+        assert_eq!(entry.action, PlanAction::Noop);
+        assert!(!entry.has_changes());
+    }
+
+    #[tokio::test]
+    async fn test_missing_record_is_planned_as_create() {
+        let group: RecordGroup = serde_json::from_value(serde_json::json!({
+            "id": "home",
+            "api_token": "test_token",
+            "zone_id": "test_zone",
+            "hostnames": ["www.example.com"],
+            "ip_type": "ipv4",
+            "update_interval": 300
+        })).unwrap();
+
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_find_records().times(1).returning(|_, _, _| Ok(Vec::new()));
+        dns_mock.expect_create_record().never();
+
+        let service = DdnsApplicationService::new(
+            Arc::new(dns_mock),
+            Arc::new(ip_mock(Ok("198.51.100.1"))),
+            Arc::new(InMemoryStateRepository::new()),
+            group.expand().remove(0),
+        );
+
+        let entry = service.plan(None).await;
+        assert_eq!(entry.action, PlanAction::Create);
+        assert_eq!(entry.current_content, None);
+        assert_eq!(entry.desired_content.as_deref(), Some("198.51.100.1"));
+    }
+
+    #[tokio::test]
+    async fn test_detection_failure_is_reported() {
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_get_record().never();
+
+        let service = DdnsApplicationService::new(
+            Arc::new(dns_mock),
+            Arc::new(ip_mock(Err(DomainError::network("link down")))),
+            Arc::new(InMemoryStateRepository::new()),
+            home_config(),
+        );
+
+        let entry = service.plan(None).await;
+        assert_eq!(entry.action, PlanAction::Error);
+        assert!(entry.error.unwrap().contains("link down"));
+    }
+
+    #[tokio::test]
+    async fn test_template_content_is_planned() {
+        let mut config = home_config();
+        config.record_type = Some("TXT".to_string());
+        config.content = Some("v=home {ip}".to_string());
+
+        let service = DdnsApplicationService::new(
+            Arc::new(live_record("v=home 192.0.2.1")),
+            Arc::new(ip_mock(Ok("198.51.100.1"))),
+            Arc::new(InMemoryStateRepository::new()),
+            config,
+        );
+
+        let entry = service.plan(None).await;
+        assert_eq!(entry.record_type, "TXT");
+        assert_eq!(entry.action, PlanAction::Update);
+        assert_eq!(entry.desired_content.as_deref(), Some("v=home 198.51.100.1"));
+    }
+}
+
+#[cfg(test)]
+mod one_shot_tests {
+    use super::*;
+    use cloudflare_ddns::infrastructure::state::InMemoryStateRepository;
+
+    fn record(content: &str) -> DnsRecord {
+        DnsRecord {
+            id: Some("home_record".to_string()),
+            name: "home.example.com".to_string(),
+            record_type: "A".to_string(),
+            content: content.to_string(),
+            ttl: 120,
+            proxied: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_record_is_not_rewritten() {
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_get_record().returning(|_, _| Ok(record("198.51.100.1")));
+        dns_mock.expect_update_record().never();
+
+        let mut ip_mock = MockIpMock::new();
+        ip_mock.expect_get_ipv4().returning(|| Ok("198.51.100.1".to_string()));
+
+        let service = DdnsApplicationService::new(
+            Arc::new(dns_mock),
+            Arc::new(ip_mock),
+            Arc::new(InMemoryStateRepository::new()),
+            home_config(),
+        );
+
+        service.load_last_content().await.unwrap();
+        assert!(!service.update_dns_record().await.unwrap().updated);
+    }
+
+    #[tokio::test]
+    async fn test_existing_state_is_kept() {
+        let state = Arc::new(InMemoryStateRepository::new());
+        state.set_last_ip(&home_config().id, "192.0.2.1").await.unwrap();
+
+        let mut dns_mock = MockDnsMock::new();
+        dns_mock.expect_get_record().never();
+
+        let service = DdnsApplicationService::new(Arc::new(dns_mock), Arc::new(MockIpMock::new()), state.clone(), home_config());
+
+        service.load_last_content().await.unwrap();
+        assert_eq!(state.get_last_ip(&home_config().id).await.unwrap().as_deref(), Some("192.0.2.1"));
+    }
+}
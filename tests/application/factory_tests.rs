@@ -0,0 +1,57 @@
+use cloudflare_ddns::application::events::Event;
+use cloudflare_ddns::application::factories::ServiceFactory;
+use cloudflare_ddns::domain::config::{ConfigId, DdnsConfig, IpType};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn env_config() -> DdnsConfig {
+    DdnsConfig {
+        id: ConfigId::new("env-ipv4"),
+        api_token: "test_token".to_string(),
+        zone_id: "test_zone".to_string(),
+        record_id: "test_record".to_string(),
+        record_name: "env.example.com".to_string(),
+        update_interval: 300,
+        ip_type: IpType::IPv4,
+        hooks: Default::default(),
+        source: Default::default(),
+        ipv6_loss: Default::default(),
+        prefix_delegation: None,
+        record_type: None,
+        content: None,
+        failover: None,
+        drift_check_interval: None,
+    }
+}
+
+#[cfg(test)]
+mod env_config_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_env_record_survives_config_reload() {
+        let service_factory = Arc::new(
+            ServiceFactory::new()
+                .with_config_watching(false)
+                .with_env_configs(vec![env_config()])
+        );
+        service_factory.init_event_listeners().await;
+
+        let config_id = ConfigId::new("env-ipv4");
+        let before = service_factory.find_ddns_service(&config_id).await.expect("環境變量中的記錄應該被載入");
+
+        service_factory.get_event_manager().publish(Event::ConfigChanged).await;
+
+        // 等待重新加載完成（服務重建後實例不同）
+        let after = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let current = service_factory.find_ddns_service(&config_id).await;
+                if !matches!(&current, Some(service) if Arc::ptr_eq(service, &before)) {
+                    return current;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }).await.unwrap();
+        assert!(after.is_some(), "重新加載配置後環境變量中的記錄應該保留");
+    }
+}
@@ -0,0 +1,104 @@
+use cloudflare_ddns::application::health::{
+    record_freshness, CheckStatus, ReadinessCheck, ReadinessReport, RecordReadiness,
+};
+use cloudflare_ddns::domain::config::{ConfigId, IpType};
+use chrono::{Duration, Utc};
+
+fn create_record(worker: CheckStatus, freshness: CheckStatus) -> RecordReadiness {
+    RecordReadiness {
+        config_id: ConfigId::new("config-1"),
+        record_name: "home.example.com".to_string(),
+        ip_type: IpType::IPv4,
+        worker,
+        freshness,
+        last_success: None,
+        last_error: None,
+    }
+}
+
+#[cfg(test)]
+mod record_freshness_tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_success_is_fresh() {
+        let now = Utc::now();
+        let started = now - Duration::hours(1);
+
+        let status = record_freshness(Some(now - Duration::seconds(600)), Some(started), 300, 3, now);
+
+        assert_eq!(status, CheckStatus::Ok);
+    }
+
+    #[test]
+    fn test_stale_success_is_degraded() {
+        let now = Utc::now();
+        let started = now - Duration::hours(1);
+
+        let status = record_freshness(Some(now - Duration::seconds(901)), Some(started), 300, 3, now);
+
+        assert_eq!(status, CheckStatus::Degraded);
+    }
+
+    #[test]
+    fn test_never_succeeded_counts_from_worker_start() {
+        let now = Utc::now();
+
+        assert_eq!(record_freshness(None, Some(now - Duration::seconds(60)), 300, 3, now), CheckStatus::Ok);
+        assert_eq!(record_freshness(None, Some(now - Duration::seconds(1000)), 300, 3, now), CheckStatus::Degraded);
+    }
+
+    #[test]
+    fn test_restarted_worker_gets_grace_period() {
+        let now = Utc::now();
+
+        let status = record_freshness(Some(now - Duration::hours(2)), Some(now - Duration::seconds(10)), 300, 3, now);
+
+        assert_eq!(status, CheckStatus::Ok);
+    }
+
+    #[test]
+    fn test_without_worker_is_skipped() {
+        let now = Utc::now();
+
+        assert_eq!(record_freshness(None, None, 300, 3, now), CheckStatus::Skipped);
+    }
+}
+
+#[cfg(test)]
+mod readiness_report_tests {
+    use super::*;
+
+    #[test]
+    fn test_ready_when_all_checks_pass() {
+        let report = ReadinessReport::new(
+            vec![
+                ReadinessCheck::new("configs", CheckStatus::Ok, ""),
+                ReadinessCheck::new("cloudflare", CheckStatus::Skipped, ""),
+            ],
+            vec![create_record(CheckStatus::Ok, CheckStatus::Ok)],
+        );
+
+        assert!(report.ready);
+    }
+
+    #[test]
+    fn test_degraded_check_makes_report_not_ready() {
+        let report = ReadinessReport::new(
+            vec![ReadinessCheck::new("cloudflare", CheckStatus::Degraded, "unreachable")],
+            Vec::new(),
+        );
+
+        assert!(!report.ready);
+    }
+
+    #[test]
+    fn test_dead_worker_makes_report_not_ready() {
+        let report = ReadinessReport::new(
+            vec![ReadinessCheck::new("configs", CheckStatus::Ok, "")],
+            vec![create_record(CheckStatus::Degraded, CheckStatus::Ok)],
+        );
+
+        assert!(!report.ready);
+    }
+}
@@ -0,0 +1,89 @@
+use cloudflare_ddns::application::events::{
+    DriftDetectedEvent, Event, EventListener, EventType, IpChangedEvent, RecordRef, UpdateFailedEvent, UpdateSucceededEvent,
+};
+use cloudflare_ddns::application::metrics::MetricsEventListener;
+use cloudflare_ddns::domain::config::{ConfigId, IpType};
+use cloudflare_ddns::domain::error::DomainError;
+use cloudflare_ddns::infrastructure::metrics::MetricsRegistry;
+use chrono::Utc;
+
+fn create_record() -> RecordRef {
+    RecordRef {
+        config_id: ConfigId::new("config-1"),
+        record_name: "home.example.com".to_string(),
+        record_type: "A".to_string(),
+        ip_type: IpType::IPv4,
+    }
+}
+
+fn create_listener() -> (MetricsEventListener, &'static MetricsRegistry) {
+    let registry: &'static MetricsRegistry = Box::leak(Box::new(MetricsRegistry::new()));
+    (MetricsEventListener::with_registry(registry), registry)
+}
+
+#[cfg(test)]
+mod metrics_listener_tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_updates_by_result() {
+        let (listener, registry) = create_listener();
+
+        listener.record(&Event::UpdateSucceeded(UpdateSucceededEvent {
+            record: create_record(),
+            ip: "203.0.113.10".to_string(),
+            timestamp: Utc::now(),
+        }));
+        listener.record(&Event::UpdateFailed(UpdateFailedEvent::from_error(
+            create_record(),
+            &DomainError::network("timeout"),
+        )));
+
+        let output = registry.render();
+
+        assert!(output.contains(
+            "ddns_record_updates_total{config_id=\"config-1\",record_name=\"home.example.com\",record_type=\"A\",result=\"success\"} 1\n"
+        ));
+        assert!(output.contains(
+            "ddns_record_updates_total{config_id=\"config-1\",record_name=\"home.example.com\",record_type=\"A\",result=\"failure\"} 1\n"
+        ));
+        assert!(output.contains(
+            "ddns_update_failures_total{config_id=\"config-1\",record_name=\"home.example.com\",record_type=\"A\",kind=\"network\"} 1\n"
+        ));
+    }
+
+    #[test]
+    fn test_counts_ip_changes_and_drift() {
+        let (listener, registry) = create_listener();
+
+        listener.record(&Event::IpChanged(IpChangedEvent {
+            record: create_record(),
+            old_ip: None,
+            new_ip: "203.0.113.10".to_string(),
+            timestamp: Utc::now(),
+        }));
+        listener.record(&Event::DriftDetected(DriftDetectedEvent {
+            record: create_record(),
+            expected_ip: "203.0.113.10".to_string(),
+            actual_ip: "198.51.100.1".to_string(),
+            timestamp: Utc::now(),
+        }));
+        listener.record(&Event::ForceUpdateAll);
+
+        let output = registry.render();
+
+        assert!(output.contains("ddns_ip_changes_total{config_id=\"config-1\",record_name=\"home.example.com\",record_type=\"A\"} 1\n"));
+        assert!(output.contains("ddns_drift_detected_total{config_id=\"config-1\",record_name=\"home.example.com\",record_type=\"A\"} 1\n"));
+    }
+
+    #[test]
+    fn test_interested_in_result_events_only() {
+        let (listener, _) = create_listener();
+
+        assert!(listener.interested_in(&EventType::UpdateSucceeded));
+        assert!(listener.interested_in(&EventType::UpdateFailed));
+        assert!(listener.interested_in(&EventType::IpChanged));
+        assert!(!listener.interested_in(&EventType::ConfigChanged));
+        assert!(!listener.interested_in(&EventType::ForceUpdateAll));
+    }
+}
@@ -1 +1,7 @@
-mod ddns_service_tests; 
\ No newline at end of file
+mod ddns_service_tests;
+mod notification_tests;
+mod metrics_tests;
+mod health_tests;
+mod scheduler_tests;
+mod background_tests;
+mod factory_tests;
@@ -0,0 +1,159 @@
+use cloudflare_ddns::application::events::{Event, EventType, IpChangedEvent, RecordRef, UpdateFailedEvent};
+use cloudflare_ddns::application::notification::{
+    notification_from_event, NotificationRateLimiter, NotificationService, RateLimitDecision,
+};
+use cloudflare_ddns::domain::config::{ConfigId, IpType, RateLimitSettings};
+use cloudflare_ddns::domain::error::DomainError;
+use cloudflare_ddns::domain::notification::{Notification, Notifier};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+fn create_record() -> RecordRef {
+    RecordRef {
+        config_id: ConfigId::new("config-1"),
+        record_name: "home.example.com".to_string(),
+        record_type: "A".to_string(),
+        ip_type: IpType::IPv4,
+    }
+}
+
+fn ip_changed(new_ip: &str) -> Event {
+    Event::IpChanged(IpChangedEvent {
+        record: create_record(),
+        old_ip: Some("198.51.100.1".to_string()),
+        new_ip: new_ip.to_string(),
+        timestamp: Utc::now(),
+    })
+}
+
+// 記錄收到的通知
+struct RecordingNotifier {
+    events: Vec<String>,
+    received: Arc<Mutex<Vec<Notification>>>,
+}
+
+#[async_trait]
+impl Notifier for RecordingNotifier {
+    fn name(&self) -> &str {
+        "recording"
+    }
+
+    fn events(&self) -> &[String] {
+        &self.events
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), DomainError> {
+        self.received.lock().unwrap().push(notification.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod notification_message_tests {
+    use super::*;
+
+    #[test]
+    fn test_ip_changed_message() {
+        let notification = notification_from_event(&ip_changed("203.0.113.7"));
+        assert_eq!(notification.event, "ip-changed");
+        assert_eq!(notification.record_key.as_deref(), Some("config-1"));
+        assert!(notification.title.contains("home.example.com"));
+        assert!(notification.message.contains("198.51.100.1"));
+        assert!(notification.message.contains("203.0.113.7"));
+        assert_eq!(notification.payload["new_ip"], "203.0.113.7");
+    }
+
+    #[test]
+    fn test_update_failed_message_includes_error_kind() {
+        let event = Event::UpdateFailed(UpdateFailedEvent::from_error(
+            create_record(),
+            &DomainError::network("connection reset"),
+        ));
+        let notification = notification_from_event(&event);
+        assert_eq!(notification.event, "update-failed");
+        assert!(notification.message.contains("[network]"));
+        assert!(notification.message.contains("connection reset"));
+    }
+
+    #[test]
+    fn test_control_events_have_no_record_key() {
+        assert_eq!(notification_from_event(&Event::ConfigChanged).record_key, None);
+    }
+}
+
+#[cfg(test)]
+mod notification_filter_tests {
+    use super::*;
+
+    #[test]
+    fn test_default_events() {
+        assert!(NotificationService::should_notify(&[], &EventType::IpChanged));
+        assert!(NotificationService::should_notify(&[], &EventType::UpdateFailed));
+        assert!(!NotificationService::should_notify(&[], &EventType::UpdateSucceeded));
+        assert!(!NotificationService::should_notify(&[], &EventType::ForceUpdateAll));
+    }
+
+    #[test]
+    fn test_configured_events() {
+        let events = vec!["update-succeeded".to_string(), "bogus".to_string()];
+        assert!(NotificationService::should_notify(&events, &EventType::UpdateSucceeded));
+        assert!(!NotificationService::should_notify(&events, &EventType::IpChanged));
+    }
+}
+
+#[cfg(test)]
+mod rate_limit_tests {
+    use super::*;
+
+    fn create_limiter() -> NotificationRateLimiter {
+        NotificationRateLimiter::new(&RateLimitSettings { max_messages: 2, window_seconds: 60 })
+    }
+
+    #[test]
+    fn test_limits_within_window() {
+        let limiter = create_limiter();
+        let start = Instant::now();
+
+        assert_eq!(limiter.check_at("slack|config-1", start), RateLimitDecision::Allowed { suppressed: 0 });
+        assert_eq!(limiter.check_at("slack|config-1", start), RateLimitDecision::Allowed { suppressed: 0 });
+        assert_eq!(limiter.check_at("slack|config-1", start), RateLimitDecision::Limited);
+        assert_eq!(limiter.check_at("slack|config-1", start + Duration::from_secs(30)), RateLimitDecision::Limited);
+
+        // 其他記錄不受影響
+        assert_eq!(limiter.check_at("slack|config-2", start), RateLimitDecision::Allowed { suppressed: 0 });
+
+        // 窗口過後恢復發送，並報告被抑制的數量
+        assert_eq!(
+            limiter.check_at("slack|config-1", start + Duration::from_secs(60)),
+            RateLimitDecision::Allowed { suppressed: 2 },
+        );
+        assert_eq!(
+            limiter.check_at("slack|config-1", start + Duration::from_secs(61)),
+            RateLimitDecision::Allowed { suppressed: 0 },
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_applies_filter_and_rate_limit() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let notifiers: Vec<Arc<dyn Notifier>> = vec![Arc::new(RecordingNotifier {
+            events: vec!["ip-changed".to_string()],
+            received: received.clone(),
+        })];
+        let limiter = NotificationRateLimiter::new(&RateLimitSettings { max_messages: 1, window_seconds: 60 });
+
+        for ip in ["203.0.113.7", "203.0.113.8"] {
+            let event = ip_changed(ip);
+            NotificationService::dispatch(&notifiers, &limiter, &event.event_type(), &notification_from_event(&event));
+        }
+        let failed = Event::UpdateFailed(UpdateFailedEvent::from_error(create_record(), &DomainError::network("x")));
+        NotificationService::dispatch(&notifiers, &limiter, &failed.event_type(), &notification_from_event(&failed));
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].payload["new_ip"], "203.0.113.7");
+    }
+}
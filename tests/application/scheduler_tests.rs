@@ -0,0 +1,229 @@
+use cloudflare_ddns::application::ddns::{DdnsApplicationService, DetectionGroup};
+use cloudflare_ddns::domain::config::{ConfigId, DdnsConfig, IpType, PrefixDelegation, SourceSettings};
+use cloudflare_ddns::domain::dns::{DnsRecord, DnsService, DnsUpdateResult};
+use cloudflare_ddns::domain::error::DomainError;
+use cloudflare_ddns::domain::ip::IpService;
+use cloudflare_ddns::domain::state::StateRepository;
+use cloudflare_ddns::infrastructure::state::InMemoryStateRepository;
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// 記錄查詢次數的 IP 服務
+#[derive(Default)]
+struct CountingIpService {
+    ipv4_calls: AtomicUsize,
+    ipv6_calls: AtomicUsize,
+    fail: bool,
+}
+
+#[async_trait]
+impl IpService for CountingIpService {
+    async fn get_ipv4(&self) -> Result<String, DomainError> {
+        self.ipv4_calls.fetch_add(1, Ordering::SeqCst);
+        if self.fail {
+            return Err(DomainError::network("lookup failed"));
+        }
+        Ok("203.0.113.10".to_string())
+    }
+
+    async fn get_ipv6(&self) -> Result<String, DomainError> {
+        self.ipv6_calls.fetch_add(1, Ordering::SeqCst);
+        Ok("2001:db8::10".to_string())
+    }
+}
+
+/// 記錄更新請求的 DNS 服務
+#[derive(Default)]
+struct RecordingDnsService {
+    updates: Mutex<Vec<DnsRecord>>,
+}
+
+#[async_trait]
+impl DnsService for RecordingDnsService {
+    async fn update_record(&self, record: DnsRecord) -> Result<DnsUpdateResult, DomainError> {
+        self.updates.lock().unwrap().push(record.clone());
+        Ok(DnsUpdateResult { record, updated: true })
+    }
+
+    async fn get_record(&self, _zone_id: &str, _record_id: &str) -> Result<DnsRecord, DomainError> {
+        Err(DomainError::dns_service("not used"))
+    }
+
+    async fn get_records(&self, _zone_id: &str) -> Result<Vec<DnsRecord>, DomainError> {
+        Ok(Vec::new())
+    }
+
+    async fn create_record(&self, _zone_id: &str, record: DnsRecord) -> Result<DnsRecord, DomainError> {
+        Ok(record)
+    }
+
+    async fn delete_record(&self, _zone_id: &str, _record_id: &str) -> Result<(), DomainError> {
+        Err(DomainError::dns_service("not used"))
+    }
+}
+
+fn create_config(name: &str, ip_type: IpType, update_interval: u64, source: SourceSettings) -> DdnsConfig {
+    DdnsConfig {
+        id: ConfigId::new(name),
+        api_token: "test_token".to_string(),
+        zone_id: "test_zone".to_string(),
+        record_id: format!("{}_record", name),
+        record_name: format!("{}.example.com", name),
+        update_interval,
+        ip_type,
+        hooks: Default::default(),
+        source,
+        ipv6_loss: Default::default(),
+        prefix_delegation: None,
+        record_type: None,
+        content: None,
+        failover: None,
+        drift_check_interval: None,
+    }
+}
+
+struct Fixture {
+    ip_service: Arc<CountingIpService>,
+    dns_service: Arc<RecordingDnsService>,
+    state: Arc<InMemoryStateRepository>,
+}
+
+impl Fixture {
+    fn new(fail: bool) -> Self {
+        Self {
+            ip_service: Arc::new(CountingIpService { fail, ..Default::default() }),
+            dns_service: Arc::new(RecordingDnsService::default()),
+            state: Arc::new(InMemoryStateRepository::new()),
+        }
+    }
+
+    fn service(&self, config: DdnsConfig) -> DdnsApplicationService {
+        DdnsApplicationService::new(self.dns_service.clone(), self.ip_service.clone(), self.state.clone(), config)
+    }
+}
+
+#[cfg(test)]
+mod detection_group_tests {
+    use super::*;
+
+    #[test]
+    fn test_records_grouped_by_source_and_interval() {
+        let fixture = Fixture::new(false);
+        let wan2 = SourceSettings { interface: Some("ppp1".to_string()), ..Default::default() };
+        let services = vec![
+            fixture.service(create_config("a", IpType::IPv4, 300, Default::default())),
+            fixture.service(create_config("b", IpType::IPv6, 300, Default::default())),
+            fixture.service(create_config("c", IpType::IPv4, 600, Default::default())),
+            fixture.service(create_config("d", IpType::IPv4, 300, wan2)),
+            fixture.service(create_config("e", IpType::IPv4, 300, Default::default())),
+        ];
+
+        let groups = DetectionGroup::group(services);
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].config_ids(), vec![ConfigId::new("a"), ConfigId::new("b"), ConfigId::new("e")]);
+        assert_eq!(groups[1].config_ids(), vec![ConfigId::new("c")]);
+        assert_eq!(groups[1].interval().as_secs(), 600);
+        assert_eq!(groups[2].config_ids(), vec![ConfigId::new("d")]);
+    }
+
+    #[tokio::test]
+    async fn test_one_detection_per_family_fans_out() {
+        let fixture = Fixture::new(false);
+        let services = (0..5)
+            .map(|i| fixture.service(create_config(&format!("v4-{}", i), IpType::IPv4, 300, Default::default())))
+            .chain((0..2).map(|i| fixture.service(create_config(&format!("v6-{}", i), IpType::IPv6, 300, Default::default()))))
+            .collect();
+        let groups = DetectionGroup::group(services);
+        assert_eq!(groups.len(), 1);
+
+        let wait = groups[0].run_once().await;
+
+        assert_eq!(wait.as_secs(), 300);
+        assert_eq!(fixture.ip_service.ipv4_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fixture.ip_service.ipv6_calls.load(Ordering::SeqCst), 1);
+
+        let updates = fixture.dns_service.updates.lock().unwrap().clone();
+        assert_eq!(updates.len(), 7);
+        assert_eq!(updates.iter().filter(|record| record.content == "203.0.113.10").count(), 5);
+        assert_eq!(updates.iter().filter(|record| record.record_type == "AAAA").count(), 2);
+        assert_eq!(fixture.state.get_last_ip(&ConfigId::new("v4-3")).await.unwrap().as_deref(), Some("203.0.113.10"));
+    }
+
+    #[tokio::test]
+    async fn test_detection_failure_recorded_for_every_record() {
+        let fixture = Fixture::new(true);
+        let services = vec![
+            fixture.service(create_config("a", IpType::IPv4, 300, Default::default())),
+            fixture.service(create_config("b", IpType::IPv4, 300, Default::default())),
+        ];
+        let groups = DetectionGroup::group(services);
+
+        groups[0].run_once().await;
+
+        assert_eq!(fixture.ip_service.ipv4_calls.load(Ordering::SeqCst), 1);
+        assert!(fixture.dns_service.updates.lock().unwrap().is_empty());
+        for id in ["a", "b"] {
+            let state = fixture.state.get_state(&ConfigId::new(id)).await.unwrap().unwrap();
+            assert!(state.last_error.unwrap().contains("lookup failed"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prefix_delegation_combines_detected_prefix_per_record() {
+        let fixture = Fixture::new(false);
+        let host = |name: &str, suffix: &str| {
+            let mut config = create_config(name, IpType::IPv6, 300, Default::default());
+            config.prefix_delegation = Some(PrefixDelegation { prefix_length: 56, suffix: suffix.parse().unwrap() });
+            fixture.service(config)
+        };
+        let services = vec![host("nas", "::ab:0:0:0:1"), host("printer", "::12:3456:789a:bcde")];
+        let groups = DetectionGroup::group(services);
+
+        groups[0].run_once().await;
+
+        assert_eq!(fixture.ip_service.ipv6_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fixture.state.get_last_ip(&ConfigId::new("nas")).await.unwrap().as_deref(), Some("2001:db8:0:ab::1"));
+        assert_eq!(
+            fixture.state.get_last_ip(&ConfigId::new("printer")).await.unwrap().as_deref(),
+            Some("2001:db8::12:3456:789a:bcde")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_once_reports_each_record() {
+        let fixture = Fixture::new(false);
+        let services = vec![
+            fixture.service(create_config("a", IpType::IPv4, 300, Default::default())),
+            fixture.service(create_config("b", IpType::IPv6, 300, Default::default())),
+        ];
+        let groups = DetectionGroup::group(services);
+
+        let outcomes = groups[0].update_once().await;
+
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].config_id, ConfigId::new("a"));
+        assert_eq!(outcomes[0].record_type, "A");
+        assert_eq!(outcomes[0].content.as_deref(), Some("203.0.113.10"));
+        assert_eq!(outcomes[1].record_type, "AAAA");
+        assert_eq!(outcomes[1].content.as_deref(), Some("2001:db8::10"));
+        assert!(outcomes.iter().all(|outcome| outcome.updated && !outcome.failed()));
+    }
+
+    #[tokio::test]
+    async fn test_update_once_reports_failures() {
+        let fixture = Fixture::new(true);
+        let services = vec![
+            fixture.service(create_config("a", IpType::IPv4, 300, Default::default())),
+            fixture.service(create_config("b", IpType::IPv4, 300, Default::default())),
+        ];
+        let groups = DetectionGroup::group(services);
+
+        let outcomes = groups[0].update_once().await;
+
+        assert_eq!(fixture.ip_service.ipv4_calls.load(Ordering::SeqCst), 1);
+        assert!(outcomes.iter().all(|outcome| outcome.failed() && !outcome.updated && outcome.content.is_none()));
+        assert!(outcomes[0].error.as_deref().unwrap().contains("lookup failed"));
+    }
+}
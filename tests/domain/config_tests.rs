@@ -1,4 +1,4 @@
-use cloudflare_ddns::domain::config::{DdnsConfig, IpType};
+use cloudflare_ddns::domain::config::{ConfigId, DdnsConfig, IpType};
 use cloudflare_ddns::domain::error::DomainError;
 
 #[cfg(test)]
@@ -30,12 +30,21 @@ mod ddns_config_tests {
 
     fn create_valid_config() -> DdnsConfig {
         DdnsConfig {
+            id: ConfigId::new("test_config"),
             api_token: "api_token".to_string(),
             zone_id: "zone_id".to_string(),
             record_id: "record_id".to_string(),
             record_name: "example.com".to_string(),
             update_interval: 300,
             ip_type: IpType::IPv4,
+            hooks: Default::default(),
+            source: Default::default(),
+            ipv6_loss: Default::default(),
+            prefix_delegation: None,
+            record_type: None,
+            content: None,
+            failover: None,
+            drift_check_interval: None,
         }
     }
 
@@ -124,4 +133,632 @@ mod ddns_config_tests {
             }
         }
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod config_revision_tests {
+    use super::*;
+
+    fn create_config() -> DdnsConfig {
+        DdnsConfig {
+            id: ConfigId::new("config-1"),
+            api_token: "api_token".to_string(),
+            zone_id: "zone_id".to_string(),
+            record_id: "record_id".to_string(),
+            record_name: "example.com".to_string(),
+            update_interval: 300,
+            ip_type: IpType::IPv4,
+            hooks: Default::default(),
+            source: Default::default(),
+            ipv6_loss: Default::default(),
+            prefix_delegation: None,
+            record_type: None,
+            content: None,
+            failover: None,
+            drift_check_interval: None,
+        }
+    }
+
+    #[test]
+    fn test_revision_is_stable_for_same_content() {
+        let config = create_config();
+        assert_eq!(config.revision(), config.clone().revision());
+    }
+
+    #[test]
+    fn test_revision_changes_with_content() {
+        let config = create_config();
+        let mut changed = config.clone();
+        changed.update_interval = 600;
+        assert_ne!(config.revision(), changed.revision());
+    }
+
+    #[test]
+    fn test_revision_is_sha256_of_serialized_config() {
+        use sha2::{Digest, Sha256};
+
+        let config = create_config();
+        let expected = hex::encode(Sha256::digest(serde_json::to_vec(&config).unwrap()));
+        assert_eq!(config.revision(), expected);
+    }
+
+    #[test]
+    fn test_generated_ids_are_unique() {
+        assert_ne!(ConfigId::generate(), ConfigId::generate());
+    }
+
+    #[test]
+    fn test_missing_id_deserializes_as_empty() {
+        let config: DdnsConfig = serde_json::from_str(r#"{
+            "api_token": "t",
+            "zone_id": "z",
+            "record_id": "r",
+            "record_name": "example.com",
+            "update_interval": 300,
+            "ip_type": "ipv4"
+        }"#).unwrap();
+        assert!(config.id.is_empty());
+    }
+
+    #[test]
+    fn test_config_id_serializes_as_plain_string() {
+        let config = create_config();
+        let value = serde_json::to_value(&config).unwrap();
+        assert_eq!(value["id"], "config-1");
+        assert_eq!(config.id.to_string(), "config-1");
+    }
+}
+
+#[cfg(test)]
+mod config_document_tests {
+    use cloudflare_ddns::domain::config::{ConfigDocument, WebhookConfig};
+    use std::collections::HashMap;
+
+    const CONFIG_JSON: &str = r#"{
+        "id": "config-1",
+        "api_token": "t",
+        "zone_id": "z",
+        "record_id": "r",
+        "record_name": "example.com",
+        "update_interval": 300,
+        "ip_type": "ipv4"
+    }"#;
+
+    fn create_webhook(url: &str) -> WebhookConfig {
+        WebhookConfig {
+            name: None,
+            url: url.to_string(),
+            events: Vec::new(),
+            template: None,
+            secret: None,
+            headers: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_legacy_formats() {
+        let document = ConfigDocument::parse(&format!("[{}]", CONFIG_JSON)).unwrap();
+        assert_eq!(document.configs.len(), 1);
+        assert!(document.notifications.is_empty());
+
+        let document = ConfigDocument::parse(CONFIG_JSON).unwrap();
+        assert_eq!(document.configs[0].record_name, "example.com");
+    }
+
+    #[test]
+    fn test_parse_sectioned_format() {
+        let contents = format!(r#"{{
+            "configs": [{}],
+            "notifications": {{
+                "webhooks": [{{ "url": "http://localhost/hook", "events": ["ip-changed"], "secret": "s" }}]
+            }}
+        }}"#, CONFIG_JSON);
+        let document = ConfigDocument::parse(&contents).unwrap();
+        assert_eq!(document.configs.len(), 1);
+        assert_eq!(document.notifications.webhooks.len(), 1);
+        assert_eq!(document.notifications.webhooks[0].events, vec!["ip-changed"]);
+        assert_eq!(document.notifications.webhooks[0].secret.as_deref(), Some("s"));
+    }
+
+    #[test]
+    fn test_to_json_keeps_legacy_format_without_notifications() {
+        let mut document = ConfigDocument::parse(CONFIG_JSON).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&document.to_json().unwrap()).unwrap();
+        assert!(json.is_array());
+
+        document.notifications.webhooks.push(create_webhook("http://localhost/hook"));
+        let json: serde_json::Value = serde_json::from_str(&document.to_json().unwrap()).unwrap();
+        assert_eq!(json["configs"].as_array().unwrap().len(), 1);
+        assert_eq!(json["notifications"]["webhooks"][0]["url"], "http://localhost/hook");
+    }
+
+    #[test]
+    fn test_webhook_validation() {
+        assert!(create_webhook("https://example.com/hook").validate().is_ok());
+        assert!(create_webhook("ftp://example.com/hook").validate().is_err());
+        assert!(create_webhook("not a url").validate().is_err());
+
+        let mut webhook = create_webhook("https://example.com/hook");
+        webhook.secret = Some(String::new());
+        assert!(webhook.validate().is_err());
+    }
+}
+
+#[cfg(test)]
+mod telemetry_settings_tests {
+    use cloudflare_ddns::domain::config::{TelemetrySettings, TraceExporter};
+
+    #[test]
+    fn test_trace_exporter_try_from() {
+        assert_eq!(TraceExporter::try_from("otlp").unwrap(), TraceExporter::Otlp);
+        assert_eq!(TraceExporter::try_from("OTLP").unwrap(), TraceExporter::Otlp);
+        assert_eq!(TraceExporter::try_from("stdout").unwrap(), TraceExporter::Stdout);
+        assert_eq!(TraceExporter::try_from("console").unwrap(), TraceExporter::Stdout);
+        assert_eq!(TraceExporter::try_from("none").unwrap(), TraceExporter::None);
+        assert_eq!(TraceExporter::try_from("").unwrap(), TraceExporter::None);
+
+        assert!(TraceExporter::try_from("jaeger").is_err());
+    }
+
+    #[test]
+    fn test_telemetry_disabled_by_default() {
+        let settings = TelemetrySettings::default();
+
+        assert!(!settings.is_enabled());
+        assert_eq!(settings.service_name, "cloudflare-ddns");
+        assert!(settings.otlp_endpoint.is_none());
+    }
+}
+
+#[cfg(test)]
+mod log_format_tests {
+    use cloudflare_ddns::domain::config::LogFormat;
+
+    #[test]
+    fn test_log_format_try_from() {
+        assert_eq!(LogFormat::try_from("json").unwrap(), LogFormat::Json);
+        assert_eq!(LogFormat::try_from("JSON").unwrap(), LogFormat::Json);
+        assert_eq!(LogFormat::try_from("text").unwrap(), LogFormat::Text);
+        assert_eq!(LogFormat::try_from("").unwrap(), LogFormat::Text);
+
+        assert!(LogFormat::try_from("xml").is_err());
+    }
+}
+
+#[cfg(test)]
+mod http_settings_tests {
+    use cloudflare_ddns::domain::config::{ConfigDocument, HttpClientSettings};
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn test_parse_http_section() {
+        let document = ConfigDocument::parse(r#"{
+            "configs": [],
+            "http": {
+                "proxy": "socks5h://127.0.0.1:1080",
+                "connect_timeout_secs": 5,
+                "timeout_secs": 20,
+                "ca_bundle": "/etc/ssl/corp.pem",
+                "bind_address": "192.0.2.10",
+                "pin_ip_family": true
+            }
+        }"#).unwrap();
+
+        let http = &document.http;
+        assert_eq!(http.proxy.as_deref(), Some("socks5h://127.0.0.1:1080"));
+        assert_eq!(http.connect_timeout_secs, Some(5));
+        assert_eq!(http.timeout_secs, Some(20));
+        assert_eq!(http.ca_bundle.as_deref(), Some("/etc/ssl/corp.pem"));
+        assert_eq!(http.bind_address, Some(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 10))));
+        assert!(http.pin_ip_family);
+        assert!(http.validate().is_ok());
+
+        // 非默認的 HTTP 設置保存時寫出完整格式
+        let json: serde_json::Value = serde_json::from_str(&document.to_json().unwrap()).unwrap();
+        assert_eq!(json["http"]["timeout_secs"], 20);
+        assert_eq!(json["http"]["pin_ip_family"], true);
+    }
+
+    #[test]
+    fn test_default_http_settings_are_omitted() {
+        let document = ConfigDocument::parse("[]").unwrap();
+        assert!(document.http.is_default());
+
+        let json: serde_json::Value = serde_json::from_str(&document.to_json().unwrap()).unwrap();
+        assert!(json.is_array());
+    }
+
+    #[test]
+    fn test_http_settings_validation() {
+        let invalid = [
+            HttpClientSettings { proxy: Some("ftp://proxy.example.com".to_string()), ..Default::default() },
+            HttpClientSettings { proxy: Some("not a url".to_string()), ..Default::default() },
+            HttpClientSettings { timeout_secs: Some(0), ..Default::default() },
+            HttpClientSettings { connect_timeout_secs: Some(0), ..Default::default() },
+            HttpClientSettings { ca_bundle: Some(" ".to_string()), ..Default::default() },
+        ];
+        for settings in invalid {
+            assert!(settings.validate().is_err(), "{:?} 應該無效", settings);
+        }
+
+        let proxy = HttpClientSettings { proxy: Some("http://proxy.example.com:3128".to_string()), ..Default::default() };
+        assert!(proxy.validate().is_ok());
+    }
+}
+
+#[cfg(test)]
+mod source_settings_tests {
+    use cloudflare_ddns::domain::config::{DdnsConfig, IpType, SourceSettings};
+
+    #[test]
+    fn test_parse_source_section() {
+        let config: DdnsConfig = serde_json::from_str(r#"{
+            "api_token": "t",
+            "zone_id": "z",
+            "record_id": "r",
+            "record_name": "wan1.example.com",
+            "update_interval": 300,
+            "ip_type": "ipv4",
+            "source": { "interface": "ppp0" }
+        }"#).unwrap();
+
+        assert_eq!(config.source.interface.as_deref(), Some("ppp0"));
+        assert!(!config.source.from_interface);
+        assert!(config.validate().is_ok());
+
+        // 未設置時不寫出 source
+        let mut config = config;
+        config.source = SourceSettings::default();
+        let json = serde_json::to_value(&config).unwrap();
+        assert!(json.get("source").is_none());
+    }
+
+    #[test]
+    fn test_source_validation() {
+        let both = SourceSettings {
+            interface: Some("eth1".to_string()),
+            address: Some("192.0.2.1".parse().unwrap()),
+            ..Default::default()
+        };
+        assert!(both.validate(&IpType::IPv4).is_err());
+
+        let wrong_family = SourceSettings { address: Some("2001:db8::1".parse().unwrap()), ..Default::default() };
+        assert!(wrong_family.validate(&IpType::IPv4).is_err());
+        assert!(wrong_family.validate(&IpType::IPv6).is_ok());
+
+        let no_interface = SourceSettings { from_interface: true, ..Default::default() };
+        assert!(no_interface.validate(&IpType::IPv4).is_err());
+
+        let empty = SourceSettings { interface: Some(" ".to_string()), ..Default::default() };
+        assert!(empty.validate(&IpType::IPv4).is_err());
+    }
+}
+
+#[cfg(test)]
+mod record_group_tests {
+    use cloudflare_ddns::domain::config::{ConfigDocument, ConfigId, IpFamilies, IpType, RecordGroup};
+
+    fn parse_group(value: serde_json::Value) -> RecordGroup {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn home_group() -> RecordGroup {
+        parse_group(serde_json::json!({
+            "id": "home",
+            "api_token": "token",
+            "zone_id": "zone",
+            "hostnames": ["example.com", "www.example.com", "vpn.example.com"],
+            "ip_type": "both",
+            "update_interval": 300
+        }))
+    }
+
+    #[test]
+    fn test_group_expands_per_hostname_and_family() {
+        let group = home_group();
+        assert_eq!(group.ip_type, IpFamilies::Both);
+        assert!(group.validate().is_ok());
+
+        let configs = group.expand();
+        assert_eq!(configs.len(), 6);
+        assert_eq!(configs[0].id, ConfigId::new("home:example.com:ipv4"));
+        assert_eq!(configs[1].id, ConfigId::new("home:example.com:ipv6"));
+        assert_eq!(configs[5].record_name, "vpn.example.com");
+        assert_eq!(configs[5].ip_type, IpType::IPv6);
+        assert!(configs.iter().all(|config| config.record_id.is_empty() && config.api_token == "token"));
+    }
+
+    #[test]
+    fn test_group_ip_type_defaults_to_ipv4() {
+        let group = parse_group(serde_json::json!({
+            "api_token": "token",
+            "zone_id": "zone",
+            "hostnames": ["example.com"],
+            "update_interval": 300
+        }));
+        assert_eq!(group.ip_type, IpFamilies::IPv4);
+        assert_eq!(group.expand().len(), 1);
+    }
+
+    #[test]
+    fn test_group_validation() {
+        let mut group = home_group();
+        group.hostnames.clear();
+        assert!(group.validate().is_err());
+
+        let mut group = home_group();
+        group.hostnames.push("WWW.example.com".to_string());
+        assert!(group.validate().is_err());
+
+        // 雙棧記錄組不能綁定單一協議族的地址
+        let mut group = home_group();
+        group.source.address = Some("192.0.2.1".parse().unwrap());
+        assert!(group.validate().is_err());
+        group.ip_type = IpFamilies::IPv4;
+        assert!(group.validate().is_ok());
+    }
+
+    #[test]
+    fn test_document_with_groups_round_trip() {
+        let document = ConfigDocument::parse(r#"{
+            "configs": [],
+            "groups": [{
+                "id": "home",
+                "api_token": "token",
+                "zone_id": "zone",
+                "hostnames": ["example.com"],
+                "ip_type": "both",
+                "update_interval": 300
+            }]
+        }"#).unwrap();
+        assert_eq!(document.groups.len(), 1);
+
+        // 有記錄組時不能寫出舊的數組格式
+        let json = document.to_json().unwrap();
+        let reparsed = ConfigDocument::parse(&json).unwrap();
+        assert_eq!(reparsed.groups[0].hostnames, vec!["example.com".to_string()]);
+    }
+}
+
+#[cfg(test)]
+mod ipv6_loss_settings_tests {
+    use cloudflare_ddns::domain::config::{
+        ConfigId, DdnsConfig, IpFamilies, IpType, Ipv6LossAction, Ipv6LossSettings, RecordGroup,
+    };
+    use std::net::Ipv6Addr;
+
+    fn dual_stack_group(ipv6_loss: serde_json::Value) -> RecordGroup {
+        serde_json::from_value(serde_json::json!({
+            "id": "home",
+            "api_token": "token",
+            "zone_id": "zone",
+            "hostnames": ["example.com"],
+            "ip_type": "both",
+            "update_interval": 300,
+            "ipv6_loss": ipv6_loss
+        })).unwrap()
+    }
+
+    fn ipv6_config(action: Ipv6LossAction) -> DdnsConfig {
+        DdnsConfig {
+            id: ConfigId::new("v6"),
+            api_token: "token".to_string(),
+            zone_id: "zone".to_string(),
+            record_id: "record".to_string(),
+            record_name: "example.com".to_string(),
+            update_interval: 300,
+            ip_type: IpType::IPv6,
+            hooks: Default::default(),
+            source: Default::default(),
+            ipv6_loss: Ipv6LossSettings { action, ..Default::default() },
+            prefix_delegation: None,
+            record_type: None,
+            content: None,
+            failover: None,
+            drift_check_interval: None,
+        }
+    }
+
+    #[test]
+    fn test_defaults() {
+        let settings: Ipv6LossSettings = serde_json::from_value(serde_json::json!({ "action": "park" })).unwrap();
+        assert_eq!(settings.action, Ipv6LossAction::Park);
+        assert_eq!(settings.after_failures, 3);
+        assert_eq!(settings.park_address, "100::".parse::<Ipv6Addr>().unwrap());
+        assert!(Ipv6LossSettings::default().is_default());
+    }
+
+    #[test]
+    fn test_group_applies_only_to_aaaa_member() {
+        let group = dual_stack_group(serde_json::json!({ "action": "delete", "after_failures": 2 }));
+        assert!(group.validate().is_ok());
+
+        let configs = group.expand();
+        assert!(configs[0].ipv6_loss.is_default());
+        assert_eq!(configs[1].ipv6_loss.action, Ipv6LossAction::Delete);
+        assert_eq!(configs[1].ipv6_loss.after_failures, 2);
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut group = dual_stack_group(serde_json::json!({ "action": "park", "after_failures": 0 }));
+        assert!(group.validate().is_err());
+
+        group.ipv6_loss.after_failures = 1;
+        group.ip_type = IpFamilies::IPv4;
+        assert!(group.validate().is_err());
+
+        // 固定記錄 ID 的記錄只能停放，不能刪除
+        assert!(ipv6_config(Ipv6LossAction::Park).validate().is_ok());
+        assert!(ipv6_config(Ipv6LossAction::Delete).validate().is_err());
+    }
+}
+
+#[cfg(test)]
+mod prefix_delegation_tests {
+    use cloudflare_ddns::domain::config::{IpFamilies, PrefixDelegation, RecordGroup};
+
+    fn delegation(prefix_length: u8, suffix: &str) -> PrefixDelegation {
+        PrefixDelegation { prefix_length, suffix: suffix.parse().unwrap() }
+    }
+
+    #[test]
+    fn test_apply_keeps_interface_id() {
+        let nas = delegation(64, "::211:32ff:fe12:3456");
+        assert!(nas.validate().is_ok());
+        assert_eq!(nas.apply("2001:db8:1:2::abcd").unwrap(), "2001:db8:1:2:211:32ff:fe12:3456");
+
+        // /56 委派時子網號也屬於後綴
+        let server = delegation(56, "::5:0:0:0:10");
+        assert_eq!(server.apply("2001:db8:aa:bb00::1").unwrap(), "2001:db8:aa:bb05::10");
+
+        assert!(nas.apply("203.0.113.1").is_err());
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(delegation(0, "::1").validate().is_err());
+        assert!(delegation(128, "::1").validate().is_err());
+        // 後綴與前綴重疊
+        assert!(delegation(64, "2001:db8::1").validate().is_err());
+    }
+
+    #[test]
+    fn test_group_assigns_suffix_per_hostname() {
+        let group: RecordGroup = serde_json::from_value(serde_json::json!({
+            "id": "lan",
+            "api_token": "token",
+            "zone_id": "zone",
+            "hostnames": ["router.example.com", "nas.example.com"],
+            "ip_type": "both",
+            "update_interval": 300,
+            "prefix_delegation": {
+                "prefix_length": 56,
+                "suffixes": { "NAS.example.com": "::1:0:0:0:20" }
+            }
+        })).unwrap();
+        assert!(group.validate().is_ok());
+
+        let configs = group.expand();
+        // 未配置後綴的主機名和 A 記錄直接使用檢測到的地址
+        assert!(configs[1].prefix_delegation.is_none());
+        assert!(configs[2].prefix_delegation.is_none());
+        assert_eq!(configs[3].prefix_delegation.as_ref().unwrap().prefix_length, 56);
+
+        let mut unknown = group.clone();
+        unknown.hostnames.pop();
+        assert!(unknown.validate().is_err());
+
+        let mut ipv4_only = group;
+        ipv4_only.ip_type = IpFamilies::IPv4;
+        assert!(ipv4_only.validate().is_err());
+    }
+}
+
+#[cfg(test)]
+mod record_content_tests {
+    use cloudflare_ddns::domain::config::{ConfigId, DdnsConfig, IpType};
+
+    fn config(ip_type: IpType, record_type: Option<&str>, content: Option<&str>) -> DdnsConfig {
+        DdnsConfig {
+            id: ConfigId::new("txt"),
+            api_token: "token".to_string(),
+            zone_id: "zone".to_string(),
+            record_id: "record".to_string(),
+            record_name: "_ddns.example.com".to_string(),
+            update_interval: 300,
+            ip_type,
+            hooks: Default::default(),
+            source: Default::default(),
+            ipv6_loss: Default::default(),
+            prefix_delegation: None,
+            record_type: record_type.map(str::to_string),
+            content: content.map(str::to_string),
+            failover: None,
+            drift_check_interval: None,
+        }
+    }
+
+    #[test]
+    fn test_record_type_defaults_to_address_type() {
+        assert_eq!(config(IpType::IPv4, None, None).dns_record_type(), "A");
+        assert_eq!(config(IpType::IPv6, None, None).dns_record_type(), "AAAA");
+        assert_eq!(config(IpType::IPv4, Some("txt"), Some("{ip}")).dns_record_type(), "TXT");
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(config(IpType::IPv4, Some("TXT"), Some("ip={ipv4} ipv6={ipv6}")).validate().is_ok());
+        assert!(config(IpType::IPv4, Some("CNAME"), Some("home.example.net")).validate().is_ok());
+
+        // 非地址記錄必須提供內容模板
+        assert!(config(IpType::IPv4, Some("TXT"), None).validate().is_err());
+        assert!(config(IpType::IPv4, Some("TXT"), Some("{address}")).validate().is_err());
+        assert!(config(IpType::IPv4, Some("AAAA"), None).validate().is_err());
+        assert!(config(IpType::IPv4, Some("T X T"), Some("{ip}")).validate().is_err());
+    }
+}
+
+#[cfg(test)]
+mod dual_stack_config_tests {
+    use cloudflare_ddns::domain::config::{ConfigDocument, ConfigId, IpFamilies, IpType, Ipv6LossAction};
+
+    fn dual_stack_entry() -> serde_json::Value {
+        serde_json::json!({
+            "id": "home",
+            "api_token": "token",
+            "zone_id": "zone",
+            "record_name": "example.com",
+            "ip_type": "both",
+            "update_interval": 300,
+            "ipv6_loss": { "action": "delete", "after_failures": 2 }
+        })
+    }
+
+    #[test]
+    fn test_single_config_becomes_one_hostname_group() {
+        let contents = serde_json::json!({ "configs": [dual_stack_entry()] }).to_string();
+        let document = ConfigDocument::parse(&contents).unwrap();
+        assert!(document.configs.is_empty());
+        assert_eq!(document.groups.len(), 1);
+
+        let group = &document.groups[0];
+        assert_eq!(group.id, ConfigId::new("home"));
+        assert_eq!(group.hostnames, vec!["example.com".to_string()]);
+        assert_eq!(group.ip_type, IpFamilies::Both);
+        assert!(group.validate().is_ok());
+
+        let members = group.expand();
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].ip_type, IpType::IPv4);
+        assert!(members[0].ipv6_loss.is_default());
+        assert_eq!(members[1].ip_type, IpType::IPv6);
+        assert_eq!(members[1].ipv6_loss.action, Ipv6LossAction::Delete);
+        assert!(members.iter().all(|member| member.record_id.is_empty() && member.record_name == "example.com"));
+    }
+
+    #[test]
+    fn test_legacy_array_format() {
+        let mut single = dual_stack_entry();
+        single["id"] = serde_json::json!("v4");
+        single["ip_type"] = serde_json::json!("ipv4");
+        single["record_id"] = serde_json::json!("record");
+        single.as_object_mut().unwrap().remove("ipv6_loss");
+
+        let contents = serde_json::json!([single, dual_stack_entry()]).to_string();
+        let document = ConfigDocument::parse(&contents).unwrap();
+        assert_eq!(document.configs.len(), 1);
+        assert_eq!(document.configs[0].ip_type, IpType::IPv4);
+        assert_eq!(document.groups.len(), 1);
+    }
+
+    #[test]
+    fn test_record_id_is_rejected() {
+        let mut entry = dual_stack_entry();
+        entry["record_id"] = serde_json::json!("record");
+        assert!(ConfigDocument::parse(&serde_json::json!([entry]).to_string()).is_err());
+
+        // 空的記錄 ID 視為未設置
+        entry["record_id"] = serde_json::json!("");
+        assert!(ConfigDocument::parse(&serde_json::json!([entry]).to_string()).is_ok());
+    }
+}
@@ -0,0 +1,41 @@
+use cloudflare_ddns::domain::dns::{render_template, template_placeholders, TemplateContext};
+use chrono::{TimeZone, Utc};
+
+fn context() -> TemplateContext {
+    TemplateContext {
+        ip: "203.0.113.10".to_string(),
+        ipv4: Some("203.0.113.10".to_string()),
+        ipv6: None,
+        hostname: "status.example.com".to_string(),
+        time: Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod template_tests {
+    use super::*;
+
+    #[test]
+    fn test_render_placeholders() {
+        let content = render_template("v=ddns1 host={hostname} ip={ip} at={timestamp} ({unix_time})", &context()).unwrap();
+        assert_eq!(content, "v=ddns1 host=status.example.com ip=203.0.113.10 at=2026-01-02T03:04:05Z (1767323045)");
+    }
+
+    #[test]
+    fn test_escaped_braces() {
+        assert_eq!(render_template("{{\"ip\": \"{ipv4}\"}}", &context()).unwrap(), "{\"ip\": \"203.0.113.10\"}");
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        assert!(template_placeholders("{address}").is_err());
+        assert!(template_placeholders("{ip").is_err());
+        assert!(template_placeholders("ip}").is_err());
+        assert_eq!(template_placeholders("{ipv4} {ipv6}").unwrap(), vec!["ipv4", "ipv6"]);
+    }
+
+    #[test]
+    fn test_missing_family_is_an_error() {
+        assert!(render_template("{ipv6}", &context()).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use cloudflare_ddns::domain::config::{FailoverSettings, HealthCheck, IpType};
+use cloudflare_ddns::domain::failover::{FailoverState, FailoverTarget};
+
+#[cfg(test)]
+mod hysteresis_tests {
+    use super::*;
+
+    #[test]
+    fn test_switches_after_consecutive_failures_only() {
+        let mut state = FailoverState::default();
+        assert_eq!(state.observe(false, 3, 2), None);
+        assert_eq!(state.observe(false, 3, 2), None);
+        // 中途成功一次重新計數
+        assert_eq!(state.observe(true, 3, 2), None);
+        assert_eq!(state.observe(false, 3, 2), None);
+        assert_eq!(state.observe(false, 3, 2), None);
+        assert_eq!(state.observe(false, 3, 2), Some(FailoverTarget::Backup));
+        assert_eq!(state.active(), FailoverTarget::Backup);
+    }
+
+    #[test]
+    fn test_recovers_after_consecutive_successes() {
+        let mut state = FailoverState::new(FailoverTarget::Backup);
+        assert_eq!(state.observe(true, 3, 2), None);
+        assert_eq!(state.observe(false, 3, 2), None);
+        assert_eq!(state.observe(true, 3, 2), None);
+        assert_eq!(state.observe(true, 3, 2), Some(FailoverTarget::Primary));
+        assert_eq!(state.active(), FailoverTarget::Primary);
+    }
+}
+
+#[cfg(test)]
+mod failover_settings_tests {
+    use super::*;
+
+    fn settings(value: serde_json::Value) -> FailoverSettings {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_with_defaults() {
+        let failover = settings(serde_json::json!({
+            "backup": "198.51.100.20",
+            "checks": [
+                { "type": "tcp", "port": 443 },
+                { "type": "http", "url": "http://{ip}:8080/health", "expected_status": 204 }
+            ]
+        }));
+        assert_eq!(failover.failure_threshold, 3);
+        assert_eq!(failover.recovery_threshold, 3);
+        assert_eq!(failover.checks[0], HealthCheck::Tcp { port: 443, timeout_seconds: 5 });
+        assert!(failover.validate(&IpType::IPv4).is_ok());
+    }
+
+    #[test]
+    fn test_validation() {
+        let tcp = serde_json::json!([{ "type": "tcp", "port": 443 }]);
+        assert!(settings(serde_json::json!({ "backup": "2001:db8::1", "checks": tcp })).validate(&IpType::IPv4).is_err());
+        assert!(settings(serde_json::json!({ "backup": "198.51.100.20", "checks": [] })).validate(&IpType::IPv4).is_err());
+        assert!(settings(serde_json::json!({ "backup": "198.51.100.20", "checks": tcp, "failure_threshold": 0 }))
+            .validate(&IpType::IPv4)
+            .is_err());
+        assert!(settings(serde_json::json!({
+            "backup": "198.51.100.20",
+            "checks": [{ "type": "http", "url": "ftp://{ip}/" }]
+        })).validate(&IpType::IPv4).is_err());
+    }
+
+    #[test]
+    fn test_target_url_brackets_ipv6() {
+        assert_eq!(
+            HealthCheck::target_url("https://{ip}:8443/health", "2001:db8::1".parse().unwrap()),
+            "https://[2001:db8::1]:8443/health"
+        );
+    }
+}
@@ -1 +1,3 @@
-mod config_tests; 
\ No newline at end of file
+mod config_tests; 
+mod content_template_tests;
+mod failover_tests;
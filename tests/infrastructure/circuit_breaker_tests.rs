@@ -0,0 +1,115 @@
+use cloudflare_ddns::domain::error::{ApiErrorType, DomainError, NetworkErrorType};
+use cloudflare_ddns::domain::http::HttpClient;
+use cloudflare_ddns::infrastructure::http::{
+    circuit_key, CircuitBreakerConfig, CircuitBreakerRegistry, CircuitState, ReqwestHttpClient,
+};
+use httpmock::prelude::*;
+use std::time::Duration;
+
+fn registry(failure_threshold: u32, open_duration: Duration) -> CircuitBreakerRegistry {
+    CircuitBreakerRegistry::new(CircuitBreakerConfig { failure_threshold, open_duration })
+}
+
+fn server_error() -> Result<(), DomainError> {
+    Err(DomainError::Network(NetworkErrorType::HttpError(502)))
+}
+
+#[cfg(test)]
+mod breaker_tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_consecutive_failures() {
+        let breakers = registry(3, Duration::from_secs(60));
+
+        for _ in 0..2 {
+            assert!(breakers.acquire("api.example.com").is_ok());
+            breakers.record("api.example.com", &server_error());
+        }
+        assert_eq!(breakers.state("api.example.com"), CircuitState::Closed);
+
+        breakers.record("api.example.com", &server_error());
+        assert_eq!(breakers.state("api.example.com"), CircuitState::Open);
+
+        let error = breakers.acquire("api.example.com").unwrap_err();
+        assert_eq!(error.kind(), "circuit_open");
+        assert!(!error.is_retryable());
+
+        // 其他主機不受影響
+        assert!(breakers.acquire("other.example.com").is_ok());
+    }
+
+    #[test]
+    fn test_client_errors_and_successes_reset_failures() {
+        let breakers = registry(2, Duration::from_secs(60));
+
+        breakers.record("api.example.com", &server_error());
+        breakers.record("api.example.com", &Ok(()));
+        breakers.record("api.example.com", &server_error());
+        breakers.record::<()>("api.example.com", &Err(DomainError::Network(NetworkErrorType::HttpError(404))));
+        breakers.record::<()>(
+            "api.example.com",
+            &Err(DomainError::Api(ApiErrorType::RateLimitError("429".to_string(), None))),
+        );
+
+        assert_eq!(breakers.state("api.example.com"), CircuitState::Closed);
+        assert_eq!(breakers.snapshot()[0].consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_half_open_allows_single_probe() {
+        let breakers = registry(1, Duration::from_millis(20));
+        breakers.record("api.example.com", &server_error());
+        assert!(breakers.acquire("api.example.com").is_err());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breakers.acquire("api.example.com").is_ok());
+        assert_eq!(breakers.state("api.example.com"), CircuitState::HalfOpen);
+        // 探測期間拒絕其他請求
+        assert!(breakers.acquire("api.example.com").is_err());
+
+        // 探測失敗重新開啟
+        breakers.record("api.example.com", &server_error());
+        assert_eq!(breakers.state("api.example.com"), CircuitState::Open);
+
+        // 探測成功關閉
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breakers.acquire("api.example.com").is_ok());
+        breakers.record("api.example.com", &Ok(()));
+        assert_eq!(breakers.state("api.example.com"), CircuitState::Closed);
+        assert!(breakers.acquire("api.example.com").is_ok());
+
+        let snapshot = breakers.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert!(snapshot[0].opened_at.is_some());
+        assert!(snapshot[0].last_error.as_deref().unwrap().contains("502"));
+    }
+
+    #[test]
+    fn test_circuit_key() {
+        assert_eq!(circuit_key("https://api.cloudflare.com/client/v4/zones"), "api.cloudflare.com");
+        assert_eq!(circuit_key("http://127.0.0.1:8080/ip"), "127.0.0.1:8080");
+        assert_eq!(circuit_key("not a url"), "not a url");
+    }
+
+    #[tokio::test]
+    async fn test_open_breaker_skips_requests() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/ip");
+            then.status(500);
+        });
+
+        let breakers: &'static CircuitBreakerRegistry = Box::leak(Box::new(registry(2, Duration::from_secs(60))));
+        let client = ReqwestHttpClient::new().with_circuit_breakers(breakers);
+
+        for _ in 0..2 {
+            assert!(client.get(&server.url("/ip"), None).await.is_err());
+        }
+        let error = client.get(&server.url("/ip"), None).await.unwrap_err();
+
+        mock.assert_hits(2);
+        assert!(matches!(error, DomainError::Network(NetworkErrorType::CircuitOpen(_))));
+        assert_eq!(breakers.state(&circuit_key(&server.url("/ip"))), CircuitState::Open);
+    }
+}
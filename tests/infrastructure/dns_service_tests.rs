@@ -28,6 +28,10 @@ impl DnsService for TestDnsService {
     async fn create_record(&self, _zone_id: &str, _record: DnsRecord) -> Result<DnsRecord, DomainError> {
         self.create_record_result.clone()
     }
+    
+    async fn delete_record(&self, _zone_id: &str, _record_id: &str) -> Result<(), DomainError> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -147,4 +151,291 @@ mod dns_service_tests {
         let result = dns_service.create_record("test_zone", record.clone()).await;
         assert!(result.is_err());
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod reachability_tests {
+    use cloudflare_ddns::domain::error::{DomainError, NetworkErrorType};
+    use cloudflare_ddns::infrastructure::dns::ReachabilityTracker;
+
+    #[test]
+    fn test_unknown_before_first_call() {
+        let tracker = ReachabilityTracker::new();
+
+        assert_eq!(tracker.snapshot().is_reachable(), None);
+    }
+
+    #[test]
+    fn test_connection_errors_are_unreachable() {
+        let tracker = ReachabilityTracker::new();
+        tracker.record::<()>(&Err(DomainError::Network(NetworkErrorType::ConnectionError("refused".to_string()))));
+
+        let snapshot = tracker.snapshot();
+        assert_eq!(snapshot.is_reachable(), Some(false));
+        assert!(snapshot.last_error.unwrap().contains("refused"));
+
+        tracker.record(&Ok(()));
+        assert_eq!(tracker.snapshot().is_reachable(), Some(true));
+    }
+
+    #[test]
+    fn test_api_responses_count_as_reachable() {
+        let tracker = ReachabilityTracker::new();
+        tracker.record::<()>(&Err(DomainError::Network(NetworkErrorType::HttpError(403))));
+        tracker.record::<()>(&Err(DomainError::LogicError("Cloudflare API error".to_string())));
+
+        assert_eq!(tracker.snapshot().is_reachable(), Some(true));
+
+        tracker.record::<()>(&Err(DomainError::Network(NetworkErrorType::HttpError(502))));
+        assert_eq!(tracker.snapshot().is_reachable(), Some(false));
+    }
+}
+
+#[cfg(test)]
+mod cloudflare_client_tests {
+    use super::*;
+    use cloudflare_ddns::domain::config::{ConfigId, DdnsConfig, IpType};
+    use cloudflare_ddns::domain::error::NetworkErrorType;
+    use cloudflare_ddns::domain::http::{HttpClient, HttpClientExt};
+    use cloudflare_ddns::infrastructure::dns::CloudflareDnsService;
+    use cloudflare_ddns::infrastructure::http::RetryableHttpClient;
+    use reqwest::header::{HeaderMap, AUTHORIZATION, CONTENT_TYPE};
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// 記錄的請求：方法、URL、請求體和請求頭
+    type RecordedRequest = (&'static str, String, Option<String>, HeaderMap);
+
+    /// 按順序返回預設響應的 HTTP 客戶端
+    #[derive(Default)]
+    struct MockHttpClient {
+        responses: Mutex<VecDeque<Result<String, DomainError>>>,
+        requests: Mutex<Vec<RecordedRequest>>,
+    }
+
+    impl MockHttpClient {
+        fn with_responses(responses: Vec<Result<String, DomainError>>) -> Arc<Self> {
+            Arc::new(Self {
+                responses: Mutex::new(responses.into()),
+                requests: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn respond(&self, method: &'static str, url: &str, body: Option<String>, headers: Option<HeaderMap>) -> Result<String, DomainError> {
+            self.requests.lock().unwrap().push((method, url.to_string(), body, headers.unwrap_or_default()));
+            self.responses.lock().unwrap().pop_front().expect("沒有預設的響應")
+        }
+    }
+
+    #[async_trait]
+    impl HttpClient for MockHttpClient {
+        async fn get(&self, url: &str, headers: Option<HeaderMap>) -> Result<String, DomainError> {
+            self.respond("GET", url, None, headers)
+        }
+
+        async fn post(&self, url: &str, body: Option<String>, headers: Option<HeaderMap>) -> Result<String, DomainError> {
+            self.respond("POST", url, body, headers)
+        }
+
+        async fn put(&self, url: &str, body: Option<String>, headers: Option<HeaderMap>) -> Result<String, DomainError> {
+            self.respond("PUT", url, body, headers)
+        }
+
+        async fn delete(&self, url: &str, headers: Option<HeaderMap>) -> Result<String, DomainError> {
+            self.respond("DELETE", url, None, headers)
+        }
+    }
+
+    fn create_config() -> DdnsConfig {
+        DdnsConfig {
+            id: ConfigId::new("test_config"),
+            api_token: "test_token".to_string(),
+            zone_id: "test_zone".to_string(),
+            record_id: "test_record".to_string(),
+            record_name: "test.example.com".to_string(),
+            update_interval: 300,
+            ip_type: IpType::IPv4,
+            hooks: Default::default(),
+            source: Default::default(),
+            ipv6_loss: Default::default(),
+            prefix_delegation: None,
+            record_type: None,
+            content: None,
+            failover: None,
+            drift_check_interval: None,
+        }
+    }
+
+    fn record_response(content: &str) -> Result<String, DomainError> {
+        Ok(format!(
+            r#"{{"success":true,"errors":[],"result":{{"id":"test_record","name":"test.example.com","type":"A","content":"{}","ttl":120,"proxied":false}}}}"#,
+            content
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_json_helpers_work_on_trait_objects() {
+        let mock = MockHttpClient::with_responses(vec![Ok(r#"{"value":1}"#.to_string())]);
+        let client: Arc<dyn HttpClient> = mock.clone();
+
+        let response: serde_json::Value = client
+            .post_json("https://example.com/api", Some(&serde_json::json!({"name": "test"})), None)
+            .await
+            .unwrap();
+        assert_eq!(response["value"], 1);
+
+        let requests = mock.requests.lock().unwrap();
+        let (method, _, body, headers) = &requests[0];
+        assert_eq!(*method, "POST");
+        assert_eq!(body.as_deref(), Some(r#"{"name":"test"}"#));
+        assert_eq!(headers[CONTENT_TYPE], "application/json");
+    }
+
+    #[tokio::test]
+    async fn test_cloudflare_service_uses_injected_client() {
+        let mock = MockHttpClient::with_responses(vec![record_response("192.168.1.1"), record_response("192.168.1.2")]);
+        let dns_service = CloudflareDnsService::new(mock.clone(), create_config());
+
+        let record = dns_service.get_record("test_zone", "test_record").await.unwrap();
+        assert_eq!(record.content, "192.168.1.1");
+
+        let mut updated = record.clone();
+        updated.content = "192.168.1.2".to_string();
+        let result = dns_service.update_record(updated).await.unwrap();
+        assert!(result.updated);
+        assert_eq!(result.record.content, "192.168.1.2");
+
+        let requests = mock.requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        let (method, url, body, headers) = &requests[1];
+        assert_eq!(*method, "PUT");
+        assert_eq!(url, "https://api.cloudflare.com/client/v4/zones/test_zone/dns_records/test_record");
+        assert!(body.as_deref().unwrap().contains(r#""content":"192.168.1.2""#));
+        assert_eq!(headers[AUTHORIZATION], "Bearer test_token");
+    }
+
+    #[tokio::test]
+    async fn test_cloudflare_rate_limit_error_code() {
+        let mock = MockHttpClient::with_responses(vec![Ok(
+            r#"{"success":false,"errors":[{"code":971,"message":"Please wait and consider throttling your request speed"}],"result":null}"#.to_string(),
+        )]);
+        let dns_service = CloudflareDnsService::new(mock, create_config());
+
+        let error = dns_service.get_record("test_zone", "test_record").await.unwrap_err();
+        assert_eq!(error.kind(), "rate_limit");
+    }
+
+    #[tokio::test]
+    async fn test_cloudflare_service_goes_through_retry_client() {
+        let mock = MockHttpClient::with_responses(vec![
+            Err(DomainError::Network(NetworkErrorType::ServiceUnavailable(None))),
+            record_response("192.168.1.1"),
+        ]);
+        let retry_client = Arc::new(RetryableHttpClient::new(mock.clone(), 2, Duration::from_millis(10)));
+        let dns_service = CloudflareDnsService::new(retry_client, create_config());
+
+        let record = dns_service.get_record("test_zone", "test_record").await.unwrap();
+        assert_eq!(record.content, "192.168.1.1");
+        assert_eq!(mock.requests.lock().unwrap().len(), 2);
+    }
+
+    fn new_record(content: &str) -> DnsRecord {
+        DnsRecord {
+            id: None,
+            name: "test.example.com".to_string(),
+            record_type: "TXT".to_string(),
+            content: content.to_string(),
+            ttl: 120,
+            proxied: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_record_is_not_retried() {
+        let mock = MockHttpClient::with_responses(vec![
+            Err(DomainError::Network(NetworkErrorType::ServiceUnavailable(None))),
+            Ok(r#"{"success":true,"errors":[],"result":[]}"#.to_string()),
+        ]);
+        let retry_client = Arc::new(RetryableHttpClient::new(mock.clone(), 2, Duration::from_millis(10)));
+        let dns_service = CloudflareDnsService::new(retry_client, create_config());
+
+        let error = dns_service.create_record("test_zone", new_record("v=1")).await.unwrap_err();
+        assert!(error.is_retryable());
+
+        // 只發送一次創建請求，失敗後按名稱和類型查找記錄是否已被創建
+        let requests = mock.requests.lock().unwrap();
+        let methods: Vec<&str> = requests.iter().map(|(method, ..)| *method).collect();
+        assert_eq!(methods, vec!["POST", "GET"]);
+        assert_eq!(
+            requests[1].1,
+            "https://api.cloudflare.com/client/v4/zones/test_zone/dns_records?name=test.example.com&type=TXT"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_record_returns_record_created_before_timeout() {
+        let mock = MockHttpClient::with_responses(vec![
+            Err(DomainError::Network(NetworkErrorType::TimeoutError("request timed out".to_string()))),
+            Ok(r#"{"success":true,"errors":[],"result":[{"id":"created","name":"test.example.com","type":"TXT","content":"v=1","ttl":120,"proxied":false}]}"#.to_string()),
+        ]);
+        let retry_client = Arc::new(RetryableHttpClient::new(mock.clone(), 2, Duration::from_millis(10)));
+        let dns_service = CloudflareDnsService::new(retry_client, create_config());
+
+        let record = dns_service.create_record("test_zone", new_record("v=1")).await.unwrap();
+        assert_eq!(record.id.as_deref(), Some("created"));
+        assert_eq!(mock.requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_find_records_filters_by_name_and_type() {
+        let mock = MockHttpClient::with_responses(vec![Ok(
+            r#"{"success":true,"errors":[],"result":[{"id":"www_v6","name":"www.example.com","type":"AAAA","content":"2001:db8::1","ttl":120,"proxied":false}]}"#.to_string(),
+        )]);
+        let dns_service = CloudflareDnsService::new(mock.clone(), create_config());
+
+        let records = dns_service.find_records("test_zone", "www.example.com", "AAAA").await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].id.as_deref(), Some("www_v6"));
+
+        let requests = mock.requests.lock().unwrap();
+        assert_eq!(
+            requests[0].1,
+            "https://api.cloudflare.com/client/v4/zones/test_zone/dns_records?name=www.example.com&type=AAAA"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_uses_record_id_from_record() {
+        let mock = MockHttpClient::with_responses(vec![record_response("192.168.1.2")]);
+        let dns_service = CloudflareDnsService::new(mock.clone(), create_config());
+
+        let record = DnsRecord {
+            id: Some("resolved_record".to_string()),
+            name: "test.example.com".to_string(),
+            record_type: "A".to_string(),
+            content: "192.168.1.2".to_string(),
+            ttl: 120,
+            proxied: false,
+        };
+        dns_service.update_record(record).await.unwrap();
+
+        let requests = mock.requests.lock().unwrap();
+        assert_eq!(requests[0].1, "https://api.cloudflare.com/client/v4/zones/test_zone/dns_records/resolved_record");
+    }
+
+    #[tokio::test]
+    async fn test_delete_record() {
+        let mock = MockHttpClient::with_responses(vec![Ok(
+            r#"{"success":true,"errors":[],"result":{"id":"www_v6"}}"#.to_string(),
+        )]);
+        let dns_service = CloudflareDnsService::new(mock.clone(), create_config());
+
+        dns_service.delete_record("test_zone", "www_v6").await.unwrap();
+
+        let requests = mock.requests.lock().unwrap();
+        let (method, url, _, headers) = &requests[0];
+        assert_eq!(*method, "DELETE");
+        assert_eq!(url, "https://api.cloudflare.com/client/v4/zones/test_zone/dns_records/www_v6");
+        assert_eq!(headers[AUTHORIZATION], "Bearer test_token");
+    }
+}
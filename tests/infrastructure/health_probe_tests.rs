@@ -0,0 +1,62 @@
+use cloudflare_ddns::domain::config::HealthCheck;
+use cloudflare_ddns::domain::failover::HealthProbe;
+use cloudflare_ddns::infrastructure::failover::NetworkHealthProbe;
+use httpmock::prelude::*;
+use std::net::IpAddr;
+
+fn localhost() -> IpAddr {
+    IpAddr::from([127, 0, 0, 1])
+}
+
+#[cfg(test)]
+mod network_health_probe_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tcp_check() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let probe = NetworkHealthProbe::new();
+
+        let open = HealthCheck::Tcp { port, timeout_seconds: 2 };
+        assert!(probe.probe(&open, localhost()).await.is_ok());
+
+        drop(listener);
+        assert!(probe.probe(&open, localhost()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_http_check_substitutes_target_and_checks_status() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/health");
+            then.status(200).body("ok");
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/down");
+            then.status(503);
+        });
+        let probe = NetworkHealthProbe::new();
+
+        let healthy = HealthCheck::Http {
+            url: format!("http://{{ip}}:{}/health", server.port()),
+            expected_status: None,
+            timeout_seconds: 2,
+        };
+        assert!(probe.probe(&healthy, localhost()).await.is_ok());
+
+        let down = HealthCheck::Http {
+            url: format!("http://{{ip}}:{}/down", server.port()),
+            expected_status: None,
+            timeout_seconds: 2,
+        };
+        assert!(probe.probe(&down, localhost()).await.is_err());
+
+        let expected_down = HealthCheck::Http {
+            url: format!("http://{{ip}}:{}/down", server.port()),
+            expected_status: Some(503),
+            timeout_seconds: 2,
+        };
+        assert!(probe.probe(&expected_down, localhost()).await.is_ok());
+    }
+}
@@ -0,0 +1,60 @@
+use cloudflare_ddns::domain::config::HookCommand;
+use cloudflare_ddns::domain::hook::HookRunner;
+use cloudflare_ddns::infrastructure::hook::ShellHookRunner;
+
+#[cfg(all(test, unix))]
+mod shell_hook_runner_tests {
+    use super::*;
+
+    fn env(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[tokio::test]
+    async fn test_captures_output_and_environment() {
+        let hook = HookCommand::new("echo \"$DDNS_OLD_IP -> $DDNS_NEW_IP\"; echo warn >&2");
+        let output = ShellHookRunner::new()
+            .run(&hook, &env(&[("DDNS_OLD_IP", "198.51.100.1"), ("DDNS_NEW_IP", "203.0.113.7")]))
+            .await
+            .unwrap();
+
+        assert!(output.success());
+        assert_eq!(output.exit_code, Some(0));
+        assert_eq!(output.stdout, "198.51.100.1 -> 203.0.113.7");
+        assert_eq!(output.stderr, "warn");
+    }
+
+    #[tokio::test]
+    async fn test_reports_exit_code() {
+        let output = ShellHookRunner::new().run(&HookCommand::new("exit 3"), &[]).await.unwrap();
+        assert!(!output.success());
+        assert_eq!(output.exit_code, Some(3));
+        assert!(!output.timed_out);
+    }
+
+    #[tokio::test]
+    async fn test_times_out() {
+        let hook = HookCommand { command: "sleep 5".to_string(), timeout_seconds: 1 };
+        let output = ShellHookRunner::new().run(&hook, &[]).await.unwrap();
+        assert!(output.timed_out);
+        assert!(!output.success());
+        assert!(output.duration.as_secs() < 5);
+    }
+
+    #[tokio::test]
+    async fn test_truncates_long_output() {
+        let output = ShellHookRunner::new()
+            .run(&HookCommand::new("head -c 10000 /dev/zero | tr '\\0' 'x'"), &[])
+            .await
+            .unwrap();
+        assert!(output.stdout.len() < 5000);
+        assert!(output.stdout.ends_with("...(truncated)"));
+    }
+
+    #[test]
+    fn test_hook_command_validation() {
+        assert!(HookCommand::new("true").validate().is_ok());
+        assert!(HookCommand::new("  ").validate().is_err());
+        assert!(HookCommand { command: "true".to_string(), timeout_seconds: 0 }.validate().is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use cloudflare_ddns::domain::config::{HttpClientSettings, IpType};
+use cloudflare_ddns::domain::http::HttpClient;
+use cloudflare_ddns::infrastructure::http::ReqwestHttpClient;
+use httpmock::prelude::*;
+use std::time::Duration;
+
+fn settings() -> HttpClientSettings {
+    HttpClientSettings {
+        timeout_secs: Some(5),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod http_settings_client_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/slow");
+            then.status(200).body("ok").delay(Duration::from_secs(3));
+        });
+
+        let client = ReqwestHttpClient::from_settings(&HttpClientSettings {
+            timeout_secs: Some(1),
+            ..Default::default()
+        }).unwrap();
+        let error = client.get(&server.url("/slow"), None).await.unwrap_err();
+
+        assert_eq!(error.kind(), "timeout");
+    }
+
+    #[tokio::test]
+    async fn test_requests_go_through_proxy() {
+        let proxy = MockServer::start();
+        let mock = proxy.mock(|when, then| {
+            when.method(GET).path("/ip");
+            then.status(200).body("203.0.113.7");
+        });
+
+        let client = ReqwestHttpClient::from_settings(&HttpClientSettings {
+            proxy: Some(proxy.base_url()),
+            ..settings()
+        }).unwrap();
+        let body = client.get("http://echo.example.invalid/ip", None).await.unwrap();
+
+        assert_eq!(body, "203.0.113.7");
+        mock.assert();
+    }
+
+    #[test]
+    fn test_invalid_ca_bundle_is_rejected() {
+        let missing = HttpClientSettings {
+            ca_bundle: Some("/nonexistent/ca.pem".to_string()),
+            ..settings()
+        };
+        assert!(ReqwestHttpClient::from_settings(&missing).is_err());
+
+        let path = std::env::temp_dir().join("ddns_test_empty_ca.pem");
+        std::fs::write(&path, "not a certificate").unwrap();
+        let empty = HttpClientSettings {
+            ca_bundle: Some(path.to_string_lossy().to_string()),
+            ..settings()
+        };
+        assert!(ReqwestHttpClient::from_settings(&empty).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_family_pinned_clients() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/ip");
+            then.status(200).body("127.0.0.1");
+        });
+
+        // 模擬服務器只監聽 IPv4 回環地址
+        let ipv4_client = ReqwestHttpClient::for_ip_type(&settings(), IpType::IPv4).unwrap();
+        assert_eq!(ipv4_client.get(&server.url("/ip"), None).await.unwrap(), "127.0.0.1");
+
+        let ipv6_client = ReqwestHttpClient::for_ip_type(&settings(), IpType::IPv6).unwrap();
+        assert!(ipv6_client.get(&server.url("/ip"), None).await.is_err());
+    }
+}
@@ -63,4 +63,186 @@ mod public_ip_service_tests {
         let result = ip_service.get_ipv6().await;
         assert!(result.is_err());
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod source_binding_tests {
+    use super::*;
+    use cloudflare_ddns::domain::config::{HttpClientSettings, IpType, SourceSettings};
+    use cloudflare_ddns::infrastructure::ip::{
+        interface_addresses, select_bind_address, select_public_address, SourceBoundIpService,
+    };
+    use httpmock::prelude::*;
+    use std::net::IpAddr;
+
+    fn addresses(values: &[&str]) -> Vec<IpAddr> {
+        values.iter().map(|value| value.parse().unwrap()).collect()
+    }
+
+    fn bound_service(source: SourceSettings, ipv4_url: String) -> SourceBoundIpService {
+        SourceBoundIpService::new(HttpClientSettings::default(), source)
+            .with_provider_urls(Some(ipv4_url), None)
+    }
+
+    #[test]
+    fn test_select_public_address() {
+        let candidates = addresses(&["127.0.0.1", "192.168.1.2", "100.64.0.9", "169.254.1.1", "203.0.113.7"]);
+        assert_eq!(select_public_address(&candidates), Some("203.0.113.7".parse().unwrap()));
+        assert_eq!(select_public_address(&addresses(&["10.0.0.2", "100.100.1.1"])), None);
+
+        let candidates = addresses(&["fe80::1", "fd00::2", "2001:db8::7"]);
+        assert_eq!(select_public_address(&candidates), Some("2001:db8::7".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_select_bind_address_prefers_public() {
+        assert_eq!(
+            select_bind_address(&addresses(&["192.168.1.2", "203.0.113.7"])),
+            Some("203.0.113.7".parse().unwrap())
+        );
+        // 光貓後面的 WAN 口只有私有地址時也可以綁定
+        assert_eq!(
+            select_bind_address(&addresses(&["127.0.0.1", "192.168.1.2"])),
+            Some("192.168.1.2".parse().unwrap())
+        );
+        assert_eq!(select_bind_address(&addresses(&["fe80::1", "::1"])), None);
+    }
+
+    #[test]
+    fn test_unknown_interface() {
+        let error = interface_addresses("ddns-test-missing0", &IpType::IPv4).unwrap_err();
+        assert_eq!(error.kind(), "ip_service");
+    }
+
+    #[tokio::test]
+    async fn test_detection_uses_source_address() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/ip");
+            then.status(200).body("203.0.113.7");
+        });
+
+        let service = bound_service(
+            SourceSettings { address: Some("127.0.0.1".parse().unwrap()), ..Default::default() },
+            server.url("/ip"),
+        );
+        assert_eq!(service.get_ipv4().await.unwrap(), "203.0.113.7");
+
+        // 本機沒有的地址無法綁定，說明請求確實從指定地址發出
+        let unavailable = bound_service(
+            SourceSettings { address: Some("192.0.2.123".parse().unwrap()), ..Default::default() },
+            server.url("/ip"),
+        );
+        assert!(unavailable.get_ipv4().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_detection_through_interface() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/ip");
+            then.status(200).body("203.0.113.8");
+        });
+
+        // 回環接口只有回環地址，不能用於綁定
+        let loopback = bound_service(
+            SourceSettings { interface: Some("lo".to_string()), ..Default::default() },
+            server.url("/ip"),
+        );
+        assert!(loopback.get_ipv4().await.is_err());
+
+        // 回環接口上沒有公網地址
+        let from_interface = bound_service(
+            SourceSettings { interface: Some("lo".to_string()), from_interface: true, ..Default::default() },
+            server.url("/ip"),
+        );
+        let error = from_interface.get_ipv4().await.unwrap_err();
+        assert!(error.to_string().contains("lo"));
+    }
+}
+
+#[cfg(test)]
+mod cached_ip_service_tests {
+    use super::*;
+    use cloudflare_ddns::infrastructure::ip::CachedIpService;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// 記錄查詢次數的 IP 服務，第一次查詢可配置為失敗
+    struct CountingIpService {
+        calls: AtomicUsize,
+        fail_first: bool,
+    }
+
+    impl CountingIpService {
+        fn new(fail_first: bool) -> Arc<Self> {
+            Arc::new(Self { calls: AtomicUsize::new(0), fail_first })
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl IpService for CountingIpService {
+        async fn get_ipv4(&self) -> Result<String, DomainError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            if self.fail_first && call == 0 {
+                return Err(DomainError::network("lookup failed"));
+            }
+            Ok(format!("203.0.113.{}", call + 1))
+        }
+
+        async fn get_ipv6(&self) -> Result<String, DomainError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok("2001:db8::1".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_lookups_use_cache() {
+        let inner = CountingIpService::new(false);
+        let service = CachedIpService::new(inner.clone(), Duration::from_secs(60));
+
+        assert_eq!(service.get_ipv4().await.unwrap(), "203.0.113.1");
+        assert_eq!(service.get_ipv4().await.unwrap(), "203.0.113.1");
+        assert_eq!(inner.calls(), 1);
+
+        // 協議族分別緩存
+        assert_eq!(service.get_ipv6().await.unwrap(), "2001:db8::1");
+        assert_eq!(inner.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_lookups_are_coalesced() {
+        let inner = CountingIpService::new(false);
+        let service = CachedIpService::new(inner.clone(), Duration::from_secs(60));
+
+        let results = futures::future::join_all((0..10).map(|_| service.get_ipv4())).await;
+
+        assert!(results.iter().all(|result| matches!(result.as_deref(), Ok("203.0.113.1"))));
+        assert_eq!(inner.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_not_cached() {
+        let inner = CountingIpService::new(true);
+        let service = CachedIpService::new(inner.clone(), Duration::from_secs(60));
+
+        assert!(service.get_ipv4().await.is_err());
+        assert_eq!(service.get_ipv4().await.unwrap(), "203.0.113.2");
+        assert_eq!(inner.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_expires() {
+        let inner = CountingIpService::new(false);
+        let service = CachedIpService::new(inner.clone(), Duration::from_millis(50));
+
+        assert_eq!(service.get_ipv4().await.unwrap(), "203.0.113.1");
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert_eq!(service.get_ipv4().await.unwrap(), "203.0.113.2");
+    }
+}
@@ -0,0 +1,61 @@
+use cloudflare_ddns::infrastructure::logging::json_log_line;
+use log::Level;
+use serde_json::Value;
+
+fn render(record: &log::Record) -> Value {
+    serde_json::from_str(&json_log_line(record)).unwrap()
+}
+
+#[cfg(test)]
+mod json_log_tests {
+    use super::*;
+
+    #[test]
+    fn test_json_line_has_fixed_fields() {
+        let line = render(
+            &log::Record::builder()
+                .args(format_args!("Updating A DNS record"))
+                .level(Level::Info)
+                .target("cloudflare_ddns::application::ddns::service")
+                .module_path(Some("cloudflare_ddns::application::ddns::service"))
+                .build(),
+        );
+
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["module"], "cloudflare_ddns::application::ddns::service");
+        assert_eq!(line["message"], "Updating A DNS record");
+        assert!(line["timestamp"].as_str().unwrap().ends_with('Z'));
+        for field in ["record_name", "config_id", "ip_type", "event", "error_kind"] {
+            assert!(line[field].is_null(), "{} should be null", field);
+        }
+    }
+
+    #[test]
+    fn test_json_line_includes_structured_fields() {
+        let fields = [
+            ("config_id", "config-1"),
+            ("record_name", "home.example.com"),
+            ("ip_type", "ipv4"),
+            ("event", "update-failed"),
+            ("error_kind", "network"),
+            ("ignored", "value"),
+        ];
+        let line = render(
+            &log::Record::builder()
+                .args(format_args!("Failed to update"))
+                .level(Level::Error)
+                .target("cloudflare_ddns")
+                .key_values(&fields)
+                .build(),
+        );
+
+        assert_eq!(line["level"], "ERROR");
+        assert_eq!(line["module"], "cloudflare_ddns");
+        assert_eq!(line["config_id"], "config-1");
+        assert_eq!(line["record_name"], "home.example.com");
+        assert_eq!(line["ip_type"], "ipv4");
+        assert_eq!(line["event"], "update-failed");
+        assert_eq!(line["error_kind"], "network");
+        assert!(line.get("ignored").is_none());
+    }
+}
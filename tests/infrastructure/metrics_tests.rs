@@ -0,0 +1,87 @@
+use cloudflare_ddns::domain::error::DomainError;
+use cloudflare_ddns::infrastructure::metrics::{metrics, observe_latency, MetricsRegistry};
+
+#[cfg(test)]
+mod metrics_registry_tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_render() {
+        let registry = MetricsRegistry::new();
+        registry.inc_counter("test_updates_total", "Test updates", &[("record_name", "home.example.com")]);
+        registry.inc_counter("test_updates_total", "Test updates", &[("record_name", "home.example.com")]);
+        registry.add_counter("test_updates_total", "Test updates", &[("record_name", "vpn.example.com")], 5.0);
+
+        let output = registry.render();
+
+        assert!(output.contains("# HELP test_updates_total Test updates\n"));
+        assert!(output.contains("# TYPE test_updates_total counter\n"));
+        assert!(output.contains("test_updates_total{record_name=\"home.example.com\"} 2\n"));
+        assert!(output.contains("test_updates_total{record_name=\"vpn.example.com\"} 5\n"));
+    }
+
+    #[test]
+    fn test_gauge_overwrites_value() {
+        let registry = MetricsRegistry::new();
+        registry.set_gauge("test_gauge", "Test gauge", &[], 10.0);
+        registry.set_gauge("test_gauge", "Test gauge", &[], 2.5);
+
+        let output = registry.render();
+
+        assert!(output.contains("# TYPE test_gauge gauge\n"));
+        assert!(output.contains("test_gauge 2.5\n"));
+        assert!(!output.contains("test_gauge 10\n"));
+    }
+
+    #[test]
+    fn test_histogram_buckets() {
+        let registry = MetricsRegistry::new();
+        registry.observe("test_duration_seconds", "Test latency", &[("operation", "get")], 0.25);
+        registry.observe("test_duration_seconds", "Test latency", &[("operation", "get")], 3.0);
+        registry.observe("test_duration_seconds", "Test latency", &[("operation", "get")], 20.0);
+
+        let output = registry.render();
+
+        assert!(output.contains("# TYPE test_duration_seconds histogram\n"));
+        assert!(output.contains("test_duration_seconds_bucket{operation=\"get\",le=\"0.1\"} 0\n"));
+        assert!(output.contains("test_duration_seconds_bucket{operation=\"get\",le=\"0.25\"} 1\n"));
+        assert!(output.contains("test_duration_seconds_bucket{operation=\"get\",le=\"5\"} 2\n"));
+        assert!(output.contains("test_duration_seconds_bucket{operation=\"get\",le=\"10\"} 2\n"));
+        assert!(output.contains("test_duration_seconds_bucket{operation=\"get\",le=\"+Inf\"} 3\n"));
+        assert!(output.contains("test_duration_seconds_sum{operation=\"get\"} 23.25\n"));
+        assert!(output.contains("test_duration_seconds_count{operation=\"get\"} 3\n"));
+    }
+
+    #[test]
+    fn test_label_values_are_escaped() {
+        let registry = MetricsRegistry::new();
+        registry.inc_counter("test_escape_total", "Test escaping", &[("value", "a\"b\\c\nd")]);
+
+        let output = registry.render();
+
+        assert!(output.contains("test_escape_total{value=\"a\\\"b\\\\c\\nd\"} 1\n"));
+    }
+
+    #[tokio::test]
+    async fn test_observe_latency_records_outcome() {
+        let ok: Result<u32, DomainError> = observe_latency(
+            "test_observe_latency_seconds",
+            "Test observe latency",
+            &[("provider", "example.com")],
+            async { Ok(1) },
+        ).await;
+        let err: Result<u32, DomainError> = observe_latency(
+            "test_observe_latency_seconds",
+            "Test observe latency",
+            &[("provider", "example.com")],
+            async { Err(DomainError::network("timeout")) },
+        ).await;
+
+        assert!(ok.is_ok());
+        assert!(err.is_err());
+
+        let output = metrics().render();
+        assert!(output.contains("test_observe_latency_seconds_count{provider=\"example.com\",outcome=\"success\"} 1\n"));
+        assert!(output.contains("test_observe_latency_seconds_count{provider=\"example.com\",outcome=\"error\"} 1\n"));
+    }
+}
@@ -1,2 +1,12 @@
 mod ip_service_tests;
-mod dns_service_tests; 
\ No newline at end of file
+mod dns_service_tests;
+mod webhook_tests;
+mod notifier_tests;
+mod hook_runner_tests;
+mod metrics_tests;
+mod telemetry_tests;
+mod logging_tests;
+mod retry_tests;
+mod circuit_breaker_tests;
+mod http_client_tests;
+mod health_probe_tests;
@@ -0,0 +1,314 @@
+use cloudflare_ddns::domain::config::{ChatWebhookConfig, EmailConfig, NotificationSettings, SmtpSecurity, TelegramConfig};
+use cloudflare_ddns::domain::http::HttpClient;
+use cloudflare_ddns::domain::notification::{Notification, Notifier};
+use cloudflare_ddns::infrastructure::http::ReqwestHttpClient;
+use cloudflare_ddns::infrastructure::notification::{
+    build_notifiers, DiscordNotifier, EmailNotifier, SlackNotifier, TelegramNotifier,
+};
+use httpmock::prelude::*;
+use serde_json::json;
+use std::sync::Arc;
+
+fn create_notification(event: &str) -> Notification {
+    Notification {
+        event: event.to_string(),
+        title: "home.example.com IP 已變更".to_string(),
+        message: "home.example.com (A) 的 IP 從 198.51.100.1 變更為 203.0.113.7".to_string(),
+        record_key: Some("config-1".to_string()),
+        payload: json!({ "new_ip": "203.0.113.7" }),
+    }
+}
+
+fn create_http_client() -> Arc<dyn HttpClient> {
+    Arc::new(ReqwestHttpClient::new())
+}
+
+fn create_chat_config(url: String) -> ChatWebhookConfig {
+    ChatWebhookConfig {
+        name: Some("ops".to_string()),
+        webhook_url: url,
+        events: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod chat_notifier_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_slack_message_format() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/slack").json_body(json!({
+                "text": ":white_check_mark: *home.example.com IP 已變更*\nhome.example.com (A) 的 IP 從 198.51.100.1 變更為 203.0.113.7"
+            }));
+            then.status(200).body("ok");
+        });
+
+        let notifier = SlackNotifier::new(create_chat_config(server.url("/slack")), create_http_client());
+        assert_eq!(notifier.name(), "slack:ops");
+        notifier.send(&create_notification("ip-changed")).await.unwrap();
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_discord_embed_color_reflects_failures() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/discord").json_body_partial(r#"{
+                "username": "Cloudflare DDNS",
+                "embeds": [{ "title": "home.example.com IP 已變更", "color": 15158332 }]
+            }"#);
+            then.status(204);
+        });
+
+        let notifier = DiscordNotifier::new(create_chat_config(server.url("/discord")), create_http_client());
+        notifier.send(&create_notification("update-failed")).await.unwrap();
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_telegram_send_message() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/bot123:abc/sendMessage").json_body_partial(r#"{
+                "chat_id": "-1001",
+                "text": "home.example.com IP 已變更\nhome.example.com (A) 的 IP 從 198.51.100.1 變更為 203.0.113.7"
+            }"#);
+            then.status(200).json_body(json!({ "ok": true }));
+        });
+
+        let notifier = TelegramNotifier::new(TelegramConfig {
+            name: None,
+            bot_token: "123:abc".to_string(),
+            chat_id: "-1001".to_string(),
+            api_url: Some(server.base_url()),
+            events: Vec::new(),
+        }, create_http_client());
+        assert_eq!(notifier.name(), "telegram:-1001");
+        notifier.send(&create_notification("ip-changed")).await.unwrap();
+        mock.assert();
+    }
+
+    #[test]
+    fn test_invalid_configs_are_skipped() {
+        let settings = NotificationSettings {
+            slack: vec![create_chat_config("https://hooks.slack.com/services/x".to_string())],
+            discord: vec![create_chat_config("not a url".to_string())],
+            ..Default::default()
+        };
+        let notifiers = build_notifiers(&settings, create_http_client());
+        assert_eq!(notifiers.len(), 1);
+        assert_eq!(notifiers[0].name(), "slack:ops");
+    }
+}
+
+#[cfg(test)]
+mod email_notifier_tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// 極簡的 SMTP 服務器，接收一封郵件後返回郵件內容
+    async fn start_smtp_sink() -> (u16, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            let mut data = String::new();
+            let mut in_data = false;
+
+            writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+            while let Some(line) = lines.next_line().await.unwrap() {
+                if in_data {
+                    if line == "." {
+                        in_data = false;
+                        writer.write_all(b"250 OK\r\n").await.unwrap();
+                    } else {
+                        data.push_str(&line);
+                        data.push('\n');
+                    }
+                    continue;
+                }
+
+                let command = line.to_uppercase();
+                let reply: &[u8] = if command.starts_with("EHLO") {
+                    b"250 localhost\r\n"
+                } else if command.starts_with("DATA") {
+                    in_data = true;
+                    b"354 End data with <CR><LF>.<CR><LF>\r\n"
+                } else if command.starts_with("QUIT") {
+                    writer.write_all(b"221 Bye\r\n").await.unwrap();
+                    break;
+                } else {
+                    b"250 OK\r\n"
+                };
+                writer.write_all(reply).await.unwrap();
+            }
+            data
+        });
+
+        (port, handle)
+    }
+
+    fn create_email_config(port: u16) -> EmailConfig {
+        EmailConfig {
+            name: None,
+            smtp_host: "127.0.0.1".to_string(),
+            smtp_port: Some(port),
+            security: SmtpSecurity::None,
+            username: None,
+            password: None,
+            from: "ddns@example.com".to_string(),
+            to: vec!["oncall@example.com".to_string()],
+            events: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_email() {
+        let (port, sink) = start_smtp_sink().await;
+        let notifier = EmailNotifier::new(create_email_config(port)).unwrap();
+        assert_eq!(notifier.name(), "email:127.0.0.1");
+
+        notifier.send(&create_notification("ip-changed")).await.unwrap();
+
+        let data = sink.await.unwrap();
+        assert!(data.contains("From: ddns@example.com"));
+        assert!(data.contains("To: oncall@example.com"));
+        assert!(data.contains("Subject: "));
+    }
+
+    #[test]
+    fn test_email_validation() {
+        let mut config = create_email_config(25);
+        assert!(config.validate().is_ok());
+
+        config.username = Some("user".to_string());
+        assert!(config.validate().is_err());
+
+        config.password = Some("pass".to_string());
+        config.to.clear();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_default_ports() {
+        let mut config = create_email_config(25);
+        config.smtp_port = None;
+        assert_eq!(config.port(), 25);
+        config.security = SmtpSecurity::Starttls;
+        assert_eq!(config.port(), 587);
+        config.security = SmtpSecurity::Tls;
+        assert_eq!(config.port(), 465);
+    }
+}
+
+#[cfg(test)]
+mod secret_redaction_tests {
+    use super::*;
+    use cloudflare_ddns::application::background::background_tasks;
+    use cloudflare_ddns::application::events::EventType;
+    use cloudflare_ddns::application::notification::{NotificationRateLimiter, NotificationService};
+    use cloudflare_ddns::domain::config::RateLimitSettings;
+    use cloudflare_ddns::infrastructure::http::RetryableHttpClient;
+    use std::sync::{Mutex, Once};
+    use std::time::Duration;
+
+    const BOT_TOKEN: &str = "4242:SECRET-bot-token";
+    const WEBHOOK_SECRET: &str = "T000/B000/SECRET-webhook";
+
+    /// 保存本程序所有日誌行的記錄器（不包括模擬服務器自身的日誌）
+    struct CapturingLogger {
+        lines: Mutex<Vec<String>>,
+    }
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.target().starts_with("cloudflare_ddns")
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                self.lines.lock().unwrap().push(format!("{} {}", record.level(), record.args()));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CapturingLogger = CapturingLogger { lines: Mutex::new(Vec::new()) };
+    static INIT: Once = Once::new();
+
+    fn captured_logs() -> &'static CapturingLogger {
+        INIT.call_once(|| {
+            log::set_logger(&LOGGER).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+        &LOGGER
+    }
+
+    fn retrying_client() -> Arc<dyn HttpClient> {
+        Arc::new(RetryableHttpClient::new(create_http_client(), 1, Duration::from_millis(10)))
+    }
+
+    /// 經通知服務發送（失敗時由通知服務記錄錯誤），等待發送完成
+    async fn dispatch(notifier: Arc<dyn Notifier>) {
+        let rate_limiter = NotificationRateLimiter::new(&RateLimitSettings::default());
+        NotificationService::dispatch(&[notifier], &rate_limiter, &EventType::IpChanged, &create_notification("ip-changed"));
+        tokio::time::timeout(Duration::from_secs(30), background_tasks().wait_idle()).await.unwrap();
+    }
+
+    fn assert_not_logged(secret: &str) {
+        let lines = captured_logs().lines.lock().unwrap().clone();
+        assert!(lines.iter().any(|line| line.contains("重試")), "retries should be logged");
+        for line in &lines {
+            assert!(!line.contains(secret), "secret leaked into log line: {}", line);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_telegram_token_not_logged() {
+        captured_logs();
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST);
+            then.status(503);
+        });
+
+        // 服務端錯誤和連接錯誤（錯誤信息中包含 URL）都會重試
+        for api_url in [server.base_url(), "http://127.0.0.1:1".to_string()] {
+            let notifier = TelegramNotifier::new(TelegramConfig {
+                name: None,
+                bot_token: BOT_TOKEN.to_string(),
+                chat_id: "-1001".to_string(),
+                api_url: Some(api_url),
+                events: Vec::new(),
+            }, retrying_client());
+            let error = notifier.send(&create_notification("ip-changed")).await.unwrap_err();
+            assert!(!error.to_string().contains(BOT_TOKEN));
+            dispatch(Arc::new(notifier)).await;
+        }
+
+        assert_not_logged(BOT_TOKEN);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_url_not_logged() {
+        captured_logs();
+        let url = format!("http://127.0.0.1:1/services/{}", WEBHOOK_SECRET);
+        let slack: Arc<dyn Notifier> = Arc::new(SlackNotifier::new(create_chat_config(url.clone()), retrying_client()));
+        let discord: Arc<dyn Notifier> = Arc::new(DiscordNotifier::new(create_chat_config(url), retrying_client()));
+
+        for notifier in [slack, discord] {
+            let error = notifier.send(&create_notification("ip-changed")).await.unwrap_err();
+            assert!(!error.to_string().contains(WEBHOOK_SECRET));
+            dispatch(notifier).await;
+        }
+
+        assert_not_logged(WEBHOOK_SECRET);
+    }
+}
@@ -0,0 +1,115 @@
+use cloudflare_ddns::domain::error::{ApiErrorType, DomainError, NetworkErrorType};
+use cloudflare_ddns::domain::http::HttpClient;
+use cloudflare_ddns::infrastructure::http::{parse_retry_after, ReqwestHttpClient, RetryPolicy, RetryableHttpClient};
+use chrono::{TimeZone, Utc};
+use httpmock::prelude::*;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 不帶抖動的策略，便於驗證等待時間
+fn deterministic_policy() -> RetryPolicy {
+    RetryPolicy {
+        max_retries: 5,
+        initial_interval: Duration::from_millis(100),
+        max_interval: Duration::from_millis(300),
+        multiplier: 2.0,
+        randomization_factor: 0.0,
+        max_elapsed_time: Some(Duration::from_secs(60)),
+    }
+}
+
+#[cfg(test)]
+mod retry_policy_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+        assert_eq!(parse_retry_after("120", now), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 5 ", now), Some(Duration::from_secs(5)));
+        assert_eq!(
+            parse_retry_after("Mon, 01 Jan 2024 00:00:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        // 已經過去的時間點表示可以立即重試
+        assert_eq!(parse_retry_after("Sun, 31 Dec 2023 23:59:00 GMT", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_exponential_delays_are_capped() {
+        let policy = deterministic_policy();
+        let mut backoff = policy.backoff();
+        let error = DomainError::Network(NetworkErrorType::HttpError(502));
+
+        let delays: Vec<_> = (0..4).map(|_| policy.next_delay(&mut backoff, &error).unwrap()).collect();
+        assert_eq!(delays, vec![
+            Duration::from_millis(100),
+            Duration::from_millis(200),
+            Duration::from_millis(300),
+            Duration::from_millis(300),
+        ]);
+    }
+
+    #[test]
+    fn test_retry_after_overrides_shorter_backoff() {
+        let policy = deterministic_policy();
+        let mut backoff = policy.backoff();
+        let error = DomainError::Api(ApiErrorType::RateLimitError("限流".to_string(), Some(Duration::from_secs(2))));
+
+        assert_eq!(policy.next_delay(&mut backoff, &error), Some(Duration::from_secs(2)));
+        assert_eq!(error.kind(), "rate_limit");
+        assert!(error.is_retryable());
+    }
+
+    #[test]
+    fn test_stops_when_retry_after_exceeds_budget() {
+        let policy = RetryPolicy {
+            max_elapsed_time: Some(Duration::from_secs(10)),
+            ..deterministic_policy()
+        };
+        let mut backoff = policy.backoff();
+        let error = DomainError::Network(NetworkErrorType::ServiceUnavailable(Some(Duration::from_secs(30))));
+
+        assert_eq!(policy.next_delay(&mut backoff, &error), None);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_request_waits_for_retry_after() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/limited");
+            then.status(429).header("Retry-After", "1");
+        });
+
+        let client = RetryableHttpClient::new(Arc::new(ReqwestHttpClient::new()), 1, Duration::from_millis(10));
+        let started = Instant::now();
+        let error = client.get(&server.url("/limited"), None).await.unwrap_err();
+
+        mock.assert_hits(2);
+        assert!(started.elapsed() >= Duration::from_secs(1));
+        assert_eq!(error.kind(), "rate_limit");
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(1)));
+    }
+
+    #[tokio::test]
+    async fn test_service_unavailable_is_retried() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/unavailable");
+            then.status(503);
+        });
+
+        let client = RetryableHttpClient::new(Arc::new(ReqwestHttpClient::new()), 0, Duration::from_millis(10))
+            .with_retry_policy(RetryPolicy {
+                max_retries: 2,
+                initial_interval: Duration::from_millis(10),
+                ..RetryPolicy::default()
+            });
+        let error = client.get(&server.url("/unavailable"), None).await.unwrap_err();
+
+        mock.assert_hits(3);
+        assert!(matches!(error, DomainError::Network(NetworkErrorType::ServiceUnavailable(None))));
+    }
+}
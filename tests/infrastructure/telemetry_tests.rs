@@ -0,0 +1,26 @@
+use cloudflare_ddns::domain::config::TelemetrySettings;
+use cloudflare_ddns::infrastructure::telemetry::{init_tracing, otlp_traces_endpoint};
+
+#[cfg(test)]
+mod trace_export_tests {
+    use super::*;
+
+    #[test]
+    fn test_otlp_traces_endpoint_appends_signal_path() {
+        assert_eq!(otlp_traces_endpoint("http://collector:4318"), "http://collector:4318/v1/traces");
+        assert_eq!(otlp_traces_endpoint("http://collector:4318/"), "http://collector:4318/v1/traces");
+        assert_eq!(otlp_traces_endpoint("https://otel.example.com/prefix"), "https://otel.example.com/prefix/v1/traces");
+    }
+
+    #[test]
+    fn test_otlp_traces_endpoint_keeps_full_path() {
+        assert_eq!(otlp_traces_endpoint("http://collector:4318/v1/traces"), "http://collector:4318/v1/traces");
+    }
+
+    #[test]
+    fn test_init_tracing_disabled() {
+        let guard = init_tracing(&TelemetrySettings::default()).unwrap();
+
+        assert!(guard.is_none());
+    }
+}
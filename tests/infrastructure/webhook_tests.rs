@@ -0,0 +1,154 @@
+use cloudflare_ddns::domain::config::WebhookConfig;
+use cloudflare_ddns::domain::http::HttpClient;
+use cloudflare_ddns::infrastructure::http::{ReqwestHttpClient, RetryableHttpClient};
+use cloudflare_ddns::infrastructure::notification::{render_template, template_variables, WebhookSender, SIGNATURE_HEADER};
+use httpmock::prelude::*;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn create_webhook(url: String) -> WebhookConfig {
+    WebhookConfig {
+        name: Some("test".to_string()),
+        url,
+        events: Vec::new(),
+        template: None,
+        secret: None,
+        headers: HashMap::new(),
+    }
+}
+
+fn ip_changed_payload() -> serde_json::Value {
+    json!({
+        "record": {
+            "config_id": "config-1",
+            "record_name": "home.example.com",
+            "record_type": "A",
+            "ip_type": "ipv4"
+        },
+        "old_ip": null,
+        "new_ip": "203.0.113.7",
+        "timestamp": "2024-01-01T00:00:00Z"
+    })
+}
+
+#[cfg(test)]
+mod template_tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template_substitutes_variables() {
+        let variables = template_variables("ip-changed", &ip_changed_payload());
+        let template = json!({
+            "text": "{{record_name}} changed to {{ new_ip }} ({{event}})",
+            "old": "{{old_ip}}",
+            "tags": ["{{record.record_type}}", "static"],
+            "count": 1
+        });
+
+        let rendered = render_template(&template, &variables);
+        assert_eq!(rendered["text"], "home.example.com changed to 203.0.113.7 (ip-changed)");
+        // 單獨的佔位符保留原始 JSON 類型
+        assert_eq!(rendered["old"], serde_json::Value::Null);
+        assert_eq!(rendered["tags"], json!(["A", "static"]));
+        assert_eq!(rendered["count"], 1);
+    }
+
+    #[test]
+    fn test_unknown_variables_render_empty() {
+        let variables = template_variables("ip-changed", &ip_changed_payload());
+        let rendered = render_template(&json!("ip={{missing}}"), &variables);
+        assert_eq!(rendered, "ip=");
+    }
+}
+
+#[cfg(test)]
+mod webhook_sender_tests {
+    use super::*;
+
+    fn create_sender(max_retries: u32) -> WebhookSender {
+        let client: Arc<dyn HttpClient> = Arc::new(RetryableHttpClient::new(
+            Arc::new(ReqwestHttpClient::new()),
+            max_retries,
+            Duration::from_millis(10),
+        ));
+        WebhookSender::new(client)
+    }
+
+    #[tokio::test]
+    async fn test_send_default_body() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/hook")
+                .header("content-type", "application/json")
+                .header("X-DDNS-Event", "ip-changed")
+                .json_body_partial(r#"{"event": "ip-changed", "data": {"new_ip": "203.0.113.7"}}"#);
+            then.status(200);
+        });
+
+        let webhook = create_webhook(server.url("/hook"));
+        create_sender(0).send(&webhook, "ip-changed", &ip_changed_payload()).await.unwrap();
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_send_template_with_signature() {
+        let server = MockServer::start();
+        let mut webhook = create_webhook(server.url("/hook"));
+        webhook.template = Some(json!({ "content": "{{record_name}} -> {{new_ip}}" }));
+        webhook.secret = Some("top-secret".to_string());
+        webhook.headers.insert("Authorization".to_string(), "Bearer abc".to_string());
+
+        let body = json!({ "content": "home.example.com -> 203.0.113.7" }).to_string();
+        let signature = WebhookSender::sign("top-secret", &body);
+        let mock = server.mock(|when, then| {
+            when.method(POST)
+                .path("/hook")
+                .header("Authorization", "Bearer abc")
+                .header(SIGNATURE_HEADER, &signature)
+                .body(&body);
+            then.status(204);
+        });
+
+        create_sender(0).send(&webhook, "ip-changed", &ip_changed_payload()).await.unwrap();
+        mock.assert();
+    }
+
+    #[test]
+    fn test_signature_format() {
+        // RFC 4231 測試向量 2
+        let signature = WebhookSender::sign("Jefe", "what do ya want for nothing?");
+        assert_eq!(signature, "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_are_retried() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/hook");
+            then.status(503);
+        });
+
+        let webhook = create_webhook(server.url("/hook"));
+        let result = create_sender(2).send(&webhook, "update-failed", &ip_changed_payload()).await;
+        assert!(result.is_err());
+        mock.assert_hits(3);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/hook");
+            then.status(400);
+        });
+
+        let webhook = create_webhook(server.url("/hook"));
+        let result = create_sender(2).send(&webhook, "update-failed", &ip_changed_payload()).await;
+        assert!(result.is_err());
+        mock.assert_hits(1);
+    }
+}
+
@@ -6,14 +6,14 @@ mod infrastructure;
 #[cfg(test)]
 mod integration_tests {
     
-    use cloudflare_ddns::domain::config::{DdnsConfig, IpType};
+    use cloudflare_ddns::domain::config::{ConfigId, DdnsConfig, IpType};
     use cloudflare_ddns::domain::dns::{DnsRecord, DnsService, DnsUpdateResult};
     use cloudflare_ddns::domain::error::DomainError;
     use cloudflare_ddns::domain::ip::IpService;
     use cloudflare_ddns::domain::state::StateRepository;
     use cloudflare_ddns::application::ddns::DdnsApplicationService;
     use cloudflare_ddns::infrastructure::state::InMemoryStateRepository;
-    use cloudflare_ddns::application::events::{EventManager, EventType, EventData, EventListener};
+    use cloudflare_ddns::application::events::{Event, EventManager, EventType, EventListener};
     use std::sync::Arc;
     use async_trait::async_trait;
     
@@ -71,6 +71,10 @@ mod integration_tests {
         async fn create_record(&self, _zone_id: &str, _record: DnsRecord) -> Result<DnsRecord, DomainError> {
             self.create_record_result.clone()
         }
+        
+        async fn delete_record(&self, _zone_id: &str, _record_id: &str) -> Result<(), DomainError> {
+            Ok(())
+        }
     }
     
     // 測試DDNS服務的完整流程
@@ -78,12 +82,21 @@ mod integration_tests {
     async fn test_ddns_update_flow() {
         // 創建測試配置
         let config = DdnsConfig {
+            id: ConfigId::new("test_config"),
             api_token: "test_token".to_string(),
             zone_id: "test_zone".to_string(),
             record_id: "test_record".to_string(),
             record_name: "test.example.com".to_string(),
             update_interval: 300,
             ip_type: IpType::IPv4,
+            hooks: Default::default(),
+            source: Default::default(),
+            ipv6_loss: Default::default(),
+            prefix_delegation: None,
+            record_type: None,
+            content: None,
+            failover: None,
+            drift_check_interval: None,
         };
         
         // 創建測試記錄
@@ -142,12 +155,12 @@ mod integration_tests {
         assert_eq!(last_record.name, "test.example.com");
         
         // 驗證狀態已更新
-        let config_id = format!("{}-{}", config.zone_id, config.record_id);
-        let last_ip = state_repository.get_last_ip(&config_id).await.unwrap();
+        let config_id = &config.id;
+        let last_ip = state_repository.get_last_ip(config_id).await.unwrap();
         assert!(last_ip.is_some());
         assert_eq!(last_ip.unwrap(), "192.168.1.1");
         
-        let last_update = state_repository.get_last_update_time(&config_id).await.unwrap();
+        let last_update = state_repository.get_last_update_time(config_id).await.unwrap();
         assert!(last_update.is_some());
     }
     
@@ -204,8 +217,8 @@ mod integration_tests {
         
         #[async_trait]
         impl EventListener for TestEventListener {
-            async fn handle_event(&self, event: EventData) {
-                if event.event_type == EventType::ForceUpdateDns {
+            async fn handle_event(&self, event: Event) {
+                if event.event_type() == EventType::ForceUpdateDns {
                     self.event_received.store(true, Ordering::SeqCst);
                 }
             }
@@ -224,10 +237,7 @@ mod integration_tests {
         event_manager.register_listener(listener).await;
         
         // 發送事件
-        event_manager.publish(EventData {
-            event_type: EventType::ForceUpdateDns,
-            data: Some("test.example.com".to_string()),
-        }).await;
+        event_manager.force_update_dns(Some(&ConfigId::new("test_config"))).await;
         
         // 確保事件被處理
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
@@ -235,6 +245,177 @@ mod integration_tests {
         // 驗證事件接收
         assert!(event_received.load(Ordering::SeqCst));
     }
+    
+    // 測試更新結果事件通過全局訂閱推送
+    #[tokio::test]
+    async fn test_update_events_are_broadcast() {
+        let record = DnsRecord {
+            id: Some("test_record".to_string()),
+            name: "test.example.com".to_string(),
+            record_type: "A".to_string(),
+            content: "192.168.1.1".to_string(),
+            ttl: 120,
+            proxied: false,
+        };
+        
+        let dns_service = Arc::new(TestDnsService {
+            update_result: Ok(DnsUpdateResult { record: record.clone(), updated: true }),
+            get_record_result: Ok(record.clone()),
+            get_records_result: Ok(vec![record.clone()]),
+            create_record_result: Ok(record.clone()),
+            last_update_record: std::sync::Mutex::new(None),
+        });
+        let ip_service = Arc::new(TestIpService {
+            ipv4_result: Ok("192.168.1.1".to_string()),
+            ipv6_result: Err(DomainError::network("no ipv6")),
+        });
+        
+        let event_manager = Arc::new(EventManager::new());
+        let mut receiver = event_manager.subscribe_all();
+        
+        let config = DdnsConfig {
+            id: ConfigId::new("events_config"),
+            api_token: "test_token".to_string(),
+            zone_id: "test_zone".to_string(),
+            record_id: "test_record".to_string(),
+            record_name: "test.example.com".to_string(),
+            update_interval: 300,
+            ip_type: IpType::IPv4,
+            hooks: Default::default(),
+            source: Default::default(),
+            ipv6_loss: Default::default(),
+            prefix_delegation: None,
+            record_type: None,
+            content: None,
+            failover: None,
+            drift_check_interval: None,
+        };
+        
+        let ddns_service = DdnsApplicationService::new(
+            dns_service,
+            ip_service,
+            Arc::new(InMemoryStateRepository::new()),
+            config.clone(),
+        ).with_event_manager(event_manager.clone());
+        
+        ddns_service.update_dns_record().await.unwrap();
+        
+        match receiver.recv().await.unwrap() {
+            Event::IpChanged(event) => {
+                assert_eq!(event.record.config_id, ConfigId::new("events_config"));
+                assert_eq!(event.record.record_type, "A");
+                assert_eq!(event.old_ip, None);
+                assert_eq!(event.new_ip, "192.168.1.1");
+            },
+            other => panic!("應該收到 IpChanged，實際為 {:?}", other),
+        }
+        
+        let succeeded = receiver.recv().await.unwrap();
+        assert_eq!(succeeded.event_type(), EventType::UpdateSucceeded);
+        
+        // IP 未變更時不發布事件
+        ddns_service.update_dns_record().await.unwrap();
+        assert!(receiver.try_recv().is_err());
+        
+        // 更新失敗時發布 UpdateFailed
+        let mut ipv6_config = config;
+        ipv6_config.ip_type = IpType::IPv6;
+        let failing_service = DdnsApplicationService::new(
+            Arc::new(TestDnsService {
+                update_result: Ok(DnsUpdateResult { record: record.clone(), updated: true }),
+                get_record_result: Ok(record.clone()),
+                get_records_result: Ok(vec![record.clone()]),
+                create_record_result: Ok(record),
+                last_update_record: std::sync::Mutex::new(None),
+            }),
+            Arc::new(TestIpService {
+                ipv4_result: Ok("192.168.1.1".to_string()),
+                ipv6_result: Err(DomainError::network("no ipv6")),
+            }),
+            Arc::new(InMemoryStateRepository::new()),
+            ipv6_config,
+        ).with_event_manager(event_manager);
+        
+        assert!(failing_service.update_dns_record().await.is_err());
+        match receiver.recv().await.unwrap() {
+            Event::UpdateFailed(event) => {
+                assert_eq!(event.record.ip_type, IpType::IPv6);
+                assert_eq!(event.error_kind, "network");
+                assert!(event.error.contains("no ipv6"));
+            },
+            other => panic!("應該收到 UpdateFailed，實際為 {:?}", other),
+        }
+    }
+    
+    // 測試監聽器只接收關注的事件類型
+    #[tokio::test]
+    async fn test_listener_subscribes_to_subset() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        
+        struct ConfigOnlyListener {
+            received: Arc<AtomicUsize>,
+        }
+        
+        #[async_trait]
+        impl EventListener for ConfigOnlyListener {
+            async fn handle_event(&self, event: Event) {
+                assert_eq!(event, Event::ConfigChanged);
+                self.received.fetch_add(1, Ordering::SeqCst);
+            }
+            
+            fn interested_in(&self, event_type: &EventType) -> bool {
+                *event_type == EventType::ConfigChanged
+            }
+        }
+        
+        let event_manager = EventManager::new();
+        let received = Arc::new(AtomicUsize::new(0));
+        event_manager.register_listener(Arc::new(tokio::sync::Mutex::new(ConfigOnlyListener {
+            received: received.clone(),
+        }))).await;
+        
+        event_manager.force_update_all_dns().await;
+        event_manager.publish(Event::ConfigChanged).await;
+        event_manager.restart_ddns_service().await;
+        
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        assert_eq!(received.load(Ordering::SeqCst), 1);
+    }
+    
+    // 測試監聽器按發布順序逐個處理事件
+    #[tokio::test]
+    async fn test_listeners_receive_events_in_order() {
+        struct SlowListener {
+            received: Arc<std::sync::Mutex<Vec<EventType>>>,
+        }
+        
+        #[async_trait]
+        impl EventListener for SlowListener {
+            async fn handle_event(&self, event: Event) {
+                // 第一個事件處理較慢，後發布的事件不能搶先處理
+                if event == Event::ConfigChanged {
+                    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+                }
+                self.received.lock().unwrap().push(event.event_type());
+            }
+        }
+        
+        let event_manager = EventManager::new();
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        event_manager.register_listener(Arc::new(tokio::sync::Mutex::new(SlowListener {
+            received: received.clone(),
+        }))).await;
+        
+        event_manager.publish(Event::ConfigChanged).await;
+        event_manager.restart_ddns_service().await;
+        event_manager.force_update_all_dns().await;
+        
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![EventType::ConfigChanged, EventType::RestartDdnsService, EventType::ForceUpdateAll]
+        );
+    }
 }
 
 // 這將作為項目的測試入口點